@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::{states::Admin, utils::OxediumError};
+
+/// Fills a newly created Admin account: `signer` becomes admin and fee collector.
+/// `init` already refuses an existing Admin PDA; this also refuses an account
+/// that already has an admin, so the admin can never be overwritten.
+/// Returns `AdminAlreadyInitialized` error in that case.
+pub fn initialize_admin(admin: &mut Admin, signer: Pubkey) -> Result<()> {
+    if admin.pubkey != Pubkey::default() {
+        return Err(OxediumError::AdminAlreadyInitialized.into());
+    }
+
+    admin.pubkey = signer;
+    admin.collector = signer;
+
+    Ok(())
+}
+
+/// Records `new_admin` as the pending admin, overwriting any earlier proposal.
+/// The caller must already have passed `check_admin`.
+pub fn set_pending_admin(admin: &mut Admin, new_admin: Pubkey) {
+    admin.pending_admin = new_admin;
+}
+
+/// Completes a handoff started by `set_pending_admin`.
+/// Returns `InvalidAdmin` unless `signer` is the pending admin; on success the
+/// signer becomes admin, the pending slot is cleared and the outgoing admin is returned.
+pub fn accept_pending_admin(admin: &mut Admin, signer: &Pubkey) -> Result<Pubkey> {
+    if admin.pending_admin == Pubkey::default() || *signer != admin.pending_admin {
+        return Err(OxediumError::InvalidAdmin.into());
+    }
+
+    let old_admin = admin.pubkey;
+    admin.pubkey = admin.pending_admin;
+    admin.pending_admin = Pubkey::default();
+
+    Ok(old_admin)
+}
@@ -0,0 +1,48 @@
+use crate::utils::{OxediumError, U256};
+
+/// Blends a staker's entry basis with a new deposit, weighted by amount.
+///
+/// The basis is the deposit-weighted average of `cumulative_yield_per_lp` at
+/// the time each token was staked, so `(current − basis) × staked / SCALE` is
+/// the position's lifetime yield regardless of how many top-ups it took.
+/// Payouts don't use it; they keep snapshotting `last_cumulative_yield`.
+///
+/// Computed as `basis + (cumulative − basis) × amount / (staked + amount)`,
+/// rounding down; the accumulator never decreases, so the delta is non-negative.
+/// The product is taken in 256 bits, and the result never exceeds `cumulative`.
+///
+/// # Arguments
+/// * `avg_entry_cumulative_yield` - Current basis
+/// * `staked_before` - Stake the current basis covers
+/// * `cumulative_yield_per_lp` - Vault accumulator at deposit time
+/// * `amount` - Deposit being added
+///
+/// # Returns
+/// * `Result<u128, OxediumError>` - New basis
+pub fn blend_entry_basis(
+    avg_entry_cumulative_yield: u128,
+    staked_before: u64,
+    cumulative_yield_per_lp: u128,
+    amount: u64,
+) -> Result<u128, OxediumError> {
+    let total = (staked_before as u128)
+        .checked_add(amount as u128)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    if total == 0 {
+        return Ok(avg_entry_cumulative_yield);
+    }
+
+    let delta = cumulative_yield_per_lp
+        .checked_sub(avg_entry_cumulative_yield)
+        .ok_or(OxediumError::OverflowInSub)?;
+
+    let step = U256::from(delta)
+        .checked_mul(U256::from(amount))
+        .ok_or(OxediumError::OverflowInMul)?
+        / U256::from(total);
+    let step = step.as_u128();
+
+    avg_entry_cumulative_yield
+        .checked_add(step)
+        .ok_or(OxediumError::OverflowInAdd)
+}
@@ -1,6 +1,16 @@
-use crate::utils::OxediumError;
+use crate::utils::{math, OxediumError};
+
+/// Rounding direction for a single fee leg.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// `floor(amount * bps / 10_000)` — dust swaps may pay 0
+    Floor,
+    /// `ceil(amount * bps / 10_000)` — any nonzero fee on a nonzero amount is at least 1
+    CeilMin1,
+}
 
 /// Calculates the resulting amount after applying LP, protocol, and partner fees.
+/// Both fees round with `RoundingMode::CeilMin1`.
 /// 
 /// # Arguments
 /// * `amount` - The initial amount to apply fees on
@@ -15,34 +25,51 @@ pub fn calculate_fee_amount(
     lp_fee_bps: u64,
     protocol_fee_bps: u64,
 ) -> Result<(u64, u64, u64), OxediumError> {
+    calculate_fee_amount_with_rounding(
+        amount,
+        lp_fee_bps,
+        RoundingMode::CeilMin1,
+        protocol_fee_bps,
+        RoundingMode::CeilMin1,
+    )
+}
+
+/// Same as `calculate_fee_amount`, with an explicit rounding mode per fee leg
+/// (e.g. let the protocol fee floor to 0 on dust while the LP fee keeps min-1).
+pub fn calculate_fee_amount_with_rounding(
+    amount: u64,
+    lp_fee_bps: u64,
+    lp_rounding: RoundingMode,
+    protocol_fee_bps: u64,
+    protocol_rounding: RoundingMode,
+) -> Result<(u64, u64, u64), OxediumError> {
 
     // Calculate LP fee from the original amount
-    let lp_fee = fee(amount, lp_fee_bps)?;
+    let lp_fee = fee(amount, lp_fee_bps, lp_rounding)?;
 
     // Calculate protocol fee as a percentage of LP fee
-    let protocol_fee = fee(amount, protocol_fee_bps)?;
+    let protocol_fee = fee(amount, protocol_fee_bps, protocol_rounding)?;
     
     // Subtract LP fee, protocol fee, fee sequentially from the original amount
-    let amount_after_fee = amount
-        .checked_sub(lp_fee)
-        .and_then(|v| v.checked_sub(protocol_fee))
-        .ok_or(OxediumError::Overflow)?;
+    let amount_after_fee = math::sub(math::sub(amount, lp_fee)?, protocol_fee)?;
 
     // Return the remaining amount and all individual fees
     Ok((amount_after_fee, lp_fee, protocol_fee))
 }
 
-/// Helper function to calculate fee in basis points (bps) with ceiling rounding.
+/// Helper function to calculate fee in basis points (bps).
 ///
-/// Uses `ceil(amount * bps / 10_000)` to prevent fee evasion via dust amounts
-/// while remaining proportional for all meaningful values.
-fn fee(amount: u64, bps: u64) -> Result<u64, OxediumError> {
+/// `CeilMin1` uses `ceil(amount * bps / 10_000)` to prevent fee evasion via
+/// dust amounts while remaining proportional for all meaningful values;
+/// `Floor` never overcharges but lets dust through for free.
+fn fee(amount: u64, bps: u64, rounding: RoundingMode) -> Result<u64, OxediumError> {
     if bps == 0 || amount == 0 {
         return Ok(0);
     }
-    // ceil(amount * bps / 10_000) via (amount * bps + 9_999) / 10_000.
-    // amount <= u64::MAX, bps <= 10_000, so amount*bps+9_999 fits in u128.
-    let f = ((amount as u128 * bps as u128 + 9_999) / 10_000) as u64;
+    let f = match rounding {
+        RoundingMode::Floor => math::mul_div(amount, bps, 10_000)?,
+        // amount <= u64::MAX, bps <= 10_000, so amount*bps fits in u128.
+        RoundingMode::CeilMin1 => (amount as u128 * bps as u128).div_ceil(10_000) as u64,
+    };
     Ok(f.min(amount))
 }
-
@@ -1,4 +1,4 @@
-use crate::utils::{OxediumError, SCALE};
+use crate::utils::{OxediumError, SCALE, U256};
 
 /// Calculates the staker's yield based on cumulative yield per LP token.
 ///
@@ -20,18 +20,57 @@ pub fn calculate_staker_yield(
         None => return Ok(0), // If underflow occurs, return 0 yield
     };
 
-    // Multiply the yield per LP token by the staker's LP balance to get total yield
-    let total_yield = delta_yield_per_lp
-        .checked_mul(staker_balance as u128)
+    // Multiply in 256 bits: delta (< 2^128) * balance (< 2^64) always fits, so a
+    // product above u128::MAX saturates below instead of failing the whole call
+    let total_yield = U256::from(delta_yield_per_lp)
+        .checked_mul(U256::from(staker_balance))
         .ok_or(OxediumError::OverflowInMul)?;
 
     // Scale down the yield to normal units by dividing by SCALE
-    // SCALE is a non-zero constant so checked_div can only fail if total_yield == 0,
-    // which is handled by the Ok(0) path above; the ? is kept for exhaustiveness.
     let final_yield = total_yield
-        .checked_div(SCALE)
+        .checked_div(U256::from(SCALE))
         .ok_or(OxediumError::OverflowInDiv)?;
 
     // Saturating cast to u64: cap at u64::MAX rather than silently truncating
-    Ok(final_yield.min(u64::MAX as u128) as u64)
+    if final_yield > U256::from(u64::MAX) {
+        return Ok(u64::MAX);
+    }
+    Ok(final_yield.as_u64())
+}
+
+/// Checked variant of `calculate_staker_yield` for paths that move funds.
+///
+/// Instead of clamping, it returns `OverflowInSub` when `current_cumulative_yield`
+/// is behind `last_recorded_yield` and `OverflowInMul` when the scaled-down
+/// product does not fit in a u64 (`OverflowInDiv` if the division fails), so a broken accumulator reverts the transaction rather than
+/// paying out nothing (or everything).
+///
+/// # Arguments
+/// * `current_cumulative_yield` - The current cumulative yield per LP token (scaled by `SCALE`)
+/// * `staker_balance` - The amount of LP tokens the staker holds
+/// * `last_recorded_yield` - The cumulative yield per LP token at the last update for this staker
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - The amount of yield earned by the staker since the last update
+pub fn try_calculate_staker_yield(
+    current_cumulative_yield: u128,
+    staker_balance: u64,
+    last_recorded_yield: u128,
+) -> Result<u64, OxediumError> {
+    let delta_yield_per_lp = current_cumulative_yield
+        .checked_sub(last_recorded_yield)
+        .ok_or(OxediumError::OverflowInSub)?;
+
+    let total_yield = U256::from(delta_yield_per_lp)
+        .checked_mul(U256::from(staker_balance))
+        .ok_or(OxediumError::OverflowInMul)?;
+
+    let final_yield = total_yield
+        .checked_div(U256::from(SCALE))
+        .ok_or(OxediumError::OverflowInDiv)?;
+
+    if final_yield > U256::from(u64::MAX) {
+        return Err(OxediumError::OverflowInMul);
+    }
+    Ok(final_yield.as_u64())
 }
@@ -1,12 +1,388 @@
 use anchor_lang::prelude::*;
-use crate::{states::Admin, utils::OxediumError};
+use crate::{components::calculate_staker_yield, states::{Admin, Staker, Vault}, utils::{OxediumError, DUST_SWEEP_GRACE_PERIOD, MAX_DUST_THRESHOLD, MAX_MAX_AGE_PRICE, MIN_MAX_AGE_PRICE}};
 
 /// Checks if the given signer is the admin of the treasury.
 /// Returns `InvalidAdmin` error if not.
-pub fn check_admin(treasury_pda: &Admin, signer: &Signer) -> Result<()> {
+pub fn check_admin(treasury_pda: &Admin, signer: &impl Key) -> Result<()> {
     if signer.key() != treasury_pda.pubkey {
         return Err(OxediumError::InvalidAdmin.into());
     }
     
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Checks that the signer is the program's upgrade authority, the only key
+/// allowed to `init_admin`. An immutable program (no upgrade authority) can't
+/// be initialized this way either. Returns `InvalidAdmin` error otherwise.
+pub fn check_upgrade_authority(upgrade_authority: Option<Pubkey>, signer: &impl Key) -> Result<()> {
+    if upgrade_authority != Some(signer.key()) {
+        return Err(OxediumError::InvalidAdmin.into());
+    }
+
+    Ok(())
+}
+
+/// Checks if the given signer is the treasury's fee collector, the only key
+/// allowed to `collect`. Returns `InvalidCollector` error if not.
+pub fn check_collector(treasury_pda: &Admin, signer: &impl Key) -> Result<()> {
+    if signer.key() != treasury_pda.collector {
+        return Err(OxediumError::InvalidCollector.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the admin has not paused the program.
+/// Returns `ProgramPaused` error if it has.
+pub fn check_not_paused(admin: &Admin) -> Result<()> {
+    if admin.paused {
+        return Err(OxediumError::ProgramPaused.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the admin has not paused this vault.
+/// Returns `VaultPaused` error if it has.
+pub fn check_vault_active(vault: &Vault) -> Result<()> {
+    if vault.paused {
+        return Err(OxediumError::VaultPaused.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the vault account belongs to `mint`. The vault PDA is already
+/// derived from the mint's seeds; this guards the stored `token_mint` against
+/// a reinitialized account or a change in that derivation.
+/// Returns `InvalidVault` error if they differ.
+pub fn check_vault_mint(vault: &Vault, mint: &Pubkey) -> Result<()> {
+    if vault.token_mint != *mint {
+        return Err(OxediumError::InvalidVault.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a vault's LP principal stays within its deposit cap.
+/// A `max_total_deposits` of 0 means unlimited.
+/// Returns `DepositCapExceeded` error if the cap would be exceeded.
+pub fn check_deposit_cap(new_initial_balance: u64, max_total_deposits: u64) -> Result<()> {
+    if max_total_deposits != 0 && new_initial_balance > max_total_deposits {
+        return Err(OxediumError::DepositCapExceeded.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a new staker's first deposit meets the vault minimum.
+/// Top-ups on an existing position (`staked_before > 0`) are exempt.
+/// Returns `BelowMinimumStake` error if a fresh deposit is too small.
+pub fn check_min_stake(staked_before: u64, amount: u64, min_stake_amount: u64) -> Result<()> {
+    if staked_before == 0 && amount < min_stake_amount {
+        return Err(OxediumError::BelowMinimumStake.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a Staker position belongs to `owner` and to `vault`, the same
+/// pair the staker instructions' account constraints enforce.
+/// Returns `InvalidStaker` error for another owner's position and
+/// `InvalidVault` error for a position in another vault.
+pub fn check_staker_position(staker: &Staker, owner: &Pubkey, vault: &Pubkey) -> Result<()> {
+    if staker.owner != *owner {
+        return Err(OxediumError::InvalidStaker.into());
+    }
+    if staker.vault != *vault {
+        return Err(OxediumError::InvalidVault.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that an unstake doesn't take out more than the position holds.
+/// Returns `InsufficientStake` error if `amount` exceeds `staked_amount`.
+pub fn check_unstake_amount(staked_amount: u64, amount: u64) -> Result<()> {
+    if amount > staked_amount {
+        return Err(OxediumError::InsufficientStake.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a vault pays unstakes out instantly. Once it has a
+/// `withdraw_delay`, every exit goes through `request_unstake`: a size cap per
+/// call could be dodged by splitting one exit across calls.
+/// Returns `WithdrawQueueRequired` error otherwise.
+pub fn check_instant_unstake(withdraw_delay: i64) -> Result<()> {
+    if withdraw_delay > 0 {
+        return Err(OxediumError::WithdrawQueueRequired.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that an oracle update reports a nonzero confidence interval when the
+/// vault requires it. Real Pyth feeds never report `conf == 0`.
+/// Returns `SuspiciousOracle` error if it does.
+pub fn check_oracle_conf(conf: u64, require_nonzero_conf: bool) -> Result<()> {
+    if require_nonzero_conf && conf == 0 {
+        return Err(OxediumError::SuspiciousOracle.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that an oracle's confidence, as bps of its price, stays within
+/// `max_conf_bps`. The ratio is uncapped, so a conf above the price is caught.
+/// A `max_conf_bps` of 0 disables the check; non-positive prices are left to
+/// the `InvalidPrice` guard in `raw_amount_out`.
+/// Returns `OracleTooUncertain` error if the ratio exceeds the bound.
+pub fn check_conf_ratio(price: i64, conf: u64, max_conf_bps: u64) -> Result<()> {
+    if max_conf_bps == 0 || price <= 0 {
+        return Ok(());
+    }
+
+    // conf / price > max_conf_bps / 10_000, cross-multiplied to avoid flooring
+    if (conf as u128) * 10_000 > (max_conf_bps as u128) * (price as u128) {
+        return Err(OxediumError::OracleTooUncertain.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a swap's input and output mints differ.
+/// Returns `SameMint` error if they are equal — both vault PDAs would
+/// otherwise resolve to the same account.
+pub fn check_distinct_mints(mint_in: &Pubkey, mint_out: &Pubkey) -> Result<()> {
+    if mint_in == mint_out {
+        return Err(OxediumError::SameMint.into());
+    }
+
+    Ok(())
+}
+
+/// Checks the fee and oracle parameters shared by `init_vault` and `update_vault`.
+/// Returns `FeeExceeds` if a fee is above its ceiling, `InvalidMaxAgePrice`
+/// if `max_age_price` is outside `MIN_MAX_AGE_PRICE..=MAX_MAX_AGE_PRICE` and
+/// `InvalidImpactThreshold` if the impact curve
+/// threshold is not below 100%. `max_protocol_fee_bps` shares the protocol fee
+/// ceiling and may not sit below `protocol_fee_bps`. `max_referral_fee_bps` is a
+/// share of the protocol fee and may not exceed all of it.
+pub fn check_vault_config(
+    base_fee_bps: u64,
+    protocol_fee_bps: u64,
+    max_protocol_fee_bps: u64,
+    max_exit_fee_bps: u64,
+    max_age_price: u64,
+    impact_threshold_bps: u64,
+    max_referral_fee_bps: u64,
+) -> Result<()> {
+    require!(base_fee_bps <= 1_000, OxediumError::FeeExceeds);
+    require!(protocol_fee_bps <= 500, OxediumError::FeeExceeds);
+    require!(max_protocol_fee_bps <= 500, OxediumError::FeeExceeds);
+    require!(max_protocol_fee_bps >= protocol_fee_bps, OxediumError::FeeExceeds);
+    require!(max_exit_fee_bps <= 1_000, OxediumError::FeeExceeds);
+    require!(
+        (MIN_MAX_AGE_PRICE..=MAX_MAX_AGE_PRICE).contains(&max_age_price),
+        OxediumError::InvalidMaxAgePrice
+    );
+    require!(impact_threshold_bps < 10_000, OxediumError::InvalidImpactThreshold);
+    require!(max_referral_fee_bps <= 10_000, OxediumError::FeeExceeds);
+
+    Ok(())
+}
+
+/// Checks that an oracle price is neither future-dated nor older than `max_age_price`.
+/// Returns `OracleDataTooOld` error otherwise.
+pub fn check_price_age(publish_time: i64, max_age_price: u64, current_timestamp: i64) -> Result<()> {
+    if publish_time > current_timestamp {
+        return Err(OxediumError::OracleDataTooOld.into());
+    }
+
+    let age = current_timestamp - publish_time;
+    if age > max_age_price as i64 {
+        msg!("Price feed stale by {} seconds", age);
+        return Err(OxediumError::OracleDataTooOld.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a Staker position can be closed without losing funds: nothing
+/// staked, nothing pending or queued, and no yield accrued since the last snapshot.
+/// Returns `StakerNotEmpty` error if any of these is non-zero.
+pub fn check_staker_closable(staker: &Staker, cumulative_yield_per_lp: u128) -> Result<()> {
+    let unsnapshotted_yield = calculate_staker_yield(
+        cumulative_yield_per_lp,
+        staker.staked_amount,
+        staker.last_cumulative_yield,
+    )?;
+
+    require!(
+        staker.staked_amount == 0 && staker.pending_claim == 0 && staker.pending_unstake_amount == 0 && unsnapshotted_yield == 0,
+        OxediumError::StakerNotEmpty
+    );
+
+    Ok(())
+}
+
+/// Checks that the net amount paid out meets the caller's minimum.
+/// Returns `HighSlippage` error if it falls short.
+pub fn check_min_out(amount_out: u64, min_out: u64) -> Result<()> {
+    if amount_out < min_out {
+        return Err(OxediumError::HighSlippage.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a swap pays out at least the vault's `min_swap_output`, so
+/// fee-dominated dust swaps revert instead of taking the input for nothing.
+/// Returns `OutputTooSmall` error if it falls short; 0 disables the check.
+pub fn check_min_swap_output(net_amount_out: u64, min_swap_output: u64) -> Result<()> {
+    if net_amount_out < min_swap_output {
+        return Err(OxediumError::OutputTooSmall.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the utilization curve adds at most `max_impact_bps` on top of
+/// the base fee, independently of the trader's `minimum_out`.
+/// Returns `ImpactTooHigh` error otherwise; 10 000 or more disables the check.
+pub fn check_max_impact(liquidity_fee_bps: u64, base_fee_bps: u64, max_impact_bps: u64) -> Result<()> {
+    if liquidity_fee_bps.saturating_sub(base_fee_bps) > max_impact_bps {
+        return Err(OxediumError::ImpactTooHigh.into());
+    }
+
+    Ok(())
+}
+
+/// Checks the opt-in lock-up parameters of a vault.
+/// Returns `InvalidLockup` for a negative duration and `FeeExceeds` for a
+/// penalty above 10%, the same ceiling as the health-based exit fee.
+pub fn check_lockup_config(lockup_duration: i64, max_lockup_penalty_bps: u64) -> Result<()> {
+    require!(lockup_duration >= 0, OxediumError::InvalidLockup);
+    require!(max_lockup_penalty_bps <= 1_000, OxediumError::FeeExceeds);
+
+    Ok(())
+}
+
+/// Checks that the two oracles of a swap were published close enough together
+/// for their cross price to be meaningful, even if each is individually fresh.
+/// A `max_oracle_skew` of 0 or less disables the check.
+/// Returns `OracleSkewTooLarge` error if the gap exceeds the bound.
+pub fn check_oracle_skew(publish_time_in: i64, publish_time_out: i64, max_oracle_skew: i64) -> Result<()> {
+    if max_oracle_skew <= 0 {
+        return Ok(());
+    }
+
+    if publish_time_in.abs_diff(publish_time_out) > max_oracle_skew as u64 {
+        return Err(OxediumError::OracleSkewTooLarge.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that an oracle's `feed_id` matches the one a vault expects, so a
+/// vault can't be wired to (or later served) another asset's price.
+/// Returns `InvalidPythAccount` on mismatch; an all-zero `expected` skips the check.
+pub fn check_feed_id(feed_id: &[u8; 32], expected: &[u8; 32]) -> Result<()> {
+    if *expected != [0u8; 32] && feed_id != expected {
+        return Err(OxediumError::InvalidPythAccount.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that the vault's tracked balance still covers what it owes: the
+/// fee collector's `protocol_yield` and LP yield not yet claimed or compounded.
+/// Run on the output vault after a swap has been settled.
+/// Returns `SolvencyViolation` error if the payout dipped into either.
+pub fn check_solvency(vault: &Vault) -> Result<()> {
+    let obligations = vault.protocol_yield as u128 + vault.unclaimed_lp_yield as u128;
+    if (vault.current_balance as u128) < obligations {
+        return Err(OxediumError::SolvencyViolation.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a staker's previous staking, unstaking, claim or compound was at
+/// least `action_cooldown` seconds ago, so a position can't enter and leave
+/// around a single yield distribution. 0 disables the check.
+/// Returns `CooldownActive` error otherwise.
+pub fn check_action_cooldown(last_action_ts: i64, action_cooldown: i64, now: i64) -> Result<()> {
+    if action_cooldown > 0 && now < last_action_ts.saturating_add(action_cooldown) {
+        return Err(OxediumError::CooldownActive.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a position's last action is at least `min_stake_age` seconds
+/// old before it claims or compounds, so a stake placed just ahead of a large
+/// swap can't take that swap's fees straight out. 0 disables the check.
+/// Returns `StakeTooYoung` error otherwise.
+pub fn check_min_stake_age(last_action_ts: i64, min_stake_age: i64, now: i64) -> Result<()> {
+    if min_stake_age > 0 && now < last_action_ts.saturating_add(min_stake_age) {
+        return Err(OxediumError::StakeTooYoung.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `sweep_dust` may forfeit a position: nothing staked or queued,
+/// less than `dust_threshold` pending, and no owner action for `DUST_SWEEP_GRACE_PERIOD`.
+/// Returns `InvalidDustThreshold` if `dust_threshold` exceeds `MAX_DUST_THRESHOLD`,
+/// `StakerNotDust` or `SweepGracePeriodActive` error otherwise.
+pub fn check_dust_sweepable(staker: &Staker, dust_threshold: u64, now: i64) -> Result<()> {
+    if dust_threshold > MAX_DUST_THRESHOLD {
+        return Err(OxediumError::InvalidDustThreshold.into());
+    }
+
+    if staker.staked_amount > 0 || staker.pending_unstake_amount > 0 || staker.pending_claim >= dust_threshold {
+        return Err(OxediumError::StakerNotDust.into());
+    }
+
+    if now < staker.last_action_ts.saturating_add(DUST_SWEEP_GRACE_PERIOD) {
+        return Err(OxediumError::SweepGracePeriodActive.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `close_vault` may delete a vault: no LP principal, no stakers,
+/// no queued withdrawals, no uncollected protocol fees and no unclaimed LP yield.
+/// `current_balance` may hold dust up to `dust_floor`, which is swept on close.
+/// Returns `VaultNotEmpty` error otherwise.
+pub fn check_vault_closable(vault: &Vault) -> Result<()> {
+    if vault.initial_balance > 0
+        || vault.total_stakers > 0
+        || vault.reserved_for_withdrawals > 0
+        || vault.protocol_yield > 0
+        || vault.unclaimed_lp_yield > 0
+        || vault.current_balance > vault.dust_floor
+    {
+        return Err(OxediumError::VaultNotEmpty.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that a batch instruction got one amount per group of `group_len`
+/// accounts in `remaining_accounts`, and at least one group.
+/// Returns `InvalidVault` error for a malformed account list and
+/// `BatchLengthMismatch` error if the amounts don't line up with it.
+pub fn check_batch_amounts(accounts_len: usize, group_len: usize, amounts_len: usize) -> Result<()> {
+    if accounts_len == 0 || !accounts_len.is_multiple_of(group_len) {
+        return Err(OxediumError::InvalidVault.into());
+    }
+    if accounts_len / group_len != amounts_len {
+        return Err(OxediumError::BatchLengthMismatch.into());
+    }
+
+    Ok(())
+}
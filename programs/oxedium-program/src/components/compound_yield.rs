@@ -0,0 +1,38 @@
+use crate::{components::{blend_entry_basis, try_calculate_staker_yield, release_lp_yield, update_staker_count}, states::{Staker, Vault}, utils::OxediumError};
+
+/// Restakes a staker's accrued yield in place.
+///
+/// Equivalent to `claim` followed by `staking` of the same amount: the yield
+/// tokens never leave the vault ATA, so `current_balance` is unchanged while
+/// `initial_balance` and `staked_amount` grow by the compounded amount.
+///
+/// # Arguments
+/// * `staker` - Staker position (mutated)
+/// * `vault` - Vault the position belongs to (mutated)
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Amount compounded into the stake
+pub fn compound_yield(staker: &mut Staker, vault: &mut Vault) -> Result<u64, OxediumError> {
+    let cumulative_yield_per_lp: u128 = vault.cumulative_yield_per_lp;
+
+    let amount: u64 = try_calculate_staker_yield(cumulative_yield_per_lp, staker.staked_amount, staker.last_cumulative_yield)?
+        .checked_add(staker.pending_claim)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    staker.last_cumulative_yield = cumulative_yield_per_lp;
+    staker.pending_claim = 0;
+    let staked_before = staker.staked_amount;
+    staker.avg_entry_cumulative_yield =
+        blend_entry_basis(staker.avg_entry_cumulative_yield, staked_before, cumulative_yield_per_lp, amount)?;
+    staker.staked_amount = staker.staked_amount
+        .checked_add(amount)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    update_staker_count(vault, staked_before, staker.staked_amount)?;
+
+    vault.initial_balance = vault.initial_balance
+        .checked_add(amount)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    release_lp_yield(vault, amount);
+
+    Ok(amount)
+}
@@ -1,17 +1,37 @@
+use anchor_lang::prelude::msg;
 use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
 
 use crate::{
-    components::{calculate_fee_amount, fees_setting, raw_amount_out},
+    components::{calculate_fee_amount, calculate_fee_amount_with_rounding, fees_setting, RoundingMode, impact_curve_bps, liquidity_fee, raw_amount_out_with_inversion, select_price},
     states::Vault,
-    utils::OxediumError,
+    utils::{OxediumError, SCALE},
 };
 
 pub struct SwapMathResult {
+    /// Liquidity fee: the base fee plus the utilization curve's impact
     pub swap_fee_bps: u64,
+    /// Base fee from vault health alone, before the utilization curve
+    pub base_fee_bps: u64,
+    /// Output vault's configured `base_fee_bps`. This and the next three fields
+    /// break `swap_fee_bps` down by source and sum to it
+    pub vault_base_fee_bps: u64,
+    /// Imbalance curve's addition on top of the vault base fee (`fees_setting`)
+    pub imbalance_fee_bps: u64,
+    /// Liquidity-impact curve's addition on top of `base_fee_bps`
+    pub liquidity_impact_bps: u64,
+    /// Oracle-confidence addition. Confidence only gates swaps (see
+    /// `preview_swap`) and adds no fee, so this is always 0
+    pub oracle_conf_bps: u64,
     pub raw_amount_out: u64,
     pub net_amount_out: u64,
     pub lp_fee_amount: u64,
     pub protocol_fee_amount: u64,
+    /// Realized price: `net_amount_out * SCALE / amount_in`
+    pub effective_price_fp: u128,
+    /// Shortfall of the realized price against the oracle-implied one, in bps
+    pub price_impact_bps: u64,
+    /// Liquidity fee was cut back so the total fee stays at 100%; see `log_fee_clamp`
+    pub fee_clamped: bool,
 }
 
 pub fn compute_swap_math(
@@ -23,72 +43,126 @@ pub fn compute_swap_math(
     vault_in: &Vault,
     vault_out: &Vault
 ) -> Result<SwapMathResult, OxediumError> {
-    let swap_fee_bps = fees_setting(&vault_in, &vault_out);
-
-    let protocol_fee_bps = vault_out.protocol_fee_bps;
-
-    let raw_out = raw_amount_out(amount_in, decimals_in, decimals_out, oracle_in, oracle_out)?;
-
-    // Liquidity-impact fee: flat base fee up to 10% utilization,
-    // then a quadratic curve that grows aggressively from 10% to 100%.
-    //
-    // utilization_bps = raw_out * 10_000 / current_balance  (0..10_000)
-    //
-    // Below THRESHOLD (10%):
-    //   liquidity_fee_bps = swap_fee_bps
-    //
-    // Above THRESHOLD:
-    //   adj = (utilization - 10%) normalised to 0..10_000
-    //   curved = adj² / 10_000
-    //   liquidity_fee_bps = swap_fee_bps + (MAX_FEE - swap_fee_bps) * curved / 10_000
-    //
-    // Examples (swap_fee_bps = 30):
-    //   10%  → 30 bps   (base only)
-    //   20%  → ~148 bps
-    //   50%  → ~1 997 bps (~20%)
-    //   70%  → ~4 475 bps (~45%)
-    //   100% → 10 000 bps (100%)
-    const MAX_FEE_BPS: u64 = 10_000;
-    const IMPACT_THRESHOLD_BPS: u64 = 1_000; // 10% — curve starts here
-
-    let liquidity_fee_bps = if vault_out.current_balance == 0 {
-        MAX_FEE_BPS
+    let swap_fee_bps = fees_setting(vault_in, vault_out);
+
+    let oracle_in = select_price(oracle_in, vault_in.use_ema_price);
+    let oracle_out = select_price(oracle_out, vault_out.use_ema_price);
+
+    let raw_out = raw_amount_out_with_inversion(
+        amount_in,
+        decimals_in,
+        decimals_out,
+        oracle_in,
+        oracle_out,
+        vault_in.invert_price,
+        vault_out.invert_price,
+    )?;
+
+    // A dust balance is effectively empty: any swap would sit at 100% utilization
+    // and be priced at MAX_FEE, so fail cleanly instead of charging a nonsensical fee.
+    if vault_out.current_balance < vault_out.dust_floor {
+        return Err(OxediumError::InsufficientLiquidity);
+    }
+
+    // Liquidity-impact fee: flat base fee up to the vault's impact threshold,
+    // then a quadratic curve up to 100% at full utilization (see `liquidity_fee`)
+    let impact_threshold_bps = vault_out.impact_threshold_bps;
+    let utilization_bps = vault_out.utilization_bps(raw_out);
+    let curved = impact_curve_bps(utilization_bps, impact_threshold_bps);
+    let liquidity_fee_bps = liquidity_fee(swap_fee_bps, utilization_bps, impact_threshold_bps);
+
+    // Optionally let the protocol fee ride the same curve, from protocol_fee_bps
+    // at the threshold up to max_protocol_fee_bps at 100% utilization.
+    let protocol_fee_bps = if vault_out.protocol_fee_follows_curve {
+        let span = vault_out.max_protocol_fee_bps.saturating_sub(vault_out.protocol_fee_bps);
+        vault_out.protocol_fee_bps + span * curved / 10_000
     } else {
-        // utilization in bps, capped at 10_000
-        let utilization_bps = ((raw_out as u128 * 10_000) / vault_out.current_balance as u128)
-            .min(10_000) as u64;
-
-        if utilization_bps <= IMPACT_THRESHOLD_BPS {
-            swap_fee_bps
-        } else {
-            // shift: map 10%..100% → 0..10_000
-            let adj = (utilization_bps - IMPACT_THRESHOLD_BPS) * 10_000
-                / (MAX_FEE_BPS - IMPACT_THRESHOLD_BPS);
-
-            // quadratic: adj² / 10_000  →  0..10_000
-            let curved = adj * adj / 10_000;
-
-            // scale from swap_fee_bps up to MAX_FEE_BPS
-            swap_fee_bps + (MAX_FEE_BPS - swap_fee_bps) * curved / 10_000
-        }
+        vault_out.protocol_fee_bps
     };
 
     if vault_out.current_balance < raw_out {
-        return Err(OxediumError::InsufficientLiquidity.into());
+        return Err(OxediumError::InsufficientLiquidity);
     }
 
-    if liquidity_fee_bps + protocol_fee_bps > 10_000 {
-        return Err(OxediumError::FeeExceeds.into());
+    // check_vault_config caps the protocol fee far below this; kept so the
+    // clamp below can never go negative
+    if protocol_fee_bps >= 10_000 {
+        return Err(OxediumError::FeeExceeds);
     }
 
-    let (after_fee, lp_fee, protocol_fee) =
-        calculate_fee_amount(raw_out, liquidity_fee_bps, protocol_fee_bps)?;
+    // Clamp rather than revert: the trade stays possible, the liquidity fee
+    // gives way so the total never exceeds 100%, and min_out protects the trader
+    let fee_clamped = liquidity_fee_bps + protocol_fee_bps > 10_000;
+    let liquidity_fee_bps = if fee_clamped {
+        10_000 - protocol_fee_bps
+    } else {
+        liquidity_fee_bps
+    };
+
+    // At exactly 100% two rounded-up legs could overshoot raw_out by one unit;
+    // flooring the LP leg makes them sum to raw_out instead
+    let (after_fee, lp_fee, protocol_fee) = if fee_clamped {
+        calculate_fee_amount_with_rounding(raw_out, liquidity_fee_bps, RoundingMode::Floor, protocol_fee_bps, RoundingMode::CeilMin1)?
+    } else {
+        calculate_fee_amount(raw_out, liquidity_fee_bps, protocol_fee_bps)?
+    };
+
+    // Absolute protocol fee floor, limited to what the LP fee leaves of raw_out
+    // so the fees never exceed the output
+    let floored_fee = vault_out.min_protocol_fee.min(raw_out - lp_fee);
+    let (after_fee, protocol_fee) = if protocol_fee < floored_fee {
+        (raw_out - lp_fee - floored_fee, floored_fee)
+    } else {
+        (after_fee, protocol_fee)
+    };
+
+    // Both prices share amount_in as denominator, so the deviation reduces to
+    // (raw_out - net_out) / raw_out: every fee leg plus the liquidity curve.
+    let effective_price_fp = if amount_in == 0 {
+        0
+    } else {
+        after_fee as u128 * SCALE / amount_in as u128
+    };
+    let price_impact_bps = if raw_out == 0 {
+        0
+    } else {
+        ((raw_out - after_fee) as u128 * 10_000 / raw_out as u128) as u64
+    };
+
+    // A clamp takes the fee back out of the impact curve first, then the
+    // imbalance curve, so the breakdown still sums to the charged fee
+    let health_fee_bps = swap_fee_bps.min(liquidity_fee_bps);
+    let vault_base_fee_bps = vault_out.base_fee_bps.min(health_fee_bps);
 
     Ok(SwapMathResult {
         swap_fee_bps: liquidity_fee_bps,
+        base_fee_bps: swap_fee_bps,
+        vault_base_fee_bps,
+        imbalance_fee_bps: health_fee_bps - vault_base_fee_bps,
+        liquidity_impact_bps: liquidity_fee_bps - health_fee_bps,
+        oracle_conf_bps: 0,
         raw_amount_out: raw_out,
         net_amount_out: after_fee,
         lp_fee_amount: lp_fee,
         protocol_fee_amount: protocol_fee,
+        effective_price_fp,
+        price_impact_bps,
+        fee_clamped,
     })
 }
+
+/// Logs a swap whose liquidity fee was clamped so the total fee stays at 100%.
+///
+/// Called once per executed swap by the instruction handlers, not from
+/// `compute_swap_math`, which `max_fillable_amount_in` runs many times per swap.
+/// A clamped liquidity fee leaves exactly `10_000 - swap_fee_bps` for the protocol.
+pub fn log_fee_clamp(result: &SwapMathResult) {
+    if !result.fee_clamped {
+        return;
+    }
+
+    msg!(
+        "Swap fee clamped to 100% {{base_fee_bps: {}, clamped_liquidity_fee_bps: {}, protocol_fee_bps: {}, raw_amount_out: {}}}",
+        result.base_fee_bps, result.swap_fee_bps, 10_000 - result.swap_fee_bps, result.raw_amount_out
+    );
+}
@@ -0,0 +1,53 @@
+use crate::{components::{blend_entry_basis, extend_unlock_ts, snapshot_yield, update_staker_count}, events::StakingEvent, states::{Staker, Vault}, utils::{math, OxediumError}};
+
+/// Books a deposit of `amount` that has already reached the vault ATA.
+///
+/// Snapshots the position's yield, blends its entry basis, grows the stake,
+/// re-locks it under the vault's lock-up and grows both vault balances.
+/// Shared by `staking` and `stake_many`; the caller checks the minimum stake
+/// and the deposit cap.
+///
+/// # Arguments
+/// * `staker` - Staker position (mutated)
+/// * `vault` - Vault the position belongs to (mutated)
+/// * `amount` - Tokens received by the vault ATA
+/// * `now` - Current unix timestamp
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Yield earned since the previous snapshot
+pub fn credit_stake(staker: &mut Staker, vault: &mut Vault, amount: u64, now: i64) -> Result<u64, OxediumError> {
+    let cumulative_yield: u128 = vault.cumulative_yield_per_lp;
+    let staked_before: u64 = staker.staked_amount;
+
+    let earned: u64 = snapshot_yield(staker, cumulative_yield)?;
+
+    staker.avg_entry_cumulative_yield = blend_entry_basis(staker.avg_entry_cumulative_yield, staked_before, cumulative_yield, amount)?;
+    staker.staked_amount = math::add(staker.staked_amount, amount)?;
+    staker.stake_unlock_ts = extend_unlock_ts(vault, staker.stake_unlock_ts, now)?;
+    staker.last_action_ts = now;
+    // staked_amount is 0 both for a freshly initialized PDA and for one that fully
+    // unstaked earlier, so re-entry is counted but repeated top-ups are not
+    update_staker_count(vault, staked_before, staker.staked_amount)?;
+
+    vault.initial_balance = math::add(vault.initial_balance, amount)?;
+    vault.current_balance = math::add(vault.current_balance, amount)?;
+
+    Ok(earned)
+}
+
+/// The `StakingEvent` `staking` and `stake_many` emit once `credit_stake` has
+/// booked a deposit of `amount`: the resulting position, its share of the
+/// vault's LP principal and its entry basis.
+pub fn staking_event(staker: &Staker, vault: &Vault, amount: u64, timestamp: i64, slot: u64) -> StakingEvent {
+    StakingEvent {
+        user: staker.owner,
+        mint: vault.token_mint,
+        amount,
+        staked_amount: staker.staked_amount,
+        share_bps: vault.share_bps(staker.staked_amount),
+        entry_cumulative_yield: staker.last_cumulative_yield,
+        avg_entry_cumulative_yield: staker.avg_entry_cumulative_yield,
+        timestamp,
+        slot,
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{associated_token::get_associated_token_address_with_program_id, token};
+
+use crate::utils::{ADMIN_SEED, OXEDIUM_SEED, OXE_GLOBAL_SEED, VAULT_SEED};
+
+/// Every derived address a client needs to build a `swap` transaction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq)]
+pub struct SwapAccounts {
+    pub vault_pda_in: Pubkey,
+    pub vault_pda_out: Pubkey,
+    pub vault_ata_in: Pubkey,
+    pub vault_ata_out: Pubkey,
+    pub signer_ata_in: Pubkey,
+    pub signer_ata_out: Pubkey,
+    pub oxe_global_pda: Pubkey,
+    pub admin_pda: Pubkey,
+}
+
+/// Derives the PDAs and associated token accounts used by `swap` for two
+/// classic SPL Token mints.
+///
+/// Vault ATAs are the associated token accounts created by `staking`
+/// (authority = vault PDA); signer ATAs are the user's associated token accounts.
+///
+/// # Arguments
+/// * `signer` - Wallet performing the swap
+/// * `mint_in` - Mint of the token being sold
+/// * `mint_out` - Mint of the token being bought
+pub fn derive_swap_accounts(signer: &Pubkey, mint_in: &Pubkey, mint_out: &Pubkey) -> SwapAccounts {
+    derive_swap_accounts_with_programs(signer, mint_in, mint_out, &token::ID, &token::ID)
+}
+
+/// Same as `derive_swap_accounts`, with the owning token program of each mint
+/// (SPL Token or Token-2022), since it is part of the ATA address.
+pub fn derive_swap_accounts_with_programs(
+    signer: &Pubkey,
+    mint_in: &Pubkey,
+    mint_out: &Pubkey,
+    token_program_in: &Pubkey,
+    token_program_out: &Pubkey,
+) -> SwapAccounts {
+    let (vault_pda_in, _) =
+        Pubkey::find_program_address(&[VAULT_SEED.as_bytes(), mint_in.as_ref()], &crate::ID);
+    let (vault_pda_out, _) =
+        Pubkey::find_program_address(&[VAULT_SEED.as_bytes(), mint_out.as_ref()], &crate::ID);
+    let (oxe_global_pda, _) = Pubkey::find_program_address(
+        &[OXEDIUM_SEED.as_bytes(), OXE_GLOBAL_SEED.as_bytes()],
+        &crate::ID,
+    );
+    let (admin_pda, _) =
+        Pubkey::find_program_address(&[OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], &crate::ID);
+
+    SwapAccounts {
+        vault_pda_in,
+        vault_pda_out,
+        vault_ata_in: get_associated_token_address_with_program_id(&vault_pda_in, mint_in, token_program_in),
+        vault_ata_out: get_associated_token_address_with_program_id(&vault_pda_out, mint_out, token_program_out),
+        signer_ata_in: get_associated_token_address_with_program_id(signer, mint_in, token_program_in),
+        signer_ata_out: get_associated_token_address_with_program_id(signer, mint_out, token_program_out),
+        oxe_global_pda,
+        admin_pda,
+    }
+}
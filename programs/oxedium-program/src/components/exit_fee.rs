@@ -0,0 +1,26 @@
+/// Health-based exit fee in bps for unstaking from a vault at `health_bps`.
+///
+/// Quadratic in the health deficit, so small drawdowns cost almost nothing
+/// and deep ones approach `max_exit_fee_bps`:
+///
+/// ```text
+/// deficit = 10_000 − health_bps            (0..10_000)
+/// curved  = deficit² / 10_000              (0..10_000)
+/// fee     = max_exit_fee_bps × curved / 10_000
+/// ```
+///
+/// Works in bps throughout, so the fee moves smoothly with health instead of
+/// in whole-percent steps. Health above 100% charges nothing.
+///
+/// # Arguments
+/// * `health_bps` - Vault health from `Vault::health_bps`
+/// * `max_exit_fee_bps` - Fee at 0% health
+///
+/// # Returns
+/// * `u64` - Exit fee in bps
+pub fn exit_fee_bps(health_bps: u64, max_exit_fee_bps: u64) -> u64 {
+    let deficit = 10_000u128.saturating_sub(health_bps as u128);
+    let curved = deficit * deficit / 10_000;
+
+    (max_exit_fee_bps as u128 * curved / 10_000) as u64
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::{PriceFeedMessage, PriceUpdateV2};
+
+use crate::{components::{check_feed_id, check_price_age}, states::Vault, utils::OxediumError};
+
+/// Picks the oracle a swap leg is priced with: `primary` while it is fresh,
+/// otherwise `fallback` if one was supplied and is itself fresh.
+///
+/// # Arguments
+/// * `primary` - Price from the vault's `pyth_price_account`
+/// * `fallback` - Price from its `fallback_pyth_price_account`, if passed in
+/// * `max_age_price` - The vault's staleness bound, applied to both
+/// * `now_ts` - Current unix timestamp
+///
+/// # Returns
+/// * `Result<(PriceFeedMessage, bool)>` - The price to use and whether it is the
+///   fallback; `OracleDataTooOld` if neither is fresh
+pub fn choose_oracle(
+    primary: PriceFeedMessage,
+    fallback: Option<PriceFeedMessage>,
+    max_age_price: u64,
+    now_ts: i64,
+) -> Result<(PriceFeedMessage, bool)> {
+    let primary_age = check_price_age(primary.publish_time, max_age_price, now_ts);
+
+    match (primary_age, fallback) {
+        (Ok(()), _) => Ok((primary, false)),
+        (Err(_), Some(fallback)) => {
+            check_price_age(fallback.publish_time, max_age_price, now_ts)?;
+            Ok((fallback, true))
+        }
+        (Err(err), None) => Err(err),
+    }
+}
+
+/// Reads `vault.fallback_pyth_price_account` from `remaining_accounts`, if the
+/// vault has one configured and it was passed. The account must be a Pyth price
+/// update carrying the vault's `feed_id`.
+pub fn load_fallback_oracle(remaining_accounts: &[AccountInfo], vault: &Vault) -> Result<Option<PriceFeedMessage>> {
+    if vault.fallback_pyth_price_account == Pubkey::default() {
+        return Ok(None);
+    }
+    let Some(info) = remaining_accounts.iter().find(|info| info.key() == vault.fallback_pyth_price_account) else {
+        return Ok(None);
+    };
+
+    require!(info.owner == &pyth_solana_receiver_sdk::ID, OxediumError::InvalidPythAccount);
+    let price_update = {
+        let data = info.try_borrow_data()?;
+        let mut slice: &[u8] = &data;
+        PriceUpdateV2::try_deserialize(&mut slice)?
+    };
+    check_feed_id(&price_update.price_message.feed_id, &vault.feed_id)?;
+
+    Ok(Some(price_update.price_message))
+}
@@ -0,0 +1,21 @@
+use std::borrow::Cow;
+
+use crate::states::Vault;
+
+/// Returns the vault as swaps out of it should be priced at `now_ts`: while
+/// `protocol_fee_waiver_until` is in the future, a copy with the protocol fee
+/// (and its curve ceiling and floor) zeroed; otherwise the vault itself.
+///
+/// The stored `protocol_fee_bps` is never modified, so the fee comes back
+/// unchanged once the waiver lapses.
+pub fn with_fee_waiver(vault: &Vault, now_ts: i64) -> Cow<'_, Vault> {
+    if now_ts >= vault.protocol_fee_waiver_until {
+        return Cow::Borrowed(vault);
+    }
+
+    let mut waived = vault.clone();
+    waived.protocol_fee_bps = 0;
+    waived.max_protocol_fee_bps = 0;
+    waived.min_protocol_fee = 0;
+    Cow::Owned(waived)
+}
@@ -9,6 +9,8 @@ use crate::states::Vault;
 ///   the base fee is applied.
 /// - If the swap increases pressure on the output vault,
 ///   the fee grows non-linearly (quadratic curve) with liquidity deviation.
+/// - If both vaults are in `stable_mode` (pegged assets, where imbalance says
+///   little about price risk), the base fee is applied regardless.
 ///
 /// # Arguments
 /// * `vault_in`  - The source vault for the swap
@@ -20,12 +22,19 @@ pub fn fees_setting(
     vault_in: &Vault,
     vault_out: &Vault,
 ) -> u64 {
+    // A stable pair only needs both sides to opt in; one stable vault alone
+    // still prices imbalance against a volatile counterpart
+    if vault_in.stable_mode && vault_out.stable_mode {
+        return vault_out.base_fee_bps;
+    }
+
     // If either vault is empty, fall back to base fee — avoid division by zero
     if vault_in.initial_balance == 0 || vault_out.initial_balance == 0 {
         return vault_out.base_fee_bps;
     }
 
-    // Relative liquidity deltas in basis points (can be negative)
+    // Relative liquidity deltas in basis points (can be negative). Balances are
+    // u64, so |current - initial| * 10_000 < 2^78 and can't overflow i128
     let delta_in_bps: i128 =
         (vault_in.current_balance as i128 - vault_in.initial_balance as i128)
             * 10_000
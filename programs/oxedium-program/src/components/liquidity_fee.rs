@@ -0,0 +1,65 @@
+const MAX_FEE_BPS: u64 = 10_000;
+
+/// Liquidity-impact curve: 0 up to the vault's impact threshold, then a
+/// quadratic that reaches 10_000 at 100% utilization.
+///
+/// ```text
+/// adj    = (utilization - threshold) normalised to 0..10_000
+/// curved = adj² / 10_000
+/// ```
+///
+/// `compute_swap_math` scales both the liquidity fee and, with
+/// `protocol_fee_follows_curve`, the protocol fee by this factor.
+///
+/// # Arguments
+/// * `utilization_bps` - Swap output as a share of the output vault's balance
+/// * `impact_threshold_bps` - Utilization where the curve starts; below 10_000
+///
+/// # Returns
+/// * `u64` - Curve factor in bps, 0..=10_000 for utilization up to 100%
+pub fn impact_curve_bps(utilization_bps: u64, impact_threshold_bps: u64) -> u64 {
+    if utilization_bps <= impact_threshold_bps || impact_threshold_bps >= MAX_FEE_BPS {
+        return 0;
+    }
+
+    // shift: map threshold..100% → 0..10_000
+    let adj = (utilization_bps - impact_threshold_bps) * 10_000
+        / (MAX_FEE_BPS - impact_threshold_bps);
+
+    // quadratic: adj² / 10_000  →  0..10_000
+    adj * adj / 10_000
+}
+
+/// Liquidity fee for a swap at `utilization_bps`: the flat base fee up to the
+/// impact threshold (10% by default), then rising along `impact_curve_bps` to
+/// 100% at full utilization.
+///
+/// ```text
+/// liquidity_fee_bps = base_fee_bps + (10_000 - base_fee_bps) * curved / 10_000
+/// ```
+///
+/// Examples (base_fee_bps = 30, threshold = 10%):
+/// ```text
+/// 10%  → 30 bps     (base only)
+/// 20%  → ~148 bps
+/// 50%  → ~1 997 bps (~20%)
+/// 70%  → ~4 475 bps (~45%)
+/// 100% → 10 000 bps (100%)
+/// ```
+///
+/// Pure, so the curve can be modelled for any hypothetical utilization without
+/// oracle inputs.
+///
+/// # Arguments
+/// * `base_fee_bps` - Health-based fee from `fees_setting`
+/// * `utilization_bps` - Swap output as a share of the output vault's balance
+/// * `impact_threshold_bps` - Utilization where the curve starts; below 10_000
+///
+/// # Returns
+/// * `u64` - Liquidity fee in bps
+pub fn liquidity_fee(base_fee_bps: u64, utilization_bps: u64, impact_threshold_bps: u64) -> u64 {
+    let curved = impact_curve_bps(utilization_bps, impact_threshold_bps);
+
+    // scale from base_fee_bps up to MAX_FEE_BPS
+    base_fee_bps + MAX_FEE_BPS.saturating_sub(base_fee_bps) * curved / 10_000
+}
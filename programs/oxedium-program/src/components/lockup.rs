@@ -0,0 +1,34 @@
+use crate::{states::Vault, utils::OxediumError};
+
+/// Returns the unlock time for a position receiving a new deposit at `now`.
+///
+/// Every deposit re-locks the whole position until `now + lockup_duration`;
+/// a lock is never shortened, even if the admin has since reduced the duration.
+/// Leaves `stake_unlock_ts` untouched while the vault's lock-up is off.
+pub fn extend_unlock_ts(vault: &Vault, stake_unlock_ts: i64, now: i64) -> Result<i64, OxediumError> {
+    if !vault.lockup_active {
+        return Ok(stake_unlock_ts);
+    }
+
+    let unlock_ts = now
+        .checked_add(vault.lockup_duration)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    Ok(unlock_ts.max(stake_unlock_ts))
+}
+
+/// Lock-up penalty in bps for unstaking at `now`, charged on top of the
+/// health-based exit fee.
+///
+/// Decays linearly from `max_lockup_penalty_bps` with a full lock remaining
+/// to 0 at `stake_unlock_ts`. Zero when the lock-up is off or has expired.
+pub fn lockup_penalty_bps(vault: &Vault, stake_unlock_ts: i64, now: i64) -> u64 {
+    if !vault.lockup_active || vault.lockup_duration <= 0 || now >= stake_unlock_ts {
+        return 0;
+    }
+
+    let duration = vault.lockup_duration as u128;
+    let remaining = ((stake_unlock_ts as i128 - now as i128) as u128).min(duration);
+
+    (vault.max_lockup_penalty_bps as u128 * remaining / duration) as u64
+}
@@ -0,0 +1,58 @@
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+use crate::{components::{compute_swap_math, SwapMathResult}, states::Vault, utils::OxediumError};
+
+/// Finds the largest `amount_in' <= amount_in` that `compute_swap_math` prices
+/// without clamping its fee against the current vault state, for partial swap fills.
+///
+/// That holds for every input below some cutoff (the output fits in
+/// `vault_out.current_balance` and total fees stay within 100%) and fails above
+/// it, so the cutoff is found by bisection over integer inputs.
+///
+/// Rounding: the result is the floor of the true cutoff — the returned input
+/// always fits and `amount_in' + 1` does not, so a fill never overdraws
+/// the output vault.
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - `amount_in` itself when the full swap fits,
+///   otherwise the largest fillable input; `InsufficientLiquidity` if nothing fits
+pub fn max_fillable_amount_in(
+    amount_in: u64,
+    oracle_in: PriceFeedMessage,
+    oracle_out: PriceFeedMessage,
+    decimals_in: u8,
+    decimals_out: u8,
+    vault_in: &Vault,
+    vault_out: &Vault,
+) -> Result<u64, OxediumError> {
+    // Dust inputs that round to zero output sit below the cutoff, not above it;
+    // counting them as fitting keeps the predicate monotonic for the bisection
+    let swap_math = |amount: u64| {
+        compute_swap_math(amount, oracle_in, oracle_out, decimals_in, decimals_out, vault_in, vault_out)
+    };
+    // A clamped fee means a 100% charge on the output, never a useful fill
+    let fits = |amount: u64| matches!(swap_math(amount), Ok(SwapMathResult { fee_clamped: false, .. }) | Err(OxediumError::OutputRoundsToZero));
+
+    if fits(amount_in) {
+        return Ok(amount_in);
+    }
+
+    // Invariant: fits(low) || low == 0, !fits(high)
+    let mut low: u64 = 0;
+    let mut high: u64 = amount_in;
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        if fits(mid) {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    // A fill that only reaches the dust band pays nothing out
+    if low == 0 || !matches!(swap_math(low), Ok(SwapMathResult { fee_clamped: false, .. })) {
+        return Err(OxediumError::InsufficientLiquidity);
+    }
+
+    Ok(low)
+}
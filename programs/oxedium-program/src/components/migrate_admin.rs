@@ -0,0 +1,42 @@
+use anchor_lang::{prelude::Pubkey, AnchorDeserialize, Discriminator};
+
+use crate::{states::Admin, utils::{OxediumError, ADMIN_SPACE}};
+
+/// Original `Admin` layout: the admin key only
+pub const LEGACY_ADMIN_LEN: usize = 8 + 32;
+
+/// Reads an `Admin` account stored in an older layout and returns it in the
+/// current one.
+///
+/// The admin keeps its key and, as `init_admin` does for a new deployment,
+/// also becomes the fee collector. No handoff is pending and the protocol is
+/// not paused.
+///
+/// # Arguments
+/// * `data` - Raw account data, discriminator included
+///
+/// # Returns
+/// * `Result<Admin, OxediumError>` - Migrated admin; `AdminUpToDate` if the
+///   account already has the current layout, `InvalidAdmin` if it isn't an
+///   Admin account of any known layout
+pub fn migrate_admin_data(data: &[u8]) -> Result<Admin, OxediumError> {
+    if data.len() < 8 || data[..8] != *Admin::DISCRIMINATOR {
+        return Err(OxediumError::InvalidAdmin);
+    }
+    let mut fields: &[u8] = &data[8..];
+
+    match data.len() {
+        ADMIN_SPACE => Err(OxediumError::AdminUpToDate),
+        LEGACY_ADMIN_LEN => {
+            let pubkey = Pubkey::deserialize(&mut fields).map_err(|_| OxediumError::InvalidAdmin)?;
+
+            Ok(Admin {
+                pubkey,
+                pending_admin: Pubkey::default(),
+                collector: pubkey,
+                paused: false,
+            })
+        }
+        _ => Err(OxediumError::InvalidAdmin),
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::{prelude::Pubkey, AnchorDeserialize, Discriminator};
+
+use crate::{states::Staker, utils::{OxediumError, STAKER_SPACE, STAKER_VERSION}};
+
+/// Original `Staker` layout: owner, vault, staked_amount, last_cumulative_yield,
+/// pending_claim
+pub const LEGACY_STAKER_LEN: usize = 8 + 32 + 32 + 8 + 16 + 8;
+
+fn read<T: AnchorDeserialize>(data: &mut &[u8]) -> Result<T, OxediumError> {
+    T::deserialize(data).map_err(|_| OxediumError::InvalidStaker)
+}
+
+/// Reads a `Staker` account stored in the original layout and returns it in the
+/// current one, at `STAKER_VERSION`.
+///
+/// Fields the old layout lacks start out neutral: the entry basis is the
+/// position's last snapshot, there is no lock-up, no recorded action, no
+/// rent subsidy to refund and no queued unstake. Everything the old layout had
+/// is carried over. `subsidized_rent` is left at 0; `migrate_staker` sets it
+/// for subsidized positions from the rent of the old layout.
+///
+/// # Arguments
+/// * `data` - Raw account data, discriminator included
+///
+/// # Returns
+/// * `Result<Staker, OxediumError>` - Migrated position; `StakerUpToDate` if the
+///   account already has the current layout, `InvalidStaker` if it isn't a
+///   Staker account of either layout
+pub fn migrate_staker_data(data: &[u8]) -> Result<Staker, OxediumError> {
+    if data.len() < 8 || data[..8] != *Staker::DISCRIMINATOR {
+        return Err(OxediumError::InvalidStaker);
+    }
+    let mut fields: &[u8] = &data[8..];
+
+    match data.len() {
+        STAKER_SPACE => Err(OxediumError::StakerUpToDate),
+        LEGACY_STAKER_LEN => {
+            let owner: Pubkey = read(&mut fields)?;
+            let vault: Pubkey = read(&mut fields)?;
+            let staked_amount: u64 = read(&mut fields)?;
+            let last_cumulative_yield: u128 = read(&mut fields)?;
+            let pending_claim: u64 = read(&mut fields)?;
+
+            Ok(Staker {
+                owner,
+                vault,
+                staked_amount,
+                last_cumulative_yield,
+                avg_entry_cumulative_yield: last_cumulative_yield,
+                pending_claim,
+                stake_unlock_ts: 0,
+                last_action_ts: 0,
+                rent_subsidized: false,
+                version: STAKER_VERSION,
+                pending_unstake_amount: 0,
+                unstake_available_ts: 0,
+                subsidized_rent: 0,
+            })
+        }
+        _ => Err(OxediumError::InvalidStaker),
+    }
+}
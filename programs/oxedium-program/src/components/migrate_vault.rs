@@ -0,0 +1,110 @@
+use anchor_lang::{prelude::Pubkey, AnchorDeserialize, Discriminator};
+
+use crate::{states::Vault, utils::{OxediumError, VAULT_SPACE, VAULT_VERSION}};
+
+/// Original `Vault` layout: base, protocol and max exit fee, token mint, Pyth
+/// account, max price age, both balances and both yield accumulators
+pub const LEGACY_VAULT_LEN: usize = 8 + 8 + 8 + 8 + 32 + 32 + 8 + 8 + 8 + 16 + 16;
+
+/// Impact threshold given to migrated vaults, the 10% the curve was built around
+pub const DEFAULT_IMPACT_THRESHOLD_BPS: u64 = 1_000;
+
+fn read<T: AnchorDeserialize>(data: &mut &[u8]) -> Result<T, OxediumError> {
+    T::deserialize(data).map_err(|_| OxediumError::InvalidVault)
+}
+
+/// Reads a `Vault` account stored in the original layout and returns it in the
+/// current one, at `VAULT_VERSION`.
+///
+/// Config the old layout lacks takes the value that reproduces its behaviour:
+/// every optional check and cap off (0 / `false`), a flat protocol fee, no
+/// feed id pinned, no fallback oracle, a USD-per-token feed, no protocol fee
+/// floor, no stable mode, and the 10% impact threshold. Counters and TWAP state
+/// start from zero.
+/// `total_stakers` is unknown and starts at 0; the staker count saturates, so
+/// exits of uncounted positions can't fail on it.
+///
+/// # Arguments
+/// * `data` - Raw account data, discriminator included
+///
+/// # Returns
+/// * `Result<Vault, OxediumError>` - Migrated vault; `VaultUpToDate` if the
+///   account already has the current layout, `InvalidVault` if it isn't a
+///   Vault account of either layout
+pub fn migrate_vault_data(data: &[u8]) -> Result<Vault, OxediumError> {
+    if data.len() < 8 || data[..8] != *Vault::DISCRIMINATOR {
+        return Err(OxediumError::InvalidVault);
+    }
+    let mut fields: &[u8] = &data[8..];
+
+    match data.len() {
+        VAULT_SPACE => Err(OxediumError::VaultUpToDate),
+        LEGACY_VAULT_LEN => {
+            let base_fee_bps: u64 = read(&mut fields)?;
+            let protocol_fee_bps: u64 = read(&mut fields)?;
+            let max_exit_fee_bps: u64 = read(&mut fields)?;
+            let token_mint: Pubkey = read(&mut fields)?;
+            let pyth_price_account: Pubkey = read(&mut fields)?;
+            let max_age_price: u64 = read(&mut fields)?;
+            let initial_balance: u64 = read(&mut fields)?;
+            let current_balance: u64 = read(&mut fields)?;
+            let cumulative_yield_per_lp: u128 = read(&mut fields)?;
+            let oxe_cumulative_yield_per_staker: u128 = read(&mut fields)?;
+
+            Ok(Vault {
+                base_fee_bps,
+                protocol_fee_bps,
+                protocol_fee_follows_curve: false,
+                max_protocol_fee_bps: protocol_fee_bps,
+                protocol_fee_waiver_until: 0,
+                max_referral_fee_bps: 0,
+                max_exit_fee_bps,
+                lockup_active: false,
+                lockup_duration: 0,
+                max_lockup_penalty_bps: 0,
+                action_cooldown: 0,
+                min_stake_age: 0,
+                impact_threshold_bps: DEFAULT_IMPACT_THRESHOLD_BPS,
+                token_mint,
+                pyth_price_account,
+                feed_id: [0; 32],
+                max_age_price,
+                max_oracle_skew: 0,
+                use_ema_price: false,
+                require_nonzero_conf: false,
+                max_conf_bps: 0,
+                max_twap_deviation_bps: 0,
+                dust_floor: 0,
+                max_total_deposits: 0,
+                min_stake_amount: 0,
+                min_swap_output: 0,
+                initial_balance,
+                current_balance,
+                total_stakers: 0,
+                cumulative_yield_per_lp,
+                yield_remainder: 0,
+                unclaimed_lp_yield: 0,
+                oxe_cumulative_yield_per_staker,
+                lifetime_yield_distributed: 0,
+                protocol_yield: 0,
+                swap_count: 0,
+                last_swap_ts: 0,
+                last_price: 0,
+                last_price_ts: 0,
+                price_cumulative: 0,
+                twap_anchor_cumulative: 0,
+                twap_anchor_ts: 0,
+                paused: false,
+                version: VAULT_VERSION,
+                fallback_pyth_price_account: Pubkey::default(),
+                max_price_jump_bps: 0,
+                withdraw_delay: 0,
+                reserved_for_withdrawals: 0,
+                invert_price: false,
+                min_protocol_fee: 0,
+                stable_mode: false,
+            })
+        }
+        _ => Err(OxediumError::InvalidVault),
+    }
+}
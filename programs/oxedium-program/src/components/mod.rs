@@ -4,10 +4,76 @@ pub use calculate_fee_amount::*;
 pub use raw_amount_out::*;
 pub use fees_setting::*;
 pub use compute_swap_math::*;
+pub use rent_subsidy::*;
+pub use quote_lp_yield::*;
+pub use record_swap::*;
+pub use derive_swap_accounts::*;
+pub use admin_transfer::*;
+pub use split_claim::*;
+pub use compound_yield::*;
+pub use take_claimable_yield::*;
+pub use twap::*;
+pub use preview_swap::*;
+pub use price_swap::*;
+pub use max_fillable_amount_in::*;
+pub use transfer_fee::*;
+pub use vault_stats::*;
+pub use split_referral_fee::*;
+pub use blend_entry_basis::*;
+pub use lockup::*;
+pub use fee_waiver::*;
+pub use settle_swap::*;
+pub use two_hop::*;
+pub use slippage_min_out::*;
+pub use unstake_share::*;
+pub use liquidity_fee::*;
+pub use exit_fee::*;
+pub use migrate_admin::*;
+pub use migrate_staker::*;
+pub use migrate_vault::*;
+pub use sweep_dust::*;
+pub use snapshot_yield::*;
+pub use fallback_oracle::*;
+pub use withdraw_queue::*;
+pub use credit_stake::*;
+pub use vault_config::*;
 
 pub mod calculate_staker_yield;
 pub mod checkers;
 pub mod calculate_fee_amount;
 pub mod raw_amount_out;
 pub mod fees_setting;
-pub mod compute_swap_math;
\ No newline at end of file
+pub mod compute_swap_math;
+pub mod rent_subsidy;
+pub mod quote_lp_yield;
+pub mod record_swap;
+pub mod derive_swap_accounts;
+pub mod admin_transfer;
+pub mod split_claim;
+pub mod compound_yield;
+pub mod take_claimable_yield;
+pub mod twap;
+pub mod preview_swap;
+pub mod price_swap;
+pub mod max_fillable_amount_in;
+pub mod transfer_fee;
+pub mod vault_stats;
+pub mod split_referral_fee;
+pub mod blend_entry_basis;
+pub mod lockup;
+pub mod fee_waiver;
+pub mod settle_swap;
+pub mod two_hop;
+pub mod slippage_min_out;
+pub mod unstake_share;
+pub mod liquidity_fee;
+pub mod exit_fee;
+pub mod migrate_admin;
+pub mod migrate_staker;
+pub mod migrate_vault;
+pub mod sweep_dust;
+pub mod snapshot_yield;
+pub mod fallback_oracle;
+pub mod withdraw_queue;
+pub mod credit_stake;
+pub mod vault_config;
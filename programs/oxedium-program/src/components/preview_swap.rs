@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+use crate::{
+    components::{
+        check_conf_ratio, check_oracle_conf, check_oracle_skew, check_price_age, check_price_jump, check_twap_deviation,
+        check_vault_active, compute_swap_math, select_price, with_fee_waiver, SwapMathResult,
+    },
+    states::Vault,
+};
+
+/// Runs every oracle and vault check `swap` performs, then `compute_swap_math`.
+///
+/// Reached through `price_swap` by `swap` and the quote instructions, and
+/// called directly by off-chain simulation, so all of them accept and price
+/// exactly the same swaps. Pyth account ownership can't be checked
+/// here and stays in the instructions.
+///
+/// # Arguments
+/// * `now_ts` - Current unix timestamp, used for staleness, TWAP and fee-waiver checks
+#[allow(clippy::too_many_arguments)]
+pub fn preview_swap(
+    amount_in: u64,
+    oracle_in: PriceFeedMessage,
+    oracle_out: PriceFeedMessage,
+    decimals_in: u8,
+    decimals_out: u8,
+    vault_in: &Vault,
+    vault_out: &Vault,
+    now_ts: i64,
+) -> Result<SwapMathResult> {
+    check_vault_active(vault_in)?;
+    check_vault_active(vault_out)?;
+
+    check_price_age(oracle_in.publish_time, vault_in.max_age_price, now_ts)?;
+    check_price_age(oracle_out.publish_time, vault_out.max_age_price, now_ts)?;
+
+    // Either vault can demand tight oracle alignment; the stricter bound wins
+    let max_oracle_skew = match (vault_in.max_oracle_skew, vault_out.max_oracle_skew) {
+        (a, b) if a > 0 && b > 0 => a.min(b),
+        (a, b) => a.max(b),
+    };
+    check_oracle_skew(oracle_in.publish_time, oracle_out.publish_time, max_oracle_skew)?;
+
+    let selected_in = select_price(oracle_in, vault_in.use_ema_price);
+    let selected_out = select_price(oracle_out, vault_out.use_ema_price);
+    check_oracle_conf(selected_in.conf, vault_in.require_nonzero_conf)?;
+    check_oracle_conf(selected_out.conf, vault_out.require_nonzero_conf)?;
+    check_conf_ratio(selected_in.price, selected_in.conf, vault_in.max_conf_bps)?;
+    check_conf_ratio(selected_out.price, selected_out.conf, vault_out.max_conf_bps)?;
+
+    // Protocol fees are charged by the output vault, so only its waiver applies
+    let result = compute_swap_math(
+        amount_in,
+        oracle_in,
+        oracle_out,
+        decimals_in,
+        decimals_out,
+        vault_in,
+        &with_fee_waiver(vault_out, now_ts)
+    )?;
+
+    // compute_swap_math has already rejected non-positive prices
+    check_twap_deviation(vault_in, selected_in.price as u64, now_ts)?;
+    check_twap_deviation(vault_out, selected_out.price as u64, now_ts)?;
+    check_price_jump(vault_in, selected_in.price as u64, now_ts)?;
+    check_price_jump(vault_out, selected_out.price as u64, now_ts)?;
+
+    Ok(result)
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+use pyth_solana_receiver_sdk::price_update::{PriceFeedMessage, PriceUpdateV2};
+
+use crate::{
+    components::{
+        check_feed_id, check_max_impact, check_min_swap_output, check_not_paused, check_vault_mint, choose_oracle,
+        gross_for_transfer, load_fallback_oracle, max_fillable_amount_in, preview_swap, transfer_fee, with_fee_waiver,
+        SwapMathResult,
+    },
+    states::{Admin, Vault},
+    utils::{math, OxediumError},
+};
+
+/// A swap priced by `price_swap`, before any token moves
+pub struct SwapQuote {
+    /// Input the signer sends, including the input mint's transfer fee
+    pub gross_amount_in: u64,
+    /// Input that reaches the vault and is priced; below the request on a partial fill
+    pub amount_in: u64,
+    pub result: SwapMathResult,
+    /// Output that reaches the trader, net of the output mint's transfer fee
+    pub delivered_out: u64,
+    pub oracle_in: PriceFeedMessage,
+    pub oracle_out: PriceFeedMessage,
+    /// Whether each leg was priced from the vault's fallback feed
+    pub fallback_in: bool,
+    pub fallback_out: bool,
+}
+
+/// Everything `swap` does before moving tokens: pause and account checks, oracle
+/// selection (with fallback), transfer fees on both legs, partial fills,
+/// `preview_swap` and the output limits.
+///
+/// `swap`, `quote_swap` and `quote_lp_yield_from_swap` all price through here,
+/// so a quote succeeds and fails exactly when the swap would. Only the caller's
+/// own `minimum_out` / slippage bound is left to `swap`.
+///
+/// # Arguments
+/// * `remaining_accounts` - May carry either vault's `fallback_pyth_price_account`
+/// * `amount_in` - Amount the signer offers, before the input transfer fee
+/// * `allow_partial` - Fill the largest amount that fits (see `max_fillable_amount_in`)
+/// * `max_impact_bps` - As in `swap`; 10 000 for no limit
+#[allow(clippy::too_many_arguments)]
+pub fn price_swap<'info>(
+    admin: &Admin,
+    token_mint_in: &InterfaceAccount<'info, MintInterface>,
+    token_mint_out: &InterfaceAccount<'info, MintInterface>,
+    pyth_price_account_in: &Account<'info, PriceUpdateV2>,
+    pyth_price_account_out: &Account<'info, PriceUpdateV2>,
+    remaining_accounts: &[AccountInfo],
+    vault_in: &Vault,
+    vault_out: &Vault,
+    amount_in: u64,
+    allow_partial: bool,
+    max_impact_bps: u64,
+    clock: &Clock,
+) -> Result<SwapQuote> {
+    check_not_paused(admin)?;
+    require!(amount_in > 0, OxediumError::ZeroAmount);
+
+    // The seeds already tie each vault to its mint; the decimals below come
+    // from the mint accounts, so pin the stored mint as well
+    check_vault_mint(vault_in, &token_mint_in.key())?;
+    check_vault_mint(vault_out, &token_mint_out.key())?;
+
+    if pyth_price_account_in.key() != vault_in.pyth_price_account {
+        return Err(OxediumError::InvalidPythAccount.into());
+    }
+    if pyth_price_account_out.key() != vault_out.pyth_price_account {
+        return Err(OxediumError::InvalidPythAccount.into());
+    }
+    // The account can be re-posted with another feed, so pin the feed as well
+    check_feed_id(&pyth_price_account_in.price_message.feed_id, &vault_in.feed_id)?;
+    check_feed_id(&pyth_price_account_out.price_message.feed_id, &vault_out.feed_id)?;
+
+    let now_ts: i64 = clock.unix_timestamp;
+
+    let (oracle_in, fallback_in) = choose_oracle(
+        pyth_price_account_in.price_message,
+        load_fallback_oracle(remaining_accounts, vault_in)?,
+        vault_in.max_age_price,
+        now_ts,
+    )?;
+    let (oracle_out, fallback_out) = choose_oracle(
+        pyth_price_account_out.price_message,
+        load_fallback_oracle(remaining_accounts, vault_out)?,
+        vault_out.max_age_price,
+        now_ts,
+    )?;
+
+    let mint_in_info = token_mint_in.to_account_info();
+    let mint_out_info = token_mint_out.to_account_info();
+
+    // Price the swap on what the vault will actually receive
+    let requested_net_in = math::sub(amount_in, transfer_fee(&mint_in_info, amount_in, clock.epoch)?)?;
+    let net_in = if allow_partial {
+        max_fillable_amount_in(
+            requested_net_in,
+            oracle_in,
+            oracle_out,
+            token_mint_in.decimals,
+            token_mint_out.decimals,
+            vault_in,
+            &with_fee_waiver(vault_out, now_ts)
+        )?
+    } else {
+        requested_net_in
+    };
+    let gross_amount_in = if net_in == requested_net_in {
+        amount_in
+    } else {
+        gross_for_transfer(&mint_in_info, net_in, clock.epoch)?
+    };
+
+    let result = preview_swap(
+        net_in,
+        oracle_in,
+        oracle_out,
+        token_mint_in.decimals,
+        token_mint_out.decimals,
+        vault_in,
+        vault_out,
+        now_ts
+    )?;
+
+    check_min_swap_output(result.net_amount_out, vault_out.min_swap_output)?;
+    check_max_impact(result.swap_fee_bps, result.base_fee_bps, max_impact_bps)?;
+
+    let delivered_out = math::sub(result.net_amount_out, transfer_fee(&mint_out_info, result.net_amount_out, clock.epoch)?)?;
+
+    Ok(SwapQuote {
+        gross_amount_in,
+        amount_in: net_in,
+        result,
+        delivered_out,
+        oracle_in,
+        oracle_out,
+        fallback_in,
+        fallback_out,
+    })
+}
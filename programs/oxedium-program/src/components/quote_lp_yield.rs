@@ -0,0 +1,53 @@
+use crate::{
+    components::calculate_staker_yield,
+    states::Vault,
+    utils::{math, OxediumError, SCALE},
+};
+
+/// Returns `vault_out` as it would look right after `staking` `hypothetical_stake`
+/// into it, so a projected swap can be priced against the new balances.
+///
+/// # Arguments
+/// * `vault_out` - The vault the LP is considering staking into
+/// * `hypothetical_stake` - Amount the LP is considering staking
+///
+/// # Returns
+/// * `Result<Vault, OxediumError>` - Copy of the vault with the stake added
+pub fn with_hypothetical_stake(vault_out: &Vault, hypothetical_stake: u64) -> Result<Vault, OxediumError> {
+    let mut staked_vault_out = vault_out.clone();
+    staked_vault_out.initial_balance = staked_vault_out.initial_balance
+        .checked_add(hypothetical_stake)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    staked_vault_out.current_balance = staked_vault_out.current_balance
+        .checked_add(hypothetical_stake)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    Ok(staked_vault_out)
+}
+
+/// Estimates the LP fee a staker would earn from a single projected swap.
+///
+/// `lp_fee_amount` comes from pricing the swap against `with_hypothetical_stake`
+/// (`price_swap` on-chain, `preview_swap` off-chain). It is spread over the new
+/// `initial_balance` the same way `swap` credits `cumulative_yield_per_lp`.
+///
+/// # Arguments
+/// * `lp_fee_amount` - LP fee of the projected swap
+/// * `staked_vault_out` - The output vault with the hypothetical stake added
+/// * `hypothetical_stake` - Amount the LP is considering staking into `vault_out`
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Yield the hypothetical stake would earn from the swap
+pub fn lp_yield_for_stake(
+    lp_fee_amount: u64,
+    staked_vault_out: &Vault,
+    hypothetical_stake: u64,
+) -> Result<u64, OxediumError> {
+    if hypothetical_stake == 0 {
+        return Ok(0);
+    }
+
+    let delta_yield_per_lp = math::mul_div_u128(lp_fee_amount as u128, SCALE, staked_vault_out.initial_balance as u128)?;
+
+    calculate_staker_yield(delta_yield_per_lp, hypothetical_stake, 0)
+}
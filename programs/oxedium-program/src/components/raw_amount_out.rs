@@ -1,5 +1,5 @@
 use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
-use crate::utils::{SCALE, OxediumError};
+use crate::utils::{SCALE, OxediumError, U256};
 
 pub fn raw_amount_out(
     amount_in: u64,
@@ -7,6 +7,26 @@ pub fn raw_amount_out(
     decimals_out: u8,
     price_message_in: PriceFeedMessage,
     price_message_out: PriceFeedMessage,
+) -> Result<u64, OxediumError> {
+    raw_amount_out_with_inversion(amount_in, decimals_in, decimals_out, price_message_in, price_message_out, false, false)
+}
+
+/// `raw_amount_out` for feeds that may quote tokens per USD instead of USD per
+/// token. With `invert_in` / `invert_out` set, that side's price is used as its
+/// reciprocal: the USD value divides by it instead of multiplying, and the
+/// output multiplies instead of dividing. The exponent goes along with the
+/// price, so `10^exponent` flips sign with it.
+///
+/// The confidence bound still works against the trader: an inverted input
+/// divides by `price + conf`, an inverted output multiplies by `price - conf`.
+pub fn raw_amount_out_with_inversion(
+    amount_in: u64,
+    decimals_in: u8,
+    decimals_out: u8,
+    price_message_in: PriceFeedMessage,
+    price_message_out: PriceFeedMessage,
+    invert_in: bool,
+    invert_out: bool,
 ) -> Result<u64, OxediumError> {
     let amount_in = amount_in as u128;
 
@@ -14,6 +34,12 @@ pub fn raw_amount_out(
         return Err(OxediumError::InvalidPrice);
     }
 
+    // No real feed publishes beyond 10^±38; reject those up front rather than
+    // relying on pow10 failing part-way through the conversion
+    if !(-38..=38).contains(&price_message_in.exponent) || !(-38..=38).contains(&price_message_out.exponent) {
+        return Err(OxediumError::InvalidPrice);
+    }
+
     // Conservative oracle bounds create a natural bid-ask spread equal to the
     // confidence interval, making round-trip oracle-latency arbitrage unprofitable.
     //
@@ -23,11 +49,9 @@ pub fn raw_amount_out(
     // On a round-trip the user must overcome a spread of 2×conf per oracle,
     // so arbitrage is only profitable when the oracle actually moved by more
     // than that spread — at which point the price update itself is the signal.
-    let price_in  = (price_message_in.price as u128)
-        .saturating_sub(price_message_in.conf as u128)
-        .max(1);
-    let price_out = (price_message_out.price as u128)
-        .saturating_add(price_message_out.conf as u128);
+    // An inverted feed moves the other way, so its bound is taken on the other side.
+    let price_in = conservative_price(price_message_in, !invert_in);
+    let price_out = conservative_price(price_message_out, invert_out);
 
     let amount_fp = amount_in
         .checked_mul(SCALE)
@@ -35,51 +59,105 @@ pub fn raw_amount_out(
         .checked_div(10u128.pow(decimals_in as u32))
         .ok_or(OxediumError::OverflowInDiv)?;
 
-    let usd_fp = apply_exponent_mul(amount_fp, price_in, price_message_in.exponent)?;
+    // The intermediates run in 256 bits: with a positive exponent on either side
+    // (or a large price against a negative output exponent) they can exceed u128
+    // even when the final amount fits in a u64. Rounding is unchanged — each step
+    // still floors exactly where the u128 version did.
+    let usd_fp = if invert_in {
+        apply_exponent_div(U256::from(amount_fp), price_in, price_message_in.exponent)?
+    } else {
+        apply_exponent_mul(U256::from(amount_fp), price_in, price_message_in.exponent)?
+    };
 
-    let out_fp = apply_exponent_div(usd_fp, price_out, price_message_out.exponent)?;
+    let out_fp = if invert_out {
+        apply_exponent_mul(usd_fp, price_out, price_message_out.exponent)?
+    } else {
+        apply_exponent_div(usd_fp, price_out, price_message_out.exponent)?
+    };
 
     let out = out_fp
-        .checked_mul(10u128.pow(decimals_out as u32))
+        .checked_mul(U256::from(10u128.pow(decimals_out as u32)))
         .ok_or(OxediumError::OverflowInMul)?
-        .checked_div(SCALE)
-        .ok_or(OxediumError::OverflowInDiv)?;
+        / U256::from(SCALE);
+
+    if out > U256::from(u64::MAX) {
+        return Err(OxediumError::OverflowInCast);
+    }
 
-    u64::try_from(out).map_err(|_| OxediumError::OverflowInCast)
+    // A nonzero input floored away entirely (e.g. dust of a 9-decimal token into
+    // a 2-decimal one) would take the input for nothing
+    if amount_in > 0 && out.is_zero() {
+        return Err(OxediumError::OutputRoundsToZero);
+    }
+
+    Ok(out.as_u64())
+}
+
+/// Oracle price widened by its confidence interval: `price - conf` (at least 1)
+/// when `lower` is set, `price + conf` otherwise. The caller has checked `price > 0`.
+fn conservative_price(price_message: PriceFeedMessage, lower: bool) -> u128 {
+    let price = price_message.price as u128;
+    let conf = price_message.conf as u128;
+    if lower {
+        price.saturating_sub(conf).max(1)
+    } else {
+        price.saturating_add(conf)
+    }
+}
+
+/// Returns the oracle message with `price`/`conf` replaced by `ema_price`/`ema_conf`
+/// when `use_ema` is set, so the conversion and confidence bounds in
+/// `raw_amount_out` run on the smoothed price. The positivity guard in
+/// `raw_amount_out` then applies to whichever price was selected.
+pub fn select_price(price_message: PriceFeedMessage, use_ema: bool) -> PriceFeedMessage {
+    if !use_ema {
+        return price_message;
+    }
+
+    PriceFeedMessage {
+        price: price_message.ema_price,
+        conf: price_message.ema_conf,
+        ..price_message
+    }
 }
 
 /// Computes `value * price * 10^exponent`, handling the exponent sign correctly.
-/// Used to convert an amount to its USD equivalent.
-fn apply_exponent_mul(value: u128, price: u128, exponent: i32) -> Result<u128, OxediumError> {
+/// Converts an amount to its USD equivalent (USD to tokens for an inverted output feed).
+fn apply_exponent_mul(value: U256, price: u128, exponent: i32) -> Result<U256, OxediumError> {
+    let scaled = value
+        .checked_mul(U256::from(price))
+        .ok_or(OxediumError::OverflowInMul)?;
     if exponent < 0 {
-        let exp = exponent.unsigned_abs();
-        value
-            .checked_mul(price).ok_or(OxediumError::OverflowInMul)?
-            .checked_div(pow10(exp)?).ok_or(OxediumError::OverflowInDiv)
+        Ok(scaled / pow10(exponent.unsigned_abs())?)
     } else {
-        let exp = exponent as u32;
-        value
-            .checked_mul(price).ok_or(OxediumError::OverflowInMul)?
-            .checked_mul(pow10(exp)?).ok_or(OxediumError::OverflowInMul)
+        scaled
+            .checked_mul(pow10(exponent as u32)?)
+            .ok_or(OxediumError::OverflowInMul)
     }
 }
 
 /// Computes `value / (price * 10^exponent)`, handling the exponent sign correctly.
-/// Used to convert a USD amount to the output token amount.
-fn apply_exponent_div(value: u128, price: u128, exponent: i32) -> Result<u128, OxediumError> {
-    if exponent >= 0 {
-        return Err(OxediumError::InvalidPrice);
+/// Converts a USD amount to the output token amount (tokens to USD for an inverted input feed).
+fn apply_exponent_div(value: U256, price: u128, exponent: i32) -> Result<U256, OxediumError> {
+    if price == 0 {
+        return Err(OxediumError::OverflowInDiv);
+    }
+    let price = U256::from(price);
+    if exponent < 0 {
+        Ok(value
+            .checked_mul(pow10(exponent.unsigned_abs())?)
+            .ok_or(OxediumError::OverflowInMul)?
+            / price)
+    } else {
+        Ok(value / price / pow10(exponent as u32)?)
     }
-    let exp = exponent.unsigned_abs();
-    value
-        .checked_mul(pow10(exp)?).ok_or(OxediumError::OverflowInMul)?
-        .checked_div(price).ok_or(OxediumError::OverflowInDiv)
 }
 
-/// Returns 10^exp as u128, guarding against exponents that would overflow.
-fn pow10(exp: u32) -> Result<u128, OxediumError> {
+/// Returns 10^exp, rejecting exponents beyond what any real feed publishes
+/// (the old u128 limit of 38 is kept).
+fn pow10(exp: u32) -> Result<U256, OxediumError> {
     if exp > 38 {
         return Err(OxediumError::OverflowInMul);
     }
-    Ok(10u128.pow(exp))
+    Ok(U256::from(10u128.pow(exp)))
 }
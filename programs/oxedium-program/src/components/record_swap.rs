@@ -0,0 +1,19 @@
+use crate::{states::Vault, utils::OxediumError};
+
+/// Updates a vault's swap tracking fields after it takes part in a swap.
+///
+/// Applied identically to every swap — including the very first one, where
+/// the zero-initialised fields are simply seeded — so no bootstrap special
+/// case exists for utilization-based metrics.
+///
+/// # Arguments
+/// * `vault` - Vault on either side of the swap
+/// * `now` - Current unix timestamp
+pub fn record_swap(vault: &mut Vault, now: i64) -> Result<(), OxediumError> {
+    vault.swap_count = vault.swap_count
+        .checked_add(1)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    vault.last_swap_ts = now;
+
+    Ok(())
+}
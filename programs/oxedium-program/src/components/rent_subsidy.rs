@@ -0,0 +1,70 @@
+use crate::{states::RentReserve, utils::OxediumError};
+
+/// Reserves rent for a first-time staker's PDA from the rent reserve.
+///
+/// A subsidy is granted only when the first deposit reaches
+/// `min_subsidized_deposit`, fewer than `max_subsidized_stakers` positions are
+/// currently subsidized, and the spendable `rent_reserve` covers the full rent.
+/// Partial subsidies are never paid, so the reserve can't be drained below zero
+/// and the reserve account's own rent-exempt lamports stay untouched.
+///
+/// # Arguments
+/// * `reserve` - The rent reserve state (mutated on success)
+/// * `first_deposit` - Amount the new staker is depositing
+/// * `rent` - Rent-exempt minimum of the new Staker PDA in lamports
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Lamports to reimburse to the staker (0 if not eligible)
+pub fn apply_rent_subsidy(
+    reserve: &mut RentReserve,
+    first_deposit: u64,
+    rent: u64,
+) -> Result<u64, OxediumError> {
+    if first_deposit < reserve.min_subsidized_deposit
+        || reserve.subsidized_stakers >= reserve.max_subsidized_stakers
+        || reserve.rent_reserve < rent
+    {
+        return Ok(0);
+    }
+
+    reserve.rent_reserve = reserve.rent_reserve
+        .checked_sub(rent)
+        .ok_or(OxediumError::OverflowInSub)?;
+    reserve.subsidized_stakers = reserve.subsidized_stakers
+        .checked_add(1)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    Ok(rent)
+}
+
+/// Returns a closed Staker PDA's subsidized rent to the rent reserve.
+///
+/// # Arguments
+/// * `reserve` - The rent reserve state (mutated)
+/// * `refund` - Lamports released by closing the Staker PDA
+pub fn refund_rent_subsidy(reserve: &mut RentReserve, refund: u64) -> Result<(), OxediumError> {
+    reserve.rent_reserve = reserve.rent_reserve
+        .checked_add(refund)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    reserve.subsidized_stakers = reserve.subsidized_stakers
+        .checked_sub(1)
+        .ok_or(OxediumError::OverflowInSub)?;
+
+    Ok(())
+}
+
+/// Splits a closing subsidized Staker PDA's lamports between the rent reserve
+/// and the owner.
+///
+/// The reserve gets back only what it paid (`subsidized_rent`); anything else
+/// the PDA holds, such as a `migrate_staker` top-up, belongs to the owner.
+///
+/// # Arguments
+/// * `lamports` - Current balance of the Staker PDA
+/// * `subsidized_rent` - Rent the reserve paid for the PDA
+///
+/// # Returns
+/// * `u64` - Lamports to return to the rent reserve
+pub fn rent_subsidy_refund(lamports: u64, subsidized_rent: u64) -> u64 {
+    lamports.min(subsidized_rent)
+}
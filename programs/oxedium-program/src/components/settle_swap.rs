@@ -0,0 +1,58 @@
+use crate::{
+    components::{credit_lp_or_protocol_yield, record_price, record_swap, SwapMathResult},
+    states::Vault,
+    utils::{math, OxediumError, SCALE, TWAP_WINDOW_SECS},
+};
+
+/// Applies a priced swap to both vaults' accounting: pays `net_amount_out` and
+/// the referral fee out of `vault_out`, records the swap and both prices, and
+/// books the LP and protocol fees on `vault_out`.
+///
+/// `vault_in.current_balance` is left to the caller, which credits what the
+/// vault actually received once the input transfer has settled.
+///
+/// # Arguments
+/// * `protocol_fee_amount` - Protocol fee left after the referrer's cut
+/// * `referral_fee_amount` - Referrer's cut, paid out of `vault_out`
+/// * `price_in` / `price_out` - Selected oracle prices, already checked positive
+/// * `total_oxe_staked` - `OxeGlobal::total_oxe_staked`, for the protocol fee accumulator
+#[allow(clippy::too_many_arguments)]
+pub fn settle_swap(
+    vault_in: &mut Vault,
+    vault_out: &mut Vault,
+    result: &SwapMathResult,
+    protocol_fee_amount: u64,
+    referral_fee_amount: u64,
+    price_in: u64,
+    price_out: u64,
+    total_oxe_staked: u64,
+    now_ts: i64,
+) -> Result<(), OxediumError> {
+    vault_out.current_balance = vault_out.current_balance
+        .checked_sub(result.net_amount_out)
+        .and_then(|v| v.checked_sub(referral_fee_amount))
+        .ok_or(OxediumError::OverflowInSub)?;
+
+    record_swap(vault_in, now_ts)?;
+    record_swap(vault_out, now_ts)?;
+    record_price(vault_in, price_in, now_ts, TWAP_WINDOW_SECS)?;
+    record_price(vault_out, price_out, now_ts, TWAP_WINDOW_SECS)?;
+
+    // When initial_balance == 0 the vault has no LPs yet; the LP fee is booked to
+    // protocol_yield for the fee collector to collect rather than dropped from accounting.
+    credit_lp_or_protocol_yield(vault_out, result.lp_fee_amount)?;
+
+    if vault_out.initial_balance > 0 {
+        // Accumulate protocol fee per OXE staker using the same SCALE pattern.
+        // If no OXE has been staked yet, protocol fees remain in the vault as
+        // excess liquidity (improves vault health until stakers join).
+        if total_oxe_staked > 0 && protocol_fee_amount > 0 {
+            let protocol_per_oxe = math::mul_div_u128(protocol_fee_amount as u128, SCALE, total_oxe_staked as u128)?;
+            vault_out.oxe_cumulative_yield_per_staker = vault_out.oxe_cumulative_yield_per_staker
+                .checked_add(protocol_per_oxe)
+                .ok_or(OxediumError::OverflowInAdd)?;
+        }
+    }
+
+    Ok(())
+}
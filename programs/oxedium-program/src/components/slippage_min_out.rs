@@ -0,0 +1,20 @@
+/// Minimum output for a swap bounded by a relative slippage instead of an
+/// absolute `minimum_out`.
+///
+/// The bound is taken from the oracle-implied `raw_amount_out`, so it covers
+/// the whole deviation from the oracle price: base fee, utilization curve and
+/// the output mint's transfer fee alike. Rounds up, so the trader never gets
+/// less than the stated percentage allows. 10_000 or more disables the bound.
+///
+/// # Arguments
+/// * `raw_amount_out` - Oracle-implied output before any fee
+/// * `max_slippage_bps` - Largest deviation the trader accepts, in bps
+///
+/// # Returns
+/// * `u64` - Smallest acceptable delivered output
+pub fn slippage_min_out(raw_amount_out: u64, max_slippage_bps: u64) -> u64 {
+    let kept_bps = 10_000u128 - max_slippage_bps.min(10_000) as u128;
+
+    // raw × kept / 10_000 ≤ raw, so the result fits in u64
+    (raw_amount_out as u128 * kept_bps).div_ceil(10_000) as u64
+}
@@ -0,0 +1,24 @@
+use crate::{components::try_calculate_staker_yield, states::Staker, utils::OxediumError};
+
+/// Moves a staker's yield accrued since `last_cumulative_yield` into
+/// `pending_claim` and advances the snapshot to `cumulative_yield_per_lp`.
+///
+/// Every instruction that changes a position's stake or pays out its yield
+/// snapshots through here first and emits a `YieldCheckpointEvent`.
+///
+/// # Arguments
+/// * `staker` - Staker position (mutated)
+/// * `cumulative_yield_per_lp` - The vault's current accumulator
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Yield earned since the previous snapshot
+pub fn snapshot_yield(staker: &mut Staker, cumulative_yield_per_lp: u128) -> Result<u64, OxediumError> {
+    let earned: u64 = try_calculate_staker_yield(cumulative_yield_per_lp, staker.staked_amount, staker.last_cumulative_yield)?;
+
+    staker.pending_claim = staker.pending_claim
+        .checked_add(earned)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    staker.last_cumulative_yield = cumulative_yield_per_lp;
+
+    Ok(earned)
+}
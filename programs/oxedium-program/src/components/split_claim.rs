@@ -0,0 +1,21 @@
+use crate::utils::OxediumError;
+
+/// Splits a staker's claimable yield into the payout and what stays pending.
+///
+/// # Arguments
+/// * `claimable` - Snapshotted yield plus existing `pending_claim`
+/// * `requested` - Amount to claim; `0` claims everything
+///
+/// # Returns
+/// * `Result<(u64, u64), OxediumError>` - `(payout, remaining_pending)`
+pub fn split_claim(claimable: u64, requested: u64) -> Result<(u64, u64), OxediumError> {
+    if requested == 0 {
+        return Ok((claimable, 0));
+    }
+
+    if requested > claimable {
+        return Err(OxediumError::ClaimExceedsYield);
+    }
+
+    Ok((requested, claimable - requested))
+}
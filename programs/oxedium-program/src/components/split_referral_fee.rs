@@ -0,0 +1,30 @@
+use crate::utils::OxediumError;
+
+/// Carves a referrer's cut out of a swap's protocol fee.
+///
+/// The referral comes out of the protocol fee, so the trader's total fee is
+/// unchanged. `referral_fee_bps` is a share of the protocol fee (10_000 = all
+/// of it) and is clamped to the vault's `max_referral_fee_bps`. Rounds down,
+/// so dust stays with the protocol.
+///
+/// # Arguments
+/// * `protocol_fee_amount` - Protocol fee charged on the swap
+/// * `referral_fee_bps` - Referrer's requested share; pass 0 when there is no referrer
+/// * `max_referral_fee_bps` - Vault cap on the share
+///
+/// # Returns
+/// * `Result<(u64, u64), OxediumError>` - `(protocol_kept, referral_amount)`
+pub fn split_referral_fee(
+    protocol_fee_amount: u64,
+    referral_fee_bps: u64,
+    max_referral_fee_bps: u64,
+) -> Result<(u64, u64), OxediumError> {
+    let bps = referral_fee_bps.min(max_referral_fee_bps).min(10_000);
+
+    let referral = (protocol_fee_amount as u128 * bps as u128 / 10_000) as u64;
+    let kept = protocol_fee_amount
+        .checked_sub(referral)
+        .ok_or(OxediumError::OverflowInSub)?;
+
+    Ok((kept, referral))
+}
@@ -0,0 +1,19 @@
+use crate::{components::{credit_lp_or_protocol_yield, release_lp_yield}, states::{Staker, Vault}, utils::OxediumError};
+
+/// Forfeits a drained position's leftover `pending_claim` back to the vault.
+///
+/// The dust is taken out of `unclaimed_lp_yield` and credited again through
+/// `credit_lp_or_protocol_yield`, so it goes to the vault's active LPs (or to
+/// `protocol_yield` if there are none). The tokens never leave the vault.
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Amount forfeited
+pub fn forfeit_dust(staker: &mut Staker, vault: &mut Vault) -> Result<u64, OxediumError> {
+    let dust = staker.pending_claim;
+    staker.pending_claim = 0;
+
+    release_lp_yield(vault, dust);
+    credit_lp_or_protocol_yield(vault, dust)?;
+
+    Ok(dust)
+}
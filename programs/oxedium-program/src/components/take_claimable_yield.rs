@@ -0,0 +1,44 @@
+use crate::{components::{calculate_staker_yield, release_lp_yield, try_calculate_staker_yield}, states::{Staker, Vault}, utils::OxediumError};
+
+/// Computes a staker's full claimable yield without settling it: yield accrued
+/// since the last snapshot plus `pending_claim`. Saturates on overflow, so use
+/// it for display only; `take_claimable_yield` uses the checked calculation.
+///
+/// # Arguments
+/// * `staker` - Staker position
+/// * `vault` - Vault the position belongs to
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Amount a full claim would pay out
+pub fn claimable_yield(staker: &Staker, vault: &Vault) -> Result<u64, OxediumError> {
+    calculate_staker_yield(vault.cumulative_yield_per_lp, staker.staked_amount, staker.last_cumulative_yield)?
+        .checked_add(staker.pending_claim)
+        .ok_or(OxediumError::OverflowInAdd)
+}
+
+/// Snapshots a staker's yield and settles the full claimable amount.
+///
+/// Clears `pending_claim`, advances `last_cumulative_yield` and deducts the
+/// payout from the vault's `current_balance` and `unclaimed_lp_yield`. The caller performs the transfer.
+///
+/// # Arguments
+/// * `staker` - Staker position (mutated)
+/// * `vault` - Vault the position belongs to (mutated)
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Amount to pay out (0 if nothing accrued)
+pub fn take_claimable_yield(staker: &mut Staker, vault: &mut Vault) -> Result<u64, OxediumError> {
+    let amount: u64 = try_calculate_staker_yield(vault.cumulative_yield_per_lp, staker.staked_amount, staker.last_cumulative_yield)?
+        .checked_add(staker.pending_claim)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    vault.current_balance = vault.current_balance
+        .checked_sub(amount)
+        .ok_or(OxediumError::OverflowInSub)?;
+    release_lp_yield(vault, amount);
+
+    staker.last_cumulative_yield = vault.cumulative_yield_per_lp;
+    staker.pending_claim = 0;
+
+    Ok(amount)
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{self, spl_token_2022::extension::transfer_fee::TransferFeeConfig},
+    token_interface::get_mint_extension_data,
+};
+
+use crate::utils::OxediumError;
+
+/// Returns the mint's Token-2022 `TransferFeeConfig`, or `None` for classic SPL
+/// mints and Token-2022 mints without the extension.
+fn transfer_fee_config(mint: &AccountInfo) -> Option<TransferFeeConfig> {
+    if mint.owner != &token_2022::ID {
+        return None;
+    }
+    get_mint_extension_data::<TransferFeeConfig>(mint).ok()
+}
+
+/// Fee withheld by the mint when `amount` is sent at `epoch`; 0 when the mint
+/// charges no transfer fee.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64, epoch: u64) -> Result<u64> {
+    match transfer_fee_config(mint) {
+        Some(config) => Ok(config
+            .calculate_epoch_fee(epoch, amount)
+            .ok_or(OxediumError::Overflow)?),
+        None => Ok(0),
+    }
+}
+
+/// Amount to send at `epoch` so that at least `post_fee_amount` arrives.
+pub fn gross_for_transfer(mint: &AccountInfo, post_fee_amount: u64, epoch: u64) -> Result<u64> {
+    let fee = match transfer_fee_config(mint) {
+        Some(config) => config
+            .calculate_inverse_epoch_fee(epoch, post_fee_amount)
+            .ok_or(OxediumError::Overflow)?,
+        None => 0,
+    };
+    Ok(post_fee_amount
+        .checked_add(fee)
+        .ok_or(OxediumError::OverflowInAdd)?)
+}
+
+/// Amount that actually landed in a token account across a transfer, checked
+/// against what the program priced the transfer at.
+///
+/// Vault accounting is credited with the returned amount, so it always matches
+/// the token balance even if a hook or fee schedule changed under us.
+/// Returns `TransferAmountMismatch` error if less than `expected` arrived.
+pub fn reconcile_received(balance_before: u64, balance_after: u64, expected: u64) -> Result<u64> {
+    let received = balance_after
+        .checked_sub(balance_before)
+        .ok_or(OxediumError::TransferAmountMismatch)?;
+
+    require!(received >= expected, OxediumError::TransferAmountMismatch);
+
+    Ok(received)
+}
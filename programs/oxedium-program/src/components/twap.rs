@@ -0,0 +1,114 @@
+use crate::{states::Vault, utils::{OxediumError, PRICE_JUMP_WINDOW_SECS, TWAP_WINDOW_SECS}};
+
+/// Returns the vault's price accumulator extended to `now`, assuming
+/// `last_price` has held since `last_price_ts`.
+pub fn price_cumulative_at(vault: &Vault, now: i64) -> Result<u128, OxediumError> {
+    if vault.last_price_ts == 0 || now <= vault.last_price_ts {
+        return Ok(vault.price_cumulative);
+    }
+
+    let elapsed = (now - vault.last_price_ts) as u128;
+    let accrued = (vault.last_price as u128)
+        .checked_mul(elapsed)
+        .ok_or(OxediumError::OverflowInMul)?;
+
+    vault.price_cumulative
+        .checked_add(accrued)
+        .ok_or(OxediumError::OverflowInAdd)
+}
+
+/// Derives the time-weighted average price between a caller-supplied
+/// accumulator snapshot (`start_cumulative` taken at `start_ts`) and `now`.
+/// An empty window returns `last_price`.
+pub fn twap_since(
+    vault: &Vault,
+    start_cumulative: u128,
+    start_ts: i64,
+    now: i64,
+) -> Result<u64, OxediumError> {
+    if now <= start_ts {
+        return Ok(vault.last_price);
+    }
+
+    let delta = price_cumulative_at(vault, now)?
+        .checked_sub(start_cumulative)
+        .ok_or(OxediumError::OverflowInSub)?;
+    let twap = delta / (now - start_ts) as u128;
+
+    u64::try_from(twap).map_err(|_| OxediumError::OverflowInCast)
+}
+
+/// Records the oracle price used by a swap touching this vault.
+///
+/// Accrues the previous price over the elapsed time, stores the new one, and
+/// rolls the TWAP anchor forward once it is older than `window` seconds.
+pub fn record_price(vault: &mut Vault, price: u64, now: i64, window: i64) -> Result<(), OxediumError> {
+    vault.price_cumulative = price_cumulative_at(vault, now)?;
+    vault.last_price = price;
+    vault.last_price_ts = now;
+
+    if vault.twap_anchor_ts == 0 || now - vault.twap_anchor_ts >= window {
+        vault.twap_anchor_cumulative = vault.price_cumulative;
+        vault.twap_anchor_ts = now;
+    }
+
+    Ok(())
+}
+
+/// Rejects an oracle price that deviates from the vault TWAP by more than
+/// `max_twap_deviation_bps`. Skipped when the bound is 0, no price has been
+/// recorded yet, or the last recorded price is `TWAP_WINDOW_SECS` old.
+///
+/// Only accepted swaps record prices, so after a lasting move past the bound
+/// the TWAP can't catch up on its own. Once it goes stale the next swap passes,
+/// and `record_price` rolls the anchor onto the new price.
+pub fn check_twap_deviation(vault: &Vault, price: u64, now: i64) -> Result<(), OxediumError> {
+    if vault.max_twap_deviation_bps == 0 || vault.last_price_ts == 0 {
+        return Ok(());
+    }
+    if now.saturating_sub(vault.last_price_ts) >= TWAP_WINDOW_SECS {
+        return Ok(());
+    }
+
+    let twap = twap_since(vault, vault.twap_anchor_cumulative, vault.twap_anchor_ts, now)? as u128;
+    if twap == 0 {
+        return Ok(());
+    }
+
+    let deviation_bps = (price as u128).abs_diff(twap)
+        .checked_mul(10_000)
+        .ok_or(OxediumError::OverflowInMul)?
+        / twap;
+
+    if deviation_bps > vault.max_twap_deviation_bps as u128 {
+        return Err(OxediumError::TwapDeviation);
+    }
+
+    Ok(())
+}
+
+/// Circuit breaker against flash moves: rejects an oracle price that differs
+/// from the vault's `last_price` by more than `max_price_jump_bps` when that
+/// price was recorded less than `PRICE_JUMP_WINDOW_SECS` ago. Skipped when the
+/// bound is 0 or no price has been recorded yet; older prices never block, so
+/// slow drift between swaps passes.
+pub fn check_price_jump(vault: &Vault, price: u64, now: i64) -> Result<(), OxediumError> {
+    if vault.max_price_jump_bps == 0 || vault.last_price_ts == 0 || vault.last_price == 0 {
+        return Ok(());
+    }
+    if now.saturating_sub(vault.last_price_ts) >= PRICE_JUMP_WINDOW_SECS {
+        return Ok(());
+    }
+
+    let last_price = vault.last_price as u128;
+    let jump_bps = (price as u128).abs_diff(last_price)
+        .checked_mul(10_000)
+        .ok_or(OxediumError::OverflowInMul)?
+        / last_price;
+
+    if jump_bps > vault.max_price_jump_bps as u128 {
+        return Err(OxediumError::PriceJumpTooLarge);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+use crate::{
+    components::{check_min_swap_output, check_solvency, preview_swap, select_price, settle_swap, SwapMathResult},
+    states::Vault,
+    utils::OxediumError,
+};
+
+/// Prices and settles an in → mid → out swap as two consecutive swaps through
+/// `vault_mid`, updating all three vaults exactly as two sequential `swap`s would.
+///
+/// Leg 1 pays `net_amount_out` of the mid token out of `vault_mid`, and leg 2
+/// takes that same amount back in, so the mid token never leaves the vault ATA.
+/// Leg 2 is priced against `vault_mid` after leg 1 has settled, and the mid
+/// vault only gets its leg 2 input credited afterwards, as a second `swap` would.
+/// `vault_in.current_balance` is left to the caller, as with `settle_swap`.
+///
+/// # Returns
+/// * `Result<(SwapMathResult, SwapMathResult)>` - The two legs, in order
+#[allow(clippy::too_many_arguments)]
+pub fn execute_two_hop(
+    amount_in: u64,
+    oracle_in: PriceFeedMessage,
+    oracle_mid: PriceFeedMessage,
+    oracle_out: PriceFeedMessage,
+    decimals_in: u8,
+    decimals_mid: u8,
+    decimals_out: u8,
+    vault_in: &mut Vault,
+    vault_mid: &mut Vault,
+    vault_out: &mut Vault,
+    total_oxe_staked: u64,
+    now_ts: i64,
+) -> Result<(SwapMathResult, SwapMathResult)> {
+    // preview_swap rejects non-positive prices before they're used below
+    let leg_in = preview_swap(amount_in, oracle_in, oracle_mid, decimals_in, decimals_mid, vault_in, vault_mid, now_ts)?;
+    check_min_swap_output(leg_in.net_amount_out, vault_mid.min_swap_output)?;
+    let price_in = select_price(oracle_in, vault_in.use_ema_price).price as u64;
+    let price_mid = select_price(oracle_mid, vault_mid.use_ema_price).price as u64;
+    settle_swap(vault_in, vault_mid, &leg_in, leg_in.protocol_fee_amount, 0, price_in, price_mid, total_oxe_staked, now_ts)?;
+
+    let leg_out = preview_swap(leg_in.net_amount_out, oracle_mid, oracle_out, decimals_mid, decimals_out, vault_mid, vault_out, now_ts)?;
+    check_min_swap_output(leg_out.net_amount_out, vault_out.min_swap_output)?;
+    let price_out = select_price(oracle_out, vault_out.use_ema_price).price as u64;
+    settle_swap(vault_mid, vault_out, &leg_out, leg_out.protocol_fee_amount, 0, price_mid, price_out, total_oxe_staked, now_ts)?;
+
+    vault_mid.current_balance = vault_mid.current_balance
+        .checked_add(leg_in.net_amount_out)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    // Checked once both legs have settled: leg 1 briefly pays the mid token
+    // out before leg 2 takes it back in
+    check_solvency(vault_mid)?;
+    check_solvency(vault_out)?;
+
+    Ok((leg_in, leg_out))
+}
@@ -0,0 +1,21 @@
+use crate::utils::OxediumError;
+
+/// Converts a share of a position into the staked amount to withdraw.
+///
+/// Rounds down. 10_000 bps is exactly `staked_amount`, so a full exit leaves
+/// nothing behind.
+///
+/// # Arguments
+/// * `staked_amount` - Position's current `staked_amount`
+/// * `bps` - Share to withdraw; `10_000` is the full position
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Amount to pass to `unstaking`
+pub fn unstake_amount_for_bps(staked_amount: u64, bps: u64) -> Result<u64, OxediumError> {
+    if bps > 10_000 {
+        return Err(OxediumError::InvalidUnstakeBps);
+    }
+
+    // bps ≤ 10_000, so the result never exceeds staked_amount
+    Ok((staked_amount as u128 * bps as u128 / 10_000) as u64)
+}
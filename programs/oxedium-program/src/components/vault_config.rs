@@ -0,0 +1,178 @@
+use anchor_lang::prelude::*;
+
+use crate::{components::{check_lockup_config, check_vault_config}, events::{VaultInitEvent, VaultUpdateEvent}, states::Vault};
+
+/// The vault settings `init_vault` takes and `update_vault` can change.
+/// The oracle account and its feed id come from the instruction's accounts instead.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VaultConfig {
+    pub base_fee_bps: u64,
+    pub protocol_fee_bps: u64,
+    pub protocol_fee_follows_curve: bool,
+    pub max_protocol_fee_bps: u64,
+    pub max_exit_fee_bps: u64,
+    pub impact_threshold_bps: u64,
+    pub max_age_price: u64,
+    pub use_ema_price: bool,
+    pub max_twap_deviation_bps: u64,
+    pub require_nonzero_conf: bool,
+    pub max_conf_bps: u64,
+    pub dust_floor: u64,
+    pub max_total_deposits: u64,
+    pub min_stake_amount: u64,
+    pub min_swap_output: u64,
+    pub max_referral_fee_bps: u64,
+    pub lockup_active: bool,
+    pub lockup_duration: i64,
+    pub max_lockup_penalty_bps: u64,
+    pub max_oracle_skew: i64,
+    pub fallback_pyth_price_account: Pubkey,
+}
+
+/// The `update_vault` argument: each `Some` replaces that setting, each `None`
+/// keeps the vault's current value.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct VaultConfigUpdate {
+    pub base_fee_bps: Option<u64>,
+    pub protocol_fee_bps: Option<u64>,
+    pub protocol_fee_follows_curve: Option<bool>,
+    pub max_protocol_fee_bps: Option<u64>,
+    pub max_exit_fee_bps: Option<u64>,
+    pub impact_threshold_bps: Option<u64>,
+    pub max_age_price: Option<u64>,
+    pub use_ema_price: Option<bool>,
+    pub max_twap_deviation_bps: Option<u64>,
+    pub require_nonzero_conf: Option<bool>,
+    pub max_conf_bps: Option<u64>,
+    pub dust_floor: Option<u64>,
+    pub max_total_deposits: Option<u64>,
+    pub min_stake_amount: Option<u64>,
+    pub min_swap_output: Option<u64>,
+    pub max_referral_fee_bps: Option<u64>,
+    pub lockup_active: Option<bool>,
+    pub lockup_duration: Option<i64>,
+    pub max_lockup_penalty_bps: Option<u64>,
+    pub max_oracle_skew: Option<i64>,
+    pub fallback_pyth_price_account: Option<Pubkey>,
+}
+
+/// Reads a vault's current settings.
+pub fn vault_config(vault: &Vault) -> VaultConfig {
+    VaultConfig {
+        base_fee_bps: vault.base_fee_bps,
+        protocol_fee_bps: vault.protocol_fee_bps,
+        protocol_fee_follows_curve: vault.protocol_fee_follows_curve,
+        max_protocol_fee_bps: vault.max_protocol_fee_bps,
+        max_exit_fee_bps: vault.max_exit_fee_bps,
+        impact_threshold_bps: vault.impact_threshold_bps,
+        max_age_price: vault.max_age_price,
+        use_ema_price: vault.use_ema_price,
+        max_twap_deviation_bps: vault.max_twap_deviation_bps,
+        require_nonzero_conf: vault.require_nonzero_conf,
+        max_conf_bps: vault.max_conf_bps,
+        dust_floor: vault.dust_floor,
+        max_total_deposits: vault.max_total_deposits,
+        min_stake_amount: vault.min_stake_amount,
+        min_swap_output: vault.min_swap_output,
+        max_referral_fee_bps: vault.max_referral_fee_bps,
+        lockup_active: vault.lockup_active,
+        lockup_duration: vault.lockup_duration,
+        max_lockup_penalty_bps: vault.max_lockup_penalty_bps,
+        max_oracle_skew: vault.max_oracle_skew,
+        fallback_pyth_price_account: vault.fallback_pyth_price_account,
+    }
+}
+
+/// Overlays the settings an `update_vault` caller passed on top of `config`.
+pub fn merge_vault_config(config: VaultConfig, update: VaultConfigUpdate) -> VaultConfig {
+    VaultConfig {
+        base_fee_bps: update.base_fee_bps.unwrap_or(config.base_fee_bps),
+        protocol_fee_bps: update.protocol_fee_bps.unwrap_or(config.protocol_fee_bps),
+        protocol_fee_follows_curve: update.protocol_fee_follows_curve.unwrap_or(config.protocol_fee_follows_curve),
+        max_protocol_fee_bps: update.max_protocol_fee_bps.unwrap_or(config.max_protocol_fee_bps),
+        max_exit_fee_bps: update.max_exit_fee_bps.unwrap_or(config.max_exit_fee_bps),
+        impact_threshold_bps: update.impact_threshold_bps.unwrap_or(config.impact_threshold_bps),
+        max_age_price: update.max_age_price.unwrap_or(config.max_age_price),
+        use_ema_price: update.use_ema_price.unwrap_or(config.use_ema_price),
+        max_twap_deviation_bps: update.max_twap_deviation_bps.unwrap_or(config.max_twap_deviation_bps),
+        require_nonzero_conf: update.require_nonzero_conf.unwrap_or(config.require_nonzero_conf),
+        max_conf_bps: update.max_conf_bps.unwrap_or(config.max_conf_bps),
+        dust_floor: update.dust_floor.unwrap_or(config.dust_floor),
+        max_total_deposits: update.max_total_deposits.unwrap_or(config.max_total_deposits),
+        min_stake_amount: update.min_stake_amount.unwrap_or(config.min_stake_amount),
+        min_swap_output: update.min_swap_output.unwrap_or(config.min_swap_output),
+        max_referral_fee_bps: update.max_referral_fee_bps.unwrap_or(config.max_referral_fee_bps),
+        lockup_active: update.lockup_active.unwrap_or(config.lockup_active),
+        lockup_duration: update.lockup_duration.unwrap_or(config.lockup_duration),
+        max_lockup_penalty_bps: update.max_lockup_penalty_bps.unwrap_or(config.max_lockup_penalty_bps),
+        max_oracle_skew: update.max_oracle_skew.unwrap_or(config.max_oracle_skew),
+        fallback_pyth_price_account: update.fallback_pyth_price_account.unwrap_or(config.fallback_pyth_price_account),
+    }
+}
+
+/// Validates `config` and writes it to the vault. Nothing is written if a
+/// check fails.
+///
+/// # Returns
+/// * `Result<()>` - `check_vault_config` / `check_lockup_config` errors
+pub fn apply_vault_config(vault: &mut Vault, config: &VaultConfig) -> Result<()> {
+    check_vault_config(
+        config.base_fee_bps,
+        config.protocol_fee_bps,
+        config.max_protocol_fee_bps,
+        config.max_exit_fee_bps,
+        config.max_age_price,
+        config.impact_threshold_bps,
+        config.max_referral_fee_bps,
+    )?;
+    check_lockup_config(config.lockup_duration, config.max_lockup_penalty_bps)?;
+
+    vault.base_fee_bps = config.base_fee_bps;
+    vault.protocol_fee_bps = config.protocol_fee_bps;
+    vault.protocol_fee_follows_curve = config.protocol_fee_follows_curve;
+    vault.max_protocol_fee_bps = config.max_protocol_fee_bps;
+    vault.max_exit_fee_bps = config.max_exit_fee_bps;
+    vault.impact_threshold_bps = config.impact_threshold_bps;
+    vault.max_age_price = config.max_age_price;
+    vault.use_ema_price = config.use_ema_price;
+    vault.max_twap_deviation_bps = config.max_twap_deviation_bps;
+    vault.require_nonzero_conf = config.require_nonzero_conf;
+    vault.max_conf_bps = config.max_conf_bps;
+    vault.dust_floor = config.dust_floor;
+    vault.max_total_deposits = config.max_total_deposits;
+    vault.min_stake_amount = config.min_stake_amount;
+    vault.min_swap_output = config.min_swap_output;
+    vault.max_referral_fee_bps = config.max_referral_fee_bps;
+    vault.lockup_active = config.lockup_active;
+    vault.lockup_duration = config.lockup_duration;
+    vault.max_lockup_penalty_bps = config.max_lockup_penalty_bps;
+    vault.max_oracle_skew = config.max_oracle_skew;
+    vault.fallback_pyth_price_account = config.fallback_pyth_price_account;
+
+    Ok(())
+}
+
+/// The `VaultInitEvent` `init_vault` emits for the vault it just wrote.
+pub fn vault_init_event(vault: &Vault, timestamp: i64, slot: u64) -> VaultInitEvent {
+    VaultInitEvent {
+        mint: vault.token_mint,
+        pyth_price_account: vault.pyth_price_account,
+        feed_id: vault.feed_id,
+        config: vault_config(vault),
+        timestamp,
+        slot,
+    }
+}
+
+/// The `VaultUpdateEvent` `update_vault` emits, with the vault's whole config
+/// after the update so indexers can rebuild it from the last event alone.
+pub fn vault_update_event(vault: &Vault, timestamp: i64, slot: u64) -> VaultUpdateEvent {
+    VaultUpdateEvent {
+        mint: vault.token_mint,
+        pyth_price_account: vault.pyth_price_account,
+        feed_id: vault.feed_id,
+        config: vault_config(vault),
+        timestamp,
+        slot,
+    }
+}
@@ -0,0 +1,167 @@
+use crate::{events::VaultStateEvent, states::Vault, utils::{OxediumError, SCALE}};
+
+/// Keeps `vault.total_stakers` in step with a position's stake changing from
+/// `staked_before` to `staked_after`.
+///
+/// A position counts while its stake is nonzero: it enters on the 0 → n
+/// transition (first stake, or re-entry after a full unstake) and leaves on
+/// n → 0. `close_staker` only runs on empty positions, so it never changes the count.
+pub fn update_staker_count(vault: &mut Vault, staked_before: u64, staked_after: u64) -> Result<(), OxediumError> {
+    if staked_before == 0 && staked_after > 0 {
+        vault.total_stakers = vault.total_stakers
+            .checked_add(1)
+            .ok_or(OxediumError::OverflowInAdd)?;
+    } else if staked_before > 0 && staked_after == 0 {
+        // Saturate: positions opened before the counter existed were never
+        // counted, and their exit must not fail on it
+        vault.total_stakers = vault.total_stakers.saturating_sub(1);
+    }
+
+    Ok(())
+}
+
+/// Credits `amount` of fees to the vault's LPs via `cumulative_yield_per_lp`
+/// and records it in `lifetime_yield_distributed`. The part of the increment
+/// lost to flooring is kept in `yield_remainder` and added to the next credit.
+///
+/// No-op when the vault has no LP principal; callers route such fees through
+/// `credit_lp_or_protocol_yield` instead.
+pub fn credit_lp_yield(vault: &mut Vault, amount: u64) -> Result<(), OxediumError> {
+    if amount == 0 || vault.initial_balance == 0 {
+        return Ok(());
+    }
+
+    // Carry the floored-off fraction forward so small fees on large vaults still
+    // accrue: the remainder is fee value × SCALE, so it stays valid even after
+    // initial_balance changes. This needs the remainder, so it divides by hand
+    // rather than through math::mul_div_u128; a u64 fee × SCALE always fits in u128
+    let scaled = (amount as u128 * SCALE)
+        .checked_add(vault.yield_remainder)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    let initial_balance = vault.initial_balance as u128;
+    vault.cumulative_yield_per_lp = vault.cumulative_yield_per_lp
+        .checked_add(scaled / initial_balance)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    vault.yield_remainder = scaled % initial_balance;
+    vault.unclaimed_lp_yield = vault.unclaimed_lp_yield
+        .checked_add(amount)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    // Analytics only: saturate rather than fail swaps once the counter tops out
+    vault.lifetime_yield_distributed = vault.lifetime_yield_distributed.saturating_add(amount);
+
+    Ok(())
+}
+
+/// Releases `amount` of LP yield from `unclaimed_lp_yield` once it has been
+/// claimed or compounded.
+///
+/// Saturates: yield credited before the counter existed was never reserved,
+/// and paying it out must not fail on it.
+pub fn release_lp_yield(vault: &mut Vault, amount: u64) {
+    vault.unclaimed_lp_yield = vault.unclaimed_lp_yield.saturating_sub(amount);
+}
+
+/// Routes an LP-bound fee (swap LP fee or unstaking exit fee): to the vault's
+/// LPs via `credit_lp_yield`, or, when the vault has no LP principal, to
+/// `protocol_yield` so the fee isn't stranded in the vault ATA with no one able
+/// to claim it. It stays there for the fee collector to `collect`; later LPs don't
+/// inherit it.
+///
+/// For exit fees, call after `initial_balance` has been reduced by the
+/// unstaked principal.
+///
+/// # Returns
+/// * `Result<bool, OxediumError>` - `true` if the fee went to LPs
+pub fn credit_lp_or_protocol_yield(vault: &mut Vault, amount: u64) -> Result<bool, OxediumError> {
+    if amount == 0 {
+        return Ok(false);
+    }
+
+    if vault.initial_balance > 0 {
+        credit_lp_yield(vault, amount)?;
+        return Ok(true);
+    }
+
+    vault.protocol_yield = vault.protocol_yield
+        .checked_add(amount)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    Ok(false)
+}
+
+/// Settles the vault's whole `protocol_yield` for `collect`: zeroes it and
+/// deducts it from `current_balance`. The caller performs the transfer.
+///
+/// Returns `ProtocolYieldExceedsBalance` if the two have drifted apart, rather
+/// than underflowing or paying out tokens that back LP principal.
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Amount to transfer (0 if nothing accrued)
+pub fn take_protocol_yield(vault: &mut Vault) -> Result<u64, OxediumError> {
+    let amount = vault.protocol_yield;
+    if amount > vault.current_balance {
+        return Err(OxediumError::ProtocolYieldExceedsBalance);
+    }
+
+    vault.current_balance -= amount;
+    vault.protocol_yield = 0;
+
+    Ok(amount)
+}
+
+/// Books tokens that reached the vault ATA outside the program (e.g. a direct
+/// transfer) as `protocol_yield`, so `collect` can withdraw them. LP principal
+/// (`initial_balance`) is untouched.
+///
+/// The ATA is expected to hold `current_balance` plus the queued unstake
+/// payouts in `reserved_for_withdrawals`. Returns `VaultBalanceDeficit` if it
+/// holds less: that means the accounting is already wrong, and nothing is changed.
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Surplus booked (0 if the two already match)
+pub fn absorb_surplus(vault: &mut Vault, ata_amount: u64) -> Result<u64, OxediumError> {
+    let surplus = ata_amount
+        .checked_sub(vault.current_balance)
+        .and_then(|free| free.checked_sub(vault.reserved_for_withdrawals))
+        .ok_or(OxediumError::VaultBalanceDeficit)?;
+
+    vault.current_balance = vault.current_balance
+        .checked_add(surplus)
+        .ok_or(OxediumError::OverflowInAdd)?;
+    vault.protocol_yield = vault.protocol_yield
+        .checked_add(surplus)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    Ok(surplus)
+}
+
+/// Books liquidity donated by `seed_liquidity` into `current_balance` only.
+///
+/// No LP claim is created (`initial_balance` is untouched), so the donation
+/// raises health for every existing staker instead of diluting them.
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - `current_balance` after the donation
+pub fn seed_vault_liquidity(vault: &mut Vault, amount: u64) -> Result<u64, OxediumError> {
+    vault.current_balance = vault.current_balance
+        .checked_add(amount)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    Ok(vault.current_balance)
+}
+
+/// The `VaultStateEvent` `get_vault_state` emits: balances, health and the
+/// fee settings a dashboard shows next to them.
+pub fn vault_state_event(vault: &Vault) -> VaultStateEvent {
+    VaultStateEvent {
+        mint: vault.token_mint,
+        initial_balance: vault.initial_balance,
+        current_balance: vault.current_balance,
+        health_bps: vault.health_bps(),
+        protocol_yield: vault.protocol_yield,
+        cumulative_yield_per_lp: vault.cumulative_yield_per_lp,
+        base_fee_bps: vault.base_fee_bps,
+        max_exit_fee_bps: vault.max_exit_fee_bps,
+    }
+}
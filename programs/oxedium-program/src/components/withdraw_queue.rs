@@ -0,0 +1,54 @@
+use crate::{states::{Staker, Vault}, utils::OxediumError};
+
+/// Queues an unstake payout that `request_unstake` has already taken out of
+/// `current_balance`: books it in `reserved_for_withdrawals` and on the staker,
+/// available after the vault's `withdraw_delay`.
+///
+/// # Arguments
+/// * `staker` - Staker position (mutated)
+/// * `vault` - Vault the position belongs to (mutated)
+/// * `payout` - Tokens to pay out, net of exit fees
+/// * `now` - Current unix timestamp
+///
+/// # Returns
+/// * `Result<i64, OxediumError>` - Unix timestamp `finalize_unstake` opens at;
+///   `WithdrawAlreadyQueued` if the staker has a payout pending
+pub fn queue_withdrawal(staker: &mut Staker, vault: &mut Vault, payout: u64, now: i64) -> Result<i64, OxediumError> {
+    if staker.pending_unstake_amount > 0 {
+        return Err(OxediumError::WithdrawAlreadyQueued);
+    }
+
+    vault.reserved_for_withdrawals = vault.reserved_for_withdrawals
+        .checked_add(payout)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    staker.pending_unstake_amount = payout;
+    staker.unstake_available_ts = now.saturating_add(vault.withdraw_delay);
+
+    Ok(staker.unstake_available_ts)
+}
+
+/// Releases a staker's queued payout once its delay has passed and clears the
+/// queue. The caller performs the transfer.
+///
+/// # Returns
+/// * `Result<u64, OxediumError>` - Amount to pay out; `NoWithdrawQueued` or
+///   `WithdrawNotReady` otherwise
+pub fn take_queued_withdrawal(staker: &mut Staker, vault: &mut Vault, now: i64) -> Result<u64, OxediumError> {
+    let amount = staker.pending_unstake_amount;
+    if amount == 0 {
+        return Err(OxediumError::NoWithdrawQueued);
+    }
+    if now < staker.unstake_available_ts {
+        return Err(OxediumError::WithdrawNotReady);
+    }
+
+    vault.reserved_for_withdrawals = vault.reserved_for_withdrawals
+        .checked_sub(amount)
+        .ok_or(OxediumError::OverflowInSub)?;
+
+    staker.pending_unstake_amount = 0;
+    staker.unstake_available_ts = 0;
+
+    Ok(amount)
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct AdminProposeEvent {
+    pub admin: Pubkey,
+    /// Takes over once it signs `accept_admin`
+    pub pending_admin: Pubkey,
+    pub timestamp: i64,
+    pub slot: u64
+}
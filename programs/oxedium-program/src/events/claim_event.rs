@@ -4,5 +4,8 @@ use anchor_lang::prelude::*;
 pub struct ClaimEvent {
     pub user: Pubkey,
     pub mint: Pubkey,
-    pub amount: u64
+    pub amount: u64,
+    pub remaining_pending: u64,
+    pub timestamp: i64,
+    pub slot: u64
 }
\ No newline at end of file
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ClaimableEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub claimable: u64
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct CollectEvent {
+    /// Key that signed the collection (the admin's fee collector)
+    pub admin: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub vault_balance_after: u64,
+    pub timestamp: i64,
+    pub slot: u64
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+/// Reads the clock for the `timestamp` and `slot` every user-facing event carries,
+/// so log subscribers don't have to join against block metadata.
+///
+/// # Returns
+/// * `Result<(i64, u64)>` - `(unix_timestamp, slot)`
+pub fn event_clock() -> Result<(i64, u64)> {
+    let clock = Clock::get()?;
+    Ok((clock.unix_timestamp, clock.slot))
+}
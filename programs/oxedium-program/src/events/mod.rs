@@ -5,6 +5,22 @@ pub use claim_event::*;
 pub use oxe_stake_event::*;
 pub use oxe_unstake_event::*;
 pub use oxe_claim_event::*;
+pub use quote_event::*;
+pub use compound_event::*;
+pub use claimable_event::*;
+pub use event_clock::*;
+pub use collect_event::*;
+pub use multi_hop_event::*;
+pub use reconcile_event::*;
+pub use vault_state_event::*;
+pub use yield_checkpoint_event::*;
+pub use withdraw_queued_event::*;
+pub use withdraw_finalized_event::*;
+pub use seed_liquidity_event::*;
+pub use vault_init_event::*;
+pub use vault_update_event::*;
+pub use admin_propose_event::*;
+pub use admin_update_event::*;
 
 pub mod swap_event;
 pub mod staking_event;
@@ -13,3 +29,19 @@ pub mod claim_event;
 pub mod oxe_stake_event;
 pub mod oxe_unstake_event;
 pub mod oxe_claim_event;
+pub mod quote_event;
+pub mod compound_event;
+pub mod claimable_event;
+pub mod event_clock;
+pub mod collect_event;
+pub mod multi_hop_event;
+pub mod reconcile_event;
+pub mod vault_state_event;
+pub mod yield_checkpoint_event;
+pub mod withdraw_queued_event;
+pub mod withdraw_finalized_event;
+pub mod seed_liquidity_event;
+pub mod vault_init_event;
+pub mod vault_update_event;
+pub mod admin_propose_event;
+pub mod admin_update_event;
\ No newline at end of file
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+
+/// Summary of a `swap_two_hop`; each leg also emits its own `SwapEvent`
+#[event]
+pub struct MultiHopEvent {
+    pub user: Pubkey,
+    pub token_in: Pubkey,
+    pub token_mid: Pubkey,
+    pub token_out: Pubkey,
+    pub amount_in: u64,
+    /// Mid-token amount leg 1 paid out and leg 2 took back in
+    pub amount_mid: u64,
+    pub amount_out: u64,
+    pub timestamp: i64,
+    pub slot: u64
+}
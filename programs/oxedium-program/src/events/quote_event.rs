@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct QuoteEvent {
+    pub amount_in: u64,
+    pub raw_amount_out: u64,
+    pub net_amount_out: u64,
+    pub swap_fee_bps: u64,
+    pub lp_fee: u64,
+    pub protocol_fee: u64,
+    pub delivered_amount_out: u64,
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct ReconcileEvent {
+    pub mint: Pubkey,
+    /// Out-of-band tokens booked to `protocol_yield`
+    pub surplus: u64,
+    pub timestamp: i64,
+    pub slot: u64
+}
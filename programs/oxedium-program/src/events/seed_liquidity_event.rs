@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct SeedLiquidityEvent {
+    pub mint: Pubkey,
+    /// Tokens donated to the vault (net of any transfer fee)
+    pub amount: u64,
+    pub current_balance: u64,
+    pub health_bps: u64,
+    pub timestamp: i64,
+    pub slot: u64
+}
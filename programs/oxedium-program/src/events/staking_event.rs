@@ -4,5 +4,13 @@ use anchor_lang::prelude::*;
 pub struct StakingEvent {
     pub user: Pubkey,
     pub mint: Pubkey,
-    pub amount: u64
+    pub amount: u64,
+    /// Position after this deposit, with its share of the vault's LP principal
+    pub staked_amount: u64,
+    pub share_bps: u64,
+    /// `cumulative_yield_per_lp` at this deposit; yield accrues from here on
+    pub entry_cumulative_yield: u128,
+    pub avg_entry_cumulative_yield: u128,
+    pub timestamp: i64,
+    pub slot: u64
 }
\ No newline at end of file
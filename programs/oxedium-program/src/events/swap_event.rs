@@ -6,10 +6,25 @@ pub struct SwapEvent {
     pub fee_bps: u64,
     pub token_in: Pubkey,
     pub token_out: Pubkey,
+    pub requested_amount_in: u64,
     pub amount_in: u64,
     pub amount_out: u64,
     pub price_in: u64,
     pub price_out: u64,
     pub lp_fee: u64,
-    pub protocol_fee: u64
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+    pub price_impact_bps: u64,
+    pub decimals_in: u8,
+    pub decimals_out: u8,
+    /// `current_balance` of each vault after the swap, for TVL without refetching accounts
+    pub vault_in_balance_after: u64,
+    pub vault_out_balance_after: u64,
+    /// `fee_bps` broken down by source; the four sum to it
+    pub base_fee_bps: u64,
+    pub imbalance_fee_bps: u64,
+    pub liquidity_impact_bps: u64,
+    pub oracle_conf_bps: u64,
+    pub timestamp: i64,
+    pub slot: u64
 }
\ No newline at end of file
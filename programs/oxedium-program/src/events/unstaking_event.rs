@@ -5,5 +5,13 @@ pub struct UnstakingEvent {
     pub user: Pubkey,
     pub mint: Pubkey,
     pub amount: u64,
-    pub extra_fee_bps: u64
+    pub extra_fee_bps: u64,
+    pub lockup_penalty_bps: u64,
+    /// Tokens withheld by the exit fee and lock-up penalty together
+    pub exit_fee: u64,
+    /// The fee was credited to the remaining LPs; false when no LP is left to
+    /// receive it and it goes to `protocol_yield` instead
+    pub exit_fee_to_lps: bool,
+    pub timestamp: i64,
+    pub slot: u64
 }
\ No newline at end of file
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+use crate::components::VaultConfig;
+
+#[event]
+pub struct VaultInitEvent {
+    pub mint: Pubkey,
+    pub pyth_price_account: Pubkey,
+    /// Feed id pinned from `pyth_price_account`
+    pub feed_id: [u8; 32],
+    /// The config `init_vault` wrote
+    pub config: VaultConfig,
+    pub timestamp: i64,
+    pub slot: u64
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct VaultStateEvent {
+    pub mint: Pubkey,
+    pub initial_balance: u64,
+    pub current_balance: u64,
+    /// `Vault::health_bps`, the figure fees and exit fees are computed from
+    pub health_bps: u64,
+    pub protocol_yield: u64,
+    pub cumulative_yield_per_lp: u128,
+    pub base_fee_bps: u64,
+    pub max_exit_fee_bps: u64
+}
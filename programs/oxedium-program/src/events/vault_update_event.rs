@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+use crate::components::VaultConfig;
+
+#[event]
+pub struct VaultUpdateEvent {
+    pub mint: Pubkey,
+    pub pyth_price_account: Pubkey,
+    /// Feed id pinned from `pyth_price_account`
+    pub feed_id: [u8; 32],
+    /// The vault's full config after the update, not just the settings passed
+    pub config: VaultConfig,
+    pub timestamp: i64,
+    pub slot: u64
+}
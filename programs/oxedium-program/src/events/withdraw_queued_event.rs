@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct WithdrawQueuedEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    /// Staked principal withdrawn
+    pub amount: u64,
+    /// Queued payout, net of the exit fee and lock-up penalty
+    pub payout: u64,
+    pub exit_fee: u64,
+    pub available_ts: i64,
+    pub timestamp: i64,
+    pub slot: u64
+}
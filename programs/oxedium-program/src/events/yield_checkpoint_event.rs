@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Emitted whenever a position's yield is snapshotted (`staking`, `unstaking`,
+/// `claim`, `claim_all`, `compound`), before any payout or restake
+#[event]
+pub struct YieldCheckpointEvent {
+    pub user: Pubkey,
+    pub vault: Pubkey,
+    pub earned_since_last: u64,
+    /// `pending_claim` right after the snapshot
+    pub new_pending_claim: u64,
+    pub cumulative_yield_per_lp: u128
+}
@@ -0,0 +1,31 @@
+use crate::{components::accept_pending_admin, events::{event_clock, AdminUpdateEvent}, states::Admin, utils::{ADMIN_SEED, OXEDIUM_SEED}};
+use anchor_lang::prelude::*;
+
+/// Second step of an admin handoff: the pending admin signs to take over.
+#[inline(never)]
+pub fn accept_admin(ctx: Context<AcceptAdminInstructionAccounts>) -> Result<()> {
+    let admin: &mut Account<'_, Admin> = &mut ctx.accounts.admin_pda;
+
+    let old_admin: Pubkey = accept_pending_admin(admin, &ctx.accounts.signer.key())?;
+
+    msg!("AcceptAdmin {{new_admin: {}}}", admin.pubkey.key());
+
+    let (timestamp, slot) = event_clock()?;
+    emit!(AdminUpdateEvent {
+        old_admin,
+        new_admin: admin.pubkey,
+        timestamp,
+        slot
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdminInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut, seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
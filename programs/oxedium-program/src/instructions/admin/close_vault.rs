@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, CloseAccount, Mint as MintInterface, TokenAccount as TokenAccountInterface,
+    TokenInterface, TransferChecked,
+};
+use crate::{
+    components::{check_admin, check_vault_closable, check_vault_mint},
+    states::{Admin, Vault},
+    utils::{ADMIN_SEED, OXEDIUM_SEED, VAULT_SEED},
+};
+
+/// Close an empty vault and its ATA, refunding both rents to the admin
+///
+/// Safeguard: the vault must owe nothing to anyone (see `check_vault_closable`),
+/// so no LP, queued withdrawal, fee collector or pending claim loses funds.
+/// Whatever the ATA still holds (dust up to `dust_floor`, or tokens sent to it
+/// directly) is swept to `recipient_ata` first, since a token account can only
+/// be closed at zero balance.
+pub fn close_vault(ctx: Context<CloseVaultInstructionAccounts>) -> Result<()> {
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    let vault: &Account<'_, Vault> = &ctx.accounts.vault_pda;
+    check_vault_mint(vault, &ctx.accounts.token_mint.key())?;
+    check_vault_closable(vault)?;
+
+    let mint_key = ctx.accounts.token_mint.key();
+    let seeds = &[VAULT_SEED.as_bytes(), mint_key.as_ref(), &[ctx.bumps.vault_pda]];
+    let signer_seeds = &[&seeds[..]];
+
+    let swept: u64 = ctx.accounts.vault_ata.amount;
+    if swept > 0 {
+        let cpi_accounts = TransferChecked {
+            from: ctx.accounts.vault_ata.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.recipient_ata.to_account_info(),
+            authority: ctx.accounts.vault_pda.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds),
+            swept,
+            ctx.accounts.token_mint.decimals)?;
+    }
+
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.vault_ata.to_account_info(),
+        destination: ctx.accounts.signer.to_account_info(),
+        authority: ctx.accounts.vault_pda.to_account_info(),
+    };
+    token_interface::close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+        signer_seeds,
+    ))?;
+
+    msg!("CloseVault {{mint: {}, swept: {}}}", mint_key, swept);
+
+    Ok(())
+}
+
+/// Accounts required for the close_vault instruction
+#[derive(Accounts)]
+pub struct CloseVaultInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, close = signer, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_pda,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// Any owner; only the mint is constrained. Receives what the vault ATA still holds
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub recipient_ata: InterfaceAccount<'info, TokenAccountInterface>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
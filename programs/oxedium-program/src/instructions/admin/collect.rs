@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+    TransferChecked,
+};
+use crate::{components::{check_collector, check_vault_mint, take_protocol_yield}, events::{event_clock, CollectEvent}, states::{Admin, Vault}, utils::{OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED, OxediumError}};
+
+/// Withdraw a vault's accrued `protocol_yield` to `recipient_ata`.
+///
+/// Signed by `admin.collector`, not the admin. The recipient can be any token
+/// account of the vault's mint (a cold wallet, a fee-distribution program), so
+/// the key that triggers collection never has to custody the fees.
+pub fn collect(ctx: Context<CollectInstructionAccounts>) -> Result<()> {
+    check_collector(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    let vault_pda_info = ctx.accounts.vault_pda.to_account_info();
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_vault_mint(vault, &ctx.accounts.token_mint.key())?;
+    let amount = take_protocol_yield(vault)?;
+    require!(amount > 0, OxediumError::ZeroAmount);
+
+    let mint_key = ctx.accounts.token_mint.key();
+    let seeds = &[VAULT_SEED.as_bytes(), mint_key.as_ref(), &[ctx.bumps.vault_pda]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault_ata.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.recipient_ata.to_account_info(),
+        authority: vault_pda_info,
+    };
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds),
+        amount,
+        ctx.accounts.token_mint.decimals)?;
+
+    let (timestamp, slot) = event_clock()?;
+    emit!(CollectEvent {
+        admin: ctx.accounts.signer.key(),
+        mint: mint_key,
+        vault: ctx.accounts.vault_pda.key(),
+        recipient: ctx.accounts.recipient_ata.key(),
+        amount,
+        vault_balance_after: ctx.accounts.vault_pda.current_balance,
+        timestamp,
+        slot
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CollectInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        token::authority = vault_pda,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccountInterface>,
+
+    /// Any owner; only the mint is constrained
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub recipient_ata: InterfaceAccount<'info, TokenAccountInterface>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountSerialize;
+use anchor_spl::token_interface::{
+    self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+    TransferChecked,
+};
+
+use crate::{
+    components::{check_collector, check_vault_mint, take_protocol_yield},
+    events::{event_clock, CollectEvent},
+    states::{Admin, Vault},
+    utils::{ADMIN_SEED, OXEDIUM_SEED, VAULT_SEED, OxediumError},
+};
+
+/// Accounts per vault in `remaining_accounts`
+const COLLECT_GROUP_LEN: usize = 4;
+
+/// Withdraw accrued `protocol_yield` from several vaults in one transaction.
+///
+/// `remaining_accounts` must be provided in groups of four:
+/// `[vault_pda, token_mint, vault_ata, recipient_ata, ...]`.
+/// The fee collector is checked once; each group then runs the same settlement
+/// as `collect`. Vaults with nothing accrued are skipped instead of failing the
+/// whole batch. Every mint in the batch must belong to the passed
+/// `token_program` (SPL Token or Token-2022).
+///
+/// # Arguments
+/// * `ctx` - context containing the signer, admin PDA and token program
+pub fn collect_all<'info>(ctx: Context<'_, '_, '_, 'info, CollectAllInstructionAccounts<'info>>) -> Result<()> {
+    check_collector(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    let (timestamp, slot) = event_clock()?;
+
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len().is_multiple_of(COLLECT_GROUP_LEN),
+        OxediumError::InvalidVault
+    );
+
+    for group in ctx.remaining_accounts.chunks(COLLECT_GROUP_LEN) {
+        let vault_info         = &group[0];
+        let mint_info          = &group[1];
+        let vault_ata_info     = &group[2];
+        let recipient_ata_info = &group[3];
+
+        require!(vault_info.is_writable, OxediumError::InvalidVault);
+        require!(vault_info.owner == &crate::ID, OxediumError::InvalidVault);
+
+        let mint_key = mint_info.key();
+        let (expected_vault, vault_bump) = Pubkey::find_program_address(
+            &[VAULT_SEED.as_bytes(), mint_key.as_ref()],
+            &crate::ID,
+        );
+        require!(vault_info.key() == expected_vault, OxediumError::InvalidVault);
+
+        let mut vault = {
+            let data = vault_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            Vault::try_deserialize(&mut slice)?
+        };
+        check_vault_mint(&vault, &mint_key)?;
+
+        let amount = take_protocol_yield(&mut vault)?;
+        if amount == 0 {
+            continue;
+        }
+
+        let token_program_key = ctx.accounts.token_program.key();
+        require!(mint_info.owner          == &token_program_key, OxediumError::InvalidVault);
+        require!(vault_ata_info.owner     == &token_program_key, OxediumError::InvalidVault);
+        require!(recipient_ata_info.owner == &token_program_key, OxediumError::InvalidVault);
+        let mint = {
+            let data = mint_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            MintInterface::try_deserialize(&mut slice)?
+        };
+        let vault_ata = {
+            let data = vault_ata_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            TokenAccountInterface::try_deserialize(&mut slice)?
+        };
+        let recipient_ata = {
+            let data = recipient_ata_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            TokenAccountInterface::try_deserialize(&mut slice)?
+        };
+        require!(vault_ata.owner == vault_info.key() && vault_ata.mint == mint_key, OxediumError::InvalidVault);
+        // Same rule as collect: any owner, but it must hold this vault's mint
+        require!(recipient_ata.mint == mint_key, OxediumError::InvalidVault);
+
+        let seeds = &[VAULT_SEED.as_bytes(), mint_key.as_ref(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: vault_ata_info.clone(),
+            mint: mint_info.clone(),
+            to: recipient_ata_info.clone(),
+            authority: vault_info.clone(),
+        };
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds),
+            amount,
+            mint.decimals)?;
+
+        {
+            let mut data = vault_info.try_borrow_mut_data()?;
+            vault.try_serialize(&mut data.as_mut())?;
+        }
+
+        emit!(CollectEvent {
+            admin: ctx.accounts.signer.key(),
+            mint: mint_key,
+            vault: vault_info.key(),
+            recipient: recipient_ata_info.key(),
+            amount,
+            vault_balance_after: vault.current_balance,
+            timestamp,
+            slot
+        });
+    }
+
+    Ok(())
+}
+
+/// Accounts context for the collect_all instruction
+#[derive(Accounts)]
+pub struct CollectAllInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::{
+    components::check_admin,
+    states::{Admin, RentReserve},
+    utils::{ADMIN_SEED, OXEDIUM_SEED, RENT_RESERVE_SEED, OxediumError},
+};
+
+/// Deposit lamports into the rent reserve used to subsidize Staker PDA rent
+///
+/// # Arguments
+/// * `ctx` - context containing all accounts
+/// * `amount` - lamports to add to the spendable reserve
+pub fn fund_rent_reserve(ctx: Context<FundRentReserveInstructionAccounts>, amount: u64) -> Result<()> {
+    require!(amount > 0, OxediumError::ZeroAmount);
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.signer.to_account_info(),
+        to: ctx.accounts.rent_reserve_pda.to_account_info(),
+    };
+    system_program::transfer(
+        CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+        amount,
+    )?;
+
+    let rent_reserve: &mut Account<'_, RentReserve> = &mut ctx.accounts.rent_reserve_pda;
+    rent_reserve.rent_reserve = rent_reserve.rent_reserve
+        .checked_add(amount)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    msg!("FundRentReserve {{amount: {}, rent_reserve: {}}}", amount, rent_reserve.rent_reserve);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FundRentReserveInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut, seeds = [OXEDIUM_SEED.as_bytes(), RENT_RESERVE_SEED.as_bytes()], bump)]
+    pub rent_reserve_pda: Account<'info, RentReserve>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub system_program: Program<'info, System>,
+}
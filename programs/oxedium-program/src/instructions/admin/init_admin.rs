@@ -1,18 +1,16 @@
-use crate::{states::Admin, utils::{ADMIN_SEED, OXEDIUM_SEED, OxediumError}};
+use crate::{components::{check_upgrade_authority, initialize_admin}, program::OxediumProgram, states::Admin, utils::{ADMIN_SEED, ADMIN_SPACE, OXEDIUM_SEED, OxediumError}};
 use anchor_lang::prelude::*;
-use std::str::FromStr;
 
+/// Registers the program's upgrade authority as the protocol admin and fee collector.
+/// The admin PDA has a fixed address and is created with `init`, so any
+/// later call fails because the account already exists. Requiring the upgrade
+/// authority stops anyone else from claiming the admin between deployment and
+/// the deployer's own `init_admin`.
 pub fn init_admin(ctx: Context<InitAdminInstructionAccounts>) -> Result<()> {
-    let admin_key = Pubkey::from_str("3gXnk9LTHHtFzKK5pkKzp58okeo9V72MjGSyzFUCvKk2")
-        .map_err(|_| OxediumError::InvalidAdmin)?;
-
-    if ctx.accounts.signer.key() != admin_key {
-        return Err(OxediumError::InvalidAdmin.into());
-    }
+    check_upgrade_authority(ctx.accounts.program_data.upgrade_authority_address, &ctx.accounts.signer)?;
 
     let admin: &mut Account<'_, Admin> = &mut ctx.accounts.admin_pda;
-
-    admin.pubkey = ctx.accounts.signer.key();
+    initialize_admin(admin, ctx.accounts.signer.key())?;
 
     msg!("InitAdmin {{new_admin: {}}}", admin.pubkey.key());
 
@@ -29,9 +27,15 @@ pub struct InitAdminInstructionAccounts<'info> {
         payer = signer,
         seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()],
         bump,
-        space = 8 + 32,
+        space = ADMIN_SPACE,
     )]
     pub admin_pda: Account<'info, Admin>,
 
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ OxediumError::InvalidAdmin)]
+    pub program: Program<'info, OxediumProgram>,
+
+    /// This program's ProgramData account; holds its upgrade authority
+    pub program_data: Account<'info, ProgramData>,
+
     pub system_program: Program<'info, System>,
 }
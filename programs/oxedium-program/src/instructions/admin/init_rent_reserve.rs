@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    components::check_admin,
+    states::{Admin, RentReserve},
+    utils::{ADMIN_SEED, OXEDIUM_SEED, RENT_RESERVE_SEED},
+};
+
+/// Create the rent reserve PDA with an empty balance and the subsidy limits
+///
+/// # Arguments
+/// * `ctx` - context containing all accounts
+/// * `min_subsidized_deposit` - smallest first deposit whose Staker PDA rent is reimbursed
+/// * `max_subsidized_stakers` - number of subsidized positions allowed at once
+pub fn init_rent_reserve(
+    ctx: Context<InitRentReserveInstructionAccounts>,
+    min_subsidized_deposit: u64,
+    max_subsidized_stakers: u64,
+) -> Result<()> {
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    let rent_reserve: &mut Account<'_, RentReserve> = &mut ctx.accounts.rent_reserve_pda;
+    rent_reserve.rent_reserve = 0;
+    rent_reserve.min_subsidized_deposit = min_subsidized_deposit;
+    rent_reserve.max_subsidized_stakers = max_subsidized_stakers;
+    rent_reserve.subsidized_stakers = 0;
+
+    msg!("InitRentReserve {{min_subsidized_deposit: {}, max_subsidized_stakers: {}}}",
+        rent_reserve.min_subsidized_deposit,
+        rent_reserve.max_subsidized_stakers
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitRentReserveInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        seeds = [OXEDIUM_SEED.as_bytes(), RENT_RESERVE_SEED.as_bytes()],
+        bump,
+        space = 8 + 8 + 8 + 8 + 8,
+    )]
+    pub rent_reserve_pda: Account<'info, RentReserve>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,42 +1,58 @@
-use crate::{components::check_admin, states::{Vault, Admin}, utils::*};
+use crate::{components::{apply_vault_config, check_admin, check_feed_id, vault_init_event, VaultConfig}, events::event_clock, states::{Vault, Admin}, utils::*};
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint as MintInterface;
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
-pub fn init_vault(
-    ctx: Context<InitVaultInstructionAccounts>,
-    base_fee_bps: u64,
-    protocol_fee_bps: u64,
-    max_age_price: u64,
-    max_exit_fee_bps: u64,
-) -> Result<()> {
+/// Create a vault for `token_mint`, priced by `pyth_price_account`
+///
+/// # Arguments
+/// * `ctx` - context containing the mint, the Pyth account and the new Vault PDA
+/// * `config` - fee, oracle, deposit and lock-up settings
+/// * `expected_feed_id` - feed id `pyth_price_account` must carry; all zeros skips the check
+pub fn init_vault(ctx: Context<InitVaultInstructionAccounts>, config: VaultConfig, expected_feed_id: [u8; 32]) -> Result<()> {
     let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
 
     check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
 
-    require!(base_fee_bps <= 1_000, OxediumError::FeeExceeds);
-    require!(protocol_fee_bps <= 500, OxediumError::FeeExceeds);
-    require!(max_exit_fee_bps <= 1_000, OxediumError::FeeExceeds);
-    require!(max_age_price > 0, OxediumError::InvalidDeviation);
+    let feed_id = ctx.accounts.pyth_price_account.price_message.feed_id;
+    check_feed_id(&feed_id, &expected_feed_id)?;
+    apply_vault_config(vault, &config)?;
 
-    vault.base_fee_bps = base_fee_bps;
-    vault.protocol_fee_bps = protocol_fee_bps;
-    vault.max_exit_fee_bps = max_exit_fee_bps;
+    vault.protocol_fee_waiver_until = 0;
     vault.token_mint = ctx.accounts.token_mint.key();
     vault.pyth_price_account = ctx.accounts.pyth_price_account.key();
-    vault.max_age_price = max_age_price;
+    vault.feed_id = feed_id;
+    vault.action_cooldown = 0;
+    vault.min_stake_age = 0;
+    vault.max_price_jump_bps = 0;
+    vault.withdraw_delay = 0;
+    vault.reserved_for_withdrawals = 0;
+    vault.invert_price = false;
+    vault.min_protocol_fee = 0;
+    vault.stable_mode = false;
     vault.initial_balance = 0;
     vault.current_balance = 0;
+    vault.total_stakers = 0;
     vault.cumulative_yield_per_lp = 0;
+    vault.yield_remainder = 0;
+    vault.unclaimed_lp_yield = 0;
     vault.oxe_cumulative_yield_per_staker = 0;
+    vault.lifetime_yield_distributed = 0;
+    vault.protocol_yield = 0;
+    vault.swap_count = 0;
+    vault.last_swap_ts = 0;
+    vault.last_price = 0;
+    vault.last_price_ts = 0;
+    vault.price_cumulative = 0;
+    vault.twap_anchor_cumulative = 0;
+    vault.twap_anchor_ts = 0;
+    vault.paused = false;
+    vault.version = VAULT_VERSION;
 
-    msg!("InitVault {{mint: {}, base_fee: {}, protocol_fee: {}, max_exit_fee: {}, max_age_price: {}}}",
-        vault.token_mint.key(),
-        vault.base_fee_bps,
-        vault.protocol_fee_bps,
-        vault.max_exit_fee_bps,
-        vault.max_age_price
-    );
+    msg!("InitVault {{mint: {}}}", vault.token_mint);
+
+    let (timestamp, slot) = event_clock()?;
+    emit!(vault_init_event(vault, timestamp, slot));
 
     Ok(())
 }
@@ -46,7 +62,7 @@ pub struct InitVaultInstructionAccounts<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
 
     pub pyth_price_account: Account<'info, PriceUpdateV2>,
 
@@ -55,7 +71,7 @@ pub struct InitVaultInstructionAccounts<'info> {
         payer = signer,
         seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()],
         bump,
-        space = 8 + 8 + 8 + 8 + 32 + 32 + 8 + 8 + 8 + 16 + 16,
+        space = VAULT_SPACE,
     )]
     pub vault_pda: Account<'info, Vault>,
 
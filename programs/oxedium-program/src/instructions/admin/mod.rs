@@ -1,11 +1,57 @@
 pub use init_admin::*;
-pub use update_admin::*;
+pub use propose_admin::*;
+pub use accept_admin::*;
+pub use set_pause::*;
 pub use init_vault::*;
 pub use update_vault::*;
+pub use set_vault_pause::*;
 pub use init_oxe_global::*;
+pub use init_rent_reserve::*;
+pub use update_rent_reserve::*;
+pub use fund_rent_reserve::*;
+pub use collect::*;
+pub use set_fee_waiver::*;
+pub use set_collector::*;
+pub use collect_all::*;
+pub use reconcile_vault::*;
+pub use set_action_cooldown::*;
+pub use set_min_stake_age::*;
+pub use set_max_price_jump::*;
+pub use set_withdraw_delay::*;
+pub use resize_vault::*;
+pub use resize_admin::*;
+pub use sweep_dust::*;
+pub use seed_liquidity::*;
+pub use set_invert_price::*;
+pub use close_vault::*;
+pub use set_min_protocol_fee::*;
+pub use set_stable_mode::*;
 
 pub mod init_admin;
-pub mod update_admin;
+pub mod propose_admin;
+pub mod accept_admin;
+pub mod set_pause;
 pub mod init_vault;
 pub mod update_vault;
+pub mod set_vault_pause;
 pub mod init_oxe_global;
+pub mod init_rent_reserve;
+pub mod update_rent_reserve;
+pub mod fund_rent_reserve;
+pub mod collect;
+pub mod set_fee_waiver;
+pub mod set_collector;
+pub mod collect_all;
+pub mod reconcile_vault;
+pub mod set_action_cooldown;
+pub mod set_min_stake_age;
+pub mod set_max_price_jump;
+pub mod set_withdraw_delay;
+pub mod resize_vault;
+pub mod resize_admin;
+pub mod sweep_dust;
+pub mod seed_liquidity;
+pub mod set_invert_price;
+pub mod close_vault;
+pub mod set_min_protocol_fee;
+pub mod set_stable_mode;
\ No newline at end of file
@@ -0,0 +1,39 @@
+use crate::{components::{check_admin, set_pending_admin}, events::{event_clock, AdminProposeEvent}, states::Admin, utils::{ADMIN_SEED, OXEDIUM_SEED}};
+use anchor_lang::prelude::*;
+
+/// First step of an admin handoff: the current admin nominates `new_admin`.
+/// Admin rights only move once `new_admin` signs `accept_admin`.
+#[inline(never)]
+pub fn propose_admin(ctx: Context<ProposeAdminInstructionAccounts>) -> Result<()> {
+    let admin: &mut Account<'_, Admin> = &mut ctx.accounts.admin_pda;
+
+    check_admin(admin, &ctx.accounts.signer)?;
+
+    set_pending_admin(admin, ctx.accounts.new_admin.key());
+
+    msg!("ProposeAdmin {{pending_admin: {}}}", admin.pending_admin.key());
+
+    let (timestamp, slot) = event_clock()?;
+    emit!(AdminProposeEvent {
+        admin: admin.pubkey,
+        pending_admin: admin.pending_admin,
+        timestamp,
+        slot
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeAdminInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: No constraints, must sign accept_admin to take effect
+    pub new_admin: AccountInfo<'info>,
+
+    #[account(mut, seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface};
+use crate::{components::{absorb_surplus, check_admin, check_vault_mint}, events::{event_clock, ReconcileEvent}, states::{Admin, Vault}, utils::{OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
+
+/// Book tokens sent straight to the vault ATA, outside `staking`/`swap`, as
+/// `protocol_yield` so the fee collector can `collect` them. Reverts without
+/// changes if the ATA holds less than `current_balance`.
+pub fn reconcile_vault(ctx: Context<ReconcileVaultInstructionAccounts>) -> Result<()> {
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+    check_vault_mint(vault, &ctx.accounts.token_mint.key())?;
+
+    let surplus = absorb_surplus(vault, ctx.accounts.vault_ata.amount)?;
+
+    let (timestamp, slot) = event_clock()?;
+    emit!(ReconcileEvent {
+        mint: vault.token_mint,
+        surplus,
+        timestamp,
+        slot
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReconcileVaultInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(
+        associated_token::mint = token_mint,
+        associated_token::authority = vault_pda,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccountInterface>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,63 @@
+use anchor_lang::{prelude::*, system_program::{self, Transfer}};
+
+use crate::{components::migrate_admin_data, utils::*};
+
+/// Move the Admin PDA from the original single-key layout to the current one
+///
+/// Every other instruction loads the admin as `Account<Admin>`, which the old
+/// 40-byte account can't satisfy, so this is the first call after upgrading a
+/// deployed program. Like `resize_vault` it reads the raw account, grows it to
+/// `ADMIN_SPACE` with the signer topping up rent, and rewrites it (see
+/// `migrate_admin_data`). Only the admin stored in the old account may sign.
+pub fn resize_admin(ctx: Context<ResizeAdminInstructionAccounts>) -> Result<()> {
+    let admin_info = ctx.accounts.admin_pda.to_account_info();
+
+    let admin = {
+        let data = admin_info.try_borrow_data()?;
+        migrate_admin_data(&data)?
+    };
+    require!(admin.pubkey == ctx.accounts.signer.key(), OxediumError::InvalidAdmin);
+
+    let rent = Rent::get()?.minimum_balance(ADMIN_SPACE);
+    let shortfall = rent.saturating_sub(admin_info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: admin_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    admin_info.resize(ADMIN_SPACE)?;
+    {
+        let mut data = admin_info.try_borrow_mut_data()?;
+        admin.try_serialize(&mut data.as_mut())?;
+    }
+
+    msg!("ResizeAdmin {{admin: {}}}", admin.pubkey);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResizeAdminInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: an older-layout Admin that can't deserialize as `Admin`; the
+    /// seeds, program ownership and discriminator are checked instead
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()],
+        bump,
+    )]
+    pub admin_pda: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
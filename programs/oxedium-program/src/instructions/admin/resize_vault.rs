@@ -0,0 +1,71 @@
+use anchor_lang::{prelude::*, system_program::{self, Transfer}};
+use anchor_spl::token_interface::Mint as MintInterface;
+
+use crate::{components::{check_admin, migrate_vault_data}, states::Admin, utils::*};
+
+/// Move a Vault stored in an older layout to the current one
+///
+/// Old accounts no longer deserialize as `Vault`, so Anchor's `realloc`
+/// constraint can't load them; the account is grown to `VAULT_SPACE` by hand,
+/// with the admin topping up rent, and rewritten with new config at its
+/// defaults (see `migrate_vault_data`). Accounts already on the current layout
+/// revert with `VaultUpToDate`.
+pub fn resize_vault(ctx: Context<ResizeVaultInstructionAccounts>) -> Result<()> {
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    let vault_info = ctx.accounts.vault_pda.to_account_info();
+
+    let vault = {
+        let data = vault_info.try_borrow_data()?;
+        migrate_vault_data(&data)?
+    };
+    require!(vault.token_mint == ctx.accounts.token_mint.key(), OxediumError::InvalidVault);
+
+    let rent = Rent::get()?.minimum_balance(VAULT_SPACE);
+    let shortfall = rent.saturating_sub(vault_info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: vault_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    vault_info.resize(VAULT_SPACE)?;
+    {
+        let mut data = vault_info.try_borrow_mut_data()?;
+        vault.try_serialize(&mut data.as_mut())?;
+    }
+
+    msg!("ResizeVault {{mint: {}, version: {}}}", vault.token_mint, vault.version);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ResizeVaultInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    /// CHECK: an older-layout Vault that can't deserialize as `Vault`; the
+    /// seeds, program ownership and discriminator are checked instead
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()],
+        bump,
+    )]
+    pub vault_pda: UncheckedAccount<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub system_program: Program<'info, System>,
+}
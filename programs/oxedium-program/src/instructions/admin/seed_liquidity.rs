@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+    TransferChecked,
+};
+use crate::{
+    components::{check_admin, check_vault_mint, reconcile_received, seed_vault_liquidity, transfer_fee},
+    events::{event_clock, SeedLiquidityEvent},
+    states::{Admin, Vault},
+    utils::{ADMIN_SEED, OXEDIUM_SEED, VAULT_SEED, OxediumError},
+};
+
+/// Donate tokens to a vault without minting an LP claim
+///
+/// Only `current_balance` grows: no Staker position is created and
+/// `initial_balance` is unchanged, so the donation restores health (and lowers
+/// exit and imbalance fees) for every existing staker.
+///
+/// # Arguments
+/// * `ctx` - context containing all accounts
+/// * `amount` - amount of vault tokens to transfer from `signer_ata`
+pub fn seed_liquidity(ctx: Context<SeedLiquidityInstructionAccounts>, amount: u64) -> Result<()> {
+    require!(amount > 0, OxediumError::ZeroAmount);
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+    check_vault_mint(&ctx.accounts.vault_pda, &ctx.accounts.token_mint.key())?;
+
+    let clock = Clock::get()?;
+    let mint_info = ctx.accounts.token_mint.to_account_info();
+    let expected = amount
+        .checked_sub(transfer_fee(&mint_info, amount, clock.epoch)?)
+        .ok_or(OxediumError::OverflowInSub)?;
+    let balance_before = ctx.accounts.vault_ata.amount;
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.signer_ata.to_account_info(),
+        mint: mint_info,
+        to: ctx.accounts.vault_ata.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    ctx.accounts.vault_ata.reload()?;
+    let amount = reconcile_received(balance_before, ctx.accounts.vault_ata.amount, expected)?;
+
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+    let current_balance = seed_vault_liquidity(vault, amount)?;
+
+    let (timestamp, slot) = event_clock()?;
+    emit!(SeedLiquidityEvent {
+        mint: vault.token_mint,
+        amount,
+        current_balance,
+        health_bps: vault.health_bps(),
+        timestamp,
+        slot
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SeedLiquidityInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        token::authority = vault_pda,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccountInterface>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
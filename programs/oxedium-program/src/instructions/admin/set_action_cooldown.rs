@@ -0,0 +1,33 @@
+use crate::{components::check_admin, states::{Admin, Vault}, utils::{OxediumError, OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+/// Set the minimum number of seconds between a staker's `staking`, `unstaking`,
+/// `claim` and `compound` on a vault, blocking positions that enter and leave
+/// around a single yield distribution. 0 disables the cooldown.
+pub fn set_action_cooldown(ctx: Context<SetActionCooldownInstructionAccounts>, action_cooldown: i64) -> Result<()> {
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    require!(action_cooldown >= 0, OxediumError::InvalidCooldown);
+    vault.action_cooldown = action_cooldown;
+
+    msg!("SetActionCooldown {{mint: {}, action_cooldown: {}}}", vault.token_mint.key(), action_cooldown);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetActionCooldownInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
@@ -0,0 +1,28 @@
+use crate::{components::check_admin, states::Admin, utils::{ADMIN_SEED, OXEDIUM_SEED}};
+use anchor_lang::prelude::*;
+
+/// Hands the fee-collector role to `new_collector`. Only the admin can change
+/// it; the collector itself can `collect` but not reconfigure anything.
+pub fn set_collector(ctx: Context<SetCollectorInstructionAccounts>) -> Result<()> {
+    let admin: &mut Account<'_, Admin> = &mut ctx.accounts.admin_pda;
+
+    check_admin(admin, &ctx.accounts.signer)?;
+
+    admin.collector = ctx.accounts.new_collector.key();
+
+    msg!("SetCollector {{collector: {}}}", admin.collector.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCollectorInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: No constraints, only its key is stored
+    pub new_collector: AccountInfo<'info>,
+
+    #[account(mut, seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
@@ -0,0 +1,32 @@
+use crate::{components::check_admin, states::{Admin, Vault}, utils::{OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+/// Waive the protocol fee on swaps out of a vault until `waiver_until` (unix
+/// timestamp). `protocol_fee_bps` itself is left as configured and applies
+/// again once the waiver lapses; a past timestamp (e.g. 0) ends it early.
+pub fn set_fee_waiver(ctx: Context<SetFeeWaiverInstructionAccounts>, waiver_until: i64) -> Result<()> {
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    vault.protocol_fee_waiver_until = waiver_until;
+
+    msg!("SetFeeWaiver {{mint: {}, waiver_until: {}}}", vault.token_mint.key(), waiver_until);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeWaiverInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
@@ -0,0 +1,32 @@
+use crate::{components::check_admin, states::{Admin, Vault}, utils::{OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+/// Set whether a vault's Pyth feed quotes tokens per USD, so swaps price with
+/// the reciprocal of the feed price. TWAP and price-jump checks compare the raw
+/// feed price with its own history, so they are unaffected.
+pub fn set_invert_price(ctx: Context<SetInvertPriceInstructionAccounts>, invert_price: bool) -> Result<()> {
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    vault.invert_price = invert_price;
+
+    msg!("SetInvertPrice {{mint: {}, invert_price: {}}}", vault.token_mint.key(), invert_price);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetInvertPriceInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
@@ -0,0 +1,32 @@
+use crate::{components::check_admin, states::{Admin, Vault}, utils::{OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+/// Set how far, in bps, a vault's oracle price may move from its last swap
+/// price within `PRICE_JUMP_WINDOW_SECS` before swaps revert with
+/// `PriceJumpTooLarge`. 0 disables the circuit breaker.
+pub fn set_max_price_jump(ctx: Context<SetMaxPriceJumpInstructionAccounts>, max_price_jump_bps: u64) -> Result<()> {
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    vault.max_price_jump_bps = max_price_jump_bps;
+
+    msg!("SetMaxPriceJump {{mint: {}, max_price_jump_bps: {}}}", vault.token_mint.key(), max_price_jump_bps);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxPriceJumpInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
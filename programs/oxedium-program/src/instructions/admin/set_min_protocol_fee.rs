@@ -0,0 +1,32 @@
+use crate::{components::check_admin, states::{Admin, Vault}, utils::{OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+/// Set a vault's protocol fee floor: every swap out of it pays the protocol at
+/// least `min_protocol_fee` token units, capped at what the LP fee leaves of
+/// the output. 0 disables it; a fee waiver suspends it.
+pub fn set_min_protocol_fee(ctx: Context<SetMinProtocolFeeInstructionAccounts>, min_protocol_fee: u64) -> Result<()> {
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    vault.min_protocol_fee = min_protocol_fee;
+
+    msg!("SetMinProtocolFee {{mint: {}, min_protocol_fee: {}}}", vault.token_mint.key(), min_protocol_fee);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMinProtocolFeeInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
@@ -0,0 +1,33 @@
+use crate::{components::check_admin, states::{Admin, Vault}, utils::{OxediumError, OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+/// Set how many seconds a position must wait after its last action before it
+/// can `claim` or `compound` on a vault, so a stake placed just ahead of a
+/// large swap can't capture that swap's fees at once. 0 disables the wait.
+pub fn set_min_stake_age(ctx: Context<SetMinStakeAgeInstructionAccounts>, min_stake_age: i64) -> Result<()> {
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    require!(min_stake_age >= 0, OxediumError::InvalidMinStakeAge);
+    vault.min_stake_age = min_stake_age;
+
+    msg!("SetMinStakeAge {{mint: {}, min_stake_age: {}}}", vault.token_mint.key(), min_stake_age);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMinStakeAgeInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
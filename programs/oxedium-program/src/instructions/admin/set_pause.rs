@@ -1,29 +1,26 @@
 use crate::{components::check_admin, states::Admin, utils::{ADMIN_SEED, OXEDIUM_SEED}};
 use anchor_lang::prelude::*;
 
+/// Pause or resume swaps, staking, unstaking and claims.
+/// Admin instructions stay callable while paused.
 #[inline(never)]
-pub fn update_admin(ctx: Context<UpdateAdminInstructionAccounts>) -> Result<()> {
+pub fn set_pause(ctx: Context<SetPauseInstructionAccounts>, paused: bool) -> Result<()> {
     let admin: &mut Account<'_, Admin> = &mut ctx.accounts.admin_pda;
 
     check_admin(admin, &ctx.accounts.signer)?;
 
-    admin.pubkey = ctx.accounts.new_admin.key();
+    admin.paused = paused;
 
-    msg!("UpdateAdmin {{new_admin: {}}}", admin.pubkey.key());
+    msg!("SetPause {{paused: {}}}", paused);
 
     Ok(())
 }
 
 #[derive(Accounts)]
-pub struct UpdateAdminInstructionAccounts<'info> {
+pub struct SetPauseInstructionAccounts<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    /// CHECK: No constraints, assumed valid by admin
-    pub new_admin: AccountInfo<'info>,
-
     #[account(mut, seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
     pub admin_pda: Account<'info, Admin>,
-
-    pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,32 @@
+use crate::{components::check_admin, states::{Admin, Vault}, utils::{OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+/// Set whether a vault holds a pegged asset. A swap between two stable-mode
+/// vaults pays the base fee however imbalanced they are; the liquidity impact
+/// curve and the protocol fee still apply.
+pub fn set_stable_mode(ctx: Context<SetStableModeInstructionAccounts>, stable_mode: bool) -> Result<()> {
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    vault.stable_mode = stable_mode;
+
+    msg!("SetStableMode {{mint: {}, stable_mode: {}}}", vault.token_mint.key(), stable_mode);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetStableModeInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
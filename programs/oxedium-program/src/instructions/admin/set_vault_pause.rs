@@ -0,0 +1,31 @@
+use crate::{components::check_admin, states::{Admin, Vault}, utils::{OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+/// Pause or resume a single vault. A paused vault rejects swaps on either side
+/// and new deposits, but stakers can still unstake and claim.
+pub fn set_vault_pause(ctx: Context<SetVaultPauseInstructionAccounts>, paused: bool) -> Result<()> {
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    vault.paused = paused;
+
+    msg!("SetVaultPause {{mint: {}, paused: {}}}", vault.token_mint.key(), paused);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetVaultPauseInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
@@ -0,0 +1,33 @@
+use crate::{components::check_admin, states::{Admin, Vault}, utils::{OxediumError, OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+/// Set how many seconds an unstake queued with `request_unstake` waits before
+/// `finalize_unstake` can pay it out. 0 lets it be finalized right away;
+/// `unstaking` is unaffected.
+pub fn set_withdraw_delay(ctx: Context<SetWithdrawDelayInstructionAccounts>, withdraw_delay: i64) -> Result<()> {
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    require!(withdraw_delay >= 0, OxediumError::InvalidWithdrawDelay);
+    vault.withdraw_delay = withdraw_delay;
+
+    msg!("SetWithdrawDelay {{mint: {}, withdraw_delay: {}}}", vault.token_mint.key(), withdraw_delay);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetWithdrawDelayInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+use crate::{components::{check_admin, check_dust_sweepable, forfeit_dust, refund_rent_subsidy, rent_subsidy_refund}, states::{Admin, RentReserve, Staker, Vault}, utils::*};
+
+/// Close a drained Staker PDA left holding a `pending_claim` below `dust_threshold`,
+/// forfeiting that dust to the vault's active LPs.
+///
+/// Safeguard: the position must have had no owner action (staking, unstaking,
+/// claim or compound) for `DUST_SWEEP_GRACE_PERIOD`. Until then the owner can
+/// claim the dust or `close_staker` themselves. Rent goes back to the owner, except
+/// the subsidized amount if the rent reserve paid for the PDA.
+///
+/// # Arguments
+/// * `ctx` - context containing the staker, its owner, the vault and optional rent reserve
+/// * `dust_threshold` - Sweep only if `pending_claim` is below this (at most `MAX_DUST_THRESHOLD`)
+pub fn sweep_dust(ctx: Context<SweepDustInstructionAccounts>, dust_threshold: u64) -> Result<()> {
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    let now: i64 = Clock::get()?.unix_timestamp;
+    let staker: &mut Account<'_, Staker> = &mut ctx.accounts.staker_pda;
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+
+    check_dust_sweepable(staker, dust_threshold, now)?;
+    let dust: u64 = forfeit_dust(staker, vault)?;
+
+    if staker.rent_subsidized {
+        let rent_reserve = ctx.accounts.rent_reserve_pda
+            .as_mut()
+            .ok_or(OxediumError::InsufficientRentReserve)?;
+
+        let staker_info = ctx.accounts.staker_pda.to_account_info();
+        let refund: u64 = rent_subsidy_refund(staker_info.lamports(), ctx.accounts.staker_pda.subsidized_rent);
+
+        refund_rent_subsidy(rent_reserve, refund)?;
+
+        // Return the subsidy to the reserve; `close = owner` then moves the rest to the owner
+        **staker_info.try_borrow_mut_lamports()? -= refund;
+        **rent_reserve.to_account_info().try_borrow_mut_lamports()? += refund;
+    }
+
+    msg!("SweepDust {{owner: {}, vault: {}, dust: {}}}", ctx.accounts.owner.key(), ctx.accounts.vault_pda.key(), dust);
+
+    Ok(())
+}
+
+/// Accounts required for the sweep_dust instruction
+#[derive(Accounts)]
+pub struct SweepDustInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    /// CHECK: No constraints beyond matching the staker's owner; only receives the rent refund
+    #[account(mut, address = staker_pda.owner @ OxediumError::InvalidStaker)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [STAKER_SEED.as_bytes(), vault_pda.key().as_ref(), owner.key().as_ref()],
+        bump,
+        constraint = staker_pda.vault == vault_pda.key() @ OxediumError::InvalidVault,
+    )]
+    pub staker_pda: Account<'info, Staker>,
+
+    /// Required only when the staker's rent was subsidized
+    #[account(mut, seeds = [OXEDIUM_SEED.as_bytes(), RENT_RESERVE_SEED.as_bytes()], bump)]
+    pub rent_reserve_pda: Option<Account<'info, RentReserve>>,
+}
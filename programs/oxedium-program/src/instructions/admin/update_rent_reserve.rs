@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    components::check_admin,
+    states::{Admin, RentReserve},
+    utils::{ADMIN_SEED, OXEDIUM_SEED, RENT_RESERVE_SEED},
+};
+
+/// Change the subsidy limits of the rent reserve; the funded balance and the
+/// count of subsidized positions are kept
+///
+/// # Arguments
+/// * `ctx` - context containing all accounts
+/// * `min_subsidized_deposit` - smallest first deposit whose Staker PDA rent is reimbursed
+/// * `max_subsidized_stakers` - number of subsidized positions allowed at once
+pub fn update_rent_reserve(
+    ctx: Context<UpdateRentReserveInstructionAccounts>,
+    min_subsidized_deposit: u64,
+    max_subsidized_stakers: u64,
+) -> Result<()> {
+    check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
+
+    let rent_reserve: &mut Account<'_, RentReserve> = &mut ctx.accounts.rent_reserve_pda;
+    rent_reserve.min_subsidized_deposit = min_subsidized_deposit;
+    rent_reserve.max_subsidized_stakers = max_subsidized_stakers;
+
+    msg!("UpdateRentReserve {{min_subsidized_deposit: {}, max_subsidized_stakers: {}}}",
+        rent_reserve.min_subsidized_deposit,
+        rent_reserve.max_subsidized_stakers
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateRentReserveInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut, seeds = [OXEDIUM_SEED.as_bytes(), RENT_RESERVE_SEED.as_bytes()], bump)]
+    pub rent_reserve_pda: Account<'info, RentReserve>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+}
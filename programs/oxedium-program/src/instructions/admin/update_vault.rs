@@ -1,37 +1,38 @@
-use crate::{components::check_admin, states::{Admin, Vault}, utils::{OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED, OxediumError}};
+use crate::{components::{apply_vault_config, check_admin, check_feed_id, merge_vault_config, vault_config, vault_update_event, VaultConfigUpdate}, events::event_clock, states::{Admin, Vault}, utils::{OXEDIUM_SEED, ADMIN_SEED, VAULT_SEED}};
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token_interface::Mint as MintInterface;
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
-pub fn update_vault(
-    ctx: Context<UpdateVaultInstructionAccounts>,
-    base_fee_bps: u64,
-    protocol_fee_bps: u64,
-    max_age_price: u64,
-    max_exit_fee_bps: u64,
-) -> Result<()> {
+/// Change the settings of an existing vault
+///
+/// Only the settings passed as `Some` change; the merged config goes through
+/// the same checks as `init_vault`. Passing `pyth_price_account` re-points the
+/// vault's oracle and pins that account's feed id; without it the oracle is
+/// left as is.
+///
+/// # Arguments
+/// * `ctx` - context containing the vault and, optionally, its new Pyth account
+/// * `update` - settings to change
+/// * `expected_feed_id` - feed id a passed `pyth_price_account` must carry; all zeros skips the check
+pub fn update_vault(ctx: Context<UpdateVaultInstructionAccounts>, update: VaultConfigUpdate, expected_feed_id: [u8; 32]) -> Result<()> {
     let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
 
     check_admin(&ctx.accounts.admin_pda, &ctx.accounts.signer)?;
 
-    require!(base_fee_bps <= 1_000, OxediumError::FeeExceeds);
-    require!(protocol_fee_bps <= 500, OxediumError::FeeExceeds);
-    require!(max_exit_fee_bps <= 1_000, OxediumError::FeeExceeds);
-    require!(max_age_price > 0, OxediumError::InvalidDeviation);
-
-    vault.base_fee_bps = base_fee_bps;
-    vault.protocol_fee_bps = protocol_fee_bps;
-    vault.max_exit_fee_bps = max_exit_fee_bps;
-    vault.pyth_price_account = ctx.accounts.pyth_price_account.key();
-    vault.max_age_price = max_age_price;
-
-    msg!("UpdateVault {{mint: {}, base_fee: {}, protocol_fee: {}, max_exit_fee: {}, max_age_price: {}}}",
-        vault.token_mint.key(),
-        vault.base_fee_bps,
-        vault.protocol_fee_bps,
-        vault.max_exit_fee_bps,
-        vault.max_age_price
-    );
+    let config = merge_vault_config(vault_config(vault), update);
+    apply_vault_config(vault, &config)?;
+
+    if let Some(pyth_price_account) = &ctx.accounts.pyth_price_account {
+        let feed_id = pyth_price_account.price_message.feed_id;
+        check_feed_id(&feed_id, &expected_feed_id)?;
+        vault.pyth_price_account = pyth_price_account.key();
+        vault.feed_id = feed_id;
+    }
+
+    msg!("UpdateVault {{mint: {}}}", vault.token_mint);
+
+    let (timestamp, slot) = event_clock()?;
+    emit!(vault_update_event(vault, timestamp, slot));
 
     Ok(())
 }
@@ -41,9 +42,9 @@ pub struct UpdateVaultInstructionAccounts<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
 
-    pub pyth_price_account: Account<'info, PriceUpdateV2>,
+    pub pyth_price_account: Option<Account<'info, PriceUpdateV2>>,
 
     #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
     pub vault_pda: Account<'info, Vault>,
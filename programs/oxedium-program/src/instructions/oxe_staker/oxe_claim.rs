@@ -1,11 +1,14 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{
+        self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+        TransferChecked,
+    },
 };
 
 use crate::{
-    components::calculate_staker_yield,
+    components::try_calculate_staker_yield,
     events::OxeClaimEvent,
     states::{OxeStaker, OxeVaultPosition, Vault},
     utils::{OXE_POSITION_SEED, OXE_STAKER_SEED, VAULT_SEED, OxediumError},
@@ -46,7 +49,7 @@ pub fn oxe_claim(ctx: Context<OxeClaimInstructionAccounts>) -> Result<()> {
     // If the staker called `oxe_unstake` with this vault in remaining_accounts,
     // earned yield was already saved to `pending_claim` and `last_cumulative_yield`
     // was advanced, so `earned` here will be 0 and only pending_claim is paid out.
-    let earned = calculate_staker_yield(
+    let earned = try_calculate_staker_yield(
         current_cumulative,
         oxe_staker.oxe_balance,
         position.last_cumulative_yield,
@@ -62,18 +65,20 @@ pub fn oxe_claim(ctx: Context<OxeClaimInstructionAccounts>) -> Result<()> {
     let seeds = &[VAULT_SEED.as_bytes(), mint_key.as_ref(), &[ctx.bumps.vault_pda]];
     let signer_seeds = &[&seeds[..]];
 
-    let cpi_accounts = token::Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.vault_ata.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
         to: ctx.accounts.signer_ata.to_account_info(),
         authority: vault_pda_info,
     };
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
             signer_seeds,
         ),
         amount,
+        ctx.accounts.token_mint.decimals,
     )?;
 
     vault.current_balance = vault.current_balance
@@ -99,7 +104,7 @@ pub struct OxeClaimInstructionAccounts<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
 
     #[account(
         seeds = [OXE_STAKER_SEED.as_bytes(), signer.key().as_ref()],
@@ -126,14 +131,20 @@ pub struct OxeClaimInstructionAccounts<'info> {
         payer = signer,
         associated_token::mint = token_mint,
         associated_token::authority = signer,
+        associated_token::token_program = token_program,
     )]
-    pub signer_ata: Account<'info, TokenAccount>,
+    pub signer_ata: InterfaceAccount<'info, TokenAccountInterface>,
 
     /// Source: vault's ATA for the vault token (protocol fees sit here)
-    #[account(mut, token::authority = vault_pda, token::mint = token_mint)]
-    pub vault_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::authority = vault_pda,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccountInterface>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
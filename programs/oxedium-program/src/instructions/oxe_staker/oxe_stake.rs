@@ -9,7 +9,7 @@ use anchor_spl::{
 };
 
 use crate::{
-    components::calculate_staker_yield,
+    components::try_calculate_staker_yield,
     events::OxeStakeEvent,
     states::{OxeGlobal, OxeStaker, OxeVaultPosition, Vault},
     utils::{OXEDIUM_SEED, OXE_GLOBAL_SEED, OXE_STAKER_SEED, OxediumError},
@@ -34,7 +34,7 @@ pub fn oxe_stake(ctx: Context<OxeStakeInstructionAccounts>, amount: u64) -> Resu
     // remaining_accounts layout: [vault_pda_0, position_pda_0, vault_pda_1, …]
     let balance_before = oxe_staker.oxe_balance;
 
-    require!(ctx.remaining_accounts.len() % 2 == 0, OxediumError::InvalidVault);
+    require!(ctx.remaining_accounts.len().is_multiple_of(2), OxediumError::InvalidVault);
 
     for i in (0..ctx.remaining_accounts.len()).step_by(2) {
         let vault_info    = &ctx.remaining_accounts[i];
@@ -65,7 +65,7 @@ pub fn oxe_stake(ctx: Context<OxeStakeInstructionAccounts>, amount: u64) -> Resu
             OxediumError::InvalidVault
         );
 
-        let earned = calculate_staker_yield(
+        let earned = try_calculate_staker_yield(
             vault.oxe_cumulative_yield_per_staker,
             balance_before,
             position.last_cumulative_yield,
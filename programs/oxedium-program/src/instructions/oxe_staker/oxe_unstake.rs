@@ -9,7 +9,7 @@ use anchor_spl::{
 };
 
 use crate::{
-    components::calculate_staker_yield,
+    components::{check_unstake_amount, try_calculate_staker_yield},
     events::OxeUnstakeEvent,
     states::{OxeGlobal, OxeStaker, OxeVaultPosition, Vault},
     utils::{OXEDIUM_SEED, OXE_GLOBAL_SEED, OXE_STAKER_SEED, OxediumError},
@@ -29,13 +29,13 @@ pub fn oxe_unstake(ctx: Context<OxeUnstakeInstructionAccounts>, amount: u64) ->
 
     let oxe_staker: &mut Account<'_, OxeStaker> = &mut ctx.accounts.oxe_staker_pda;
 
-    require!(oxe_staker.oxe_balance >= amount, OxediumError::InsufficientBalance);
+    check_unstake_amount(oxe_staker.oxe_balance, amount)?;
 
     // ── Flush vault positions BEFORE reducing balance ────────────────────────
     // remaining_accounts layout: [vault_pda_0, position_pda_0, vault_pda_1, …]
     let balance_before = oxe_staker.oxe_balance;
 
-    require!(ctx.remaining_accounts.len() % 2 == 0, OxediumError::InvalidVault);
+    require!(ctx.remaining_accounts.len().is_multiple_of(2), OxediumError::InvalidVault);
 
     for i in (0..ctx.remaining_accounts.len()).step_by(2) {
         let vault_info    = &ctx.remaining_accounts[i];
@@ -70,7 +70,7 @@ pub fn oxe_unstake(ctx: Context<OxeUnstakeInstructionAccounts>, amount: u64) ->
         );
 
         // Capture yield earned up to this moment using the pre-unstake balance
-        let earned = calculate_staker_yield(
+        let earned = try_calculate_staker_yield(
             vault.oxe_cumulative_yield_per_staker,
             balance_before,
             position.last_cumulative_yield,
@@ -1,58 +1,80 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{
+    self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+    TransferChecked,
+};
 
-use crate::{components::calculate_staker_yield, events::ClaimEvent, states::{Staker, Vault}, utils::{VAULT_SEED, STAKER_SEED, OxediumError}};
+use crate::{components::{check_action_cooldown, check_min_stake_age, check_not_paused, check_vault_mint, release_lp_yield, snapshot_yield, split_claim}, events::{event_clock, ClaimEvent, YieldCheckpointEvent}, states::{Admin, Staker, Vault}, utils::{ADMIN_SEED, OXEDIUM_SEED, VAULT_SEED, STAKER_SEED, OxediumError}};
 
 /// Claim accumulated yield for a staker from a vault
 ///
 /// # Arguments
 /// * `ctx` - context containing all accounts required for claiming
-pub fn claim(ctx: Context<ClaimInstructionAccounts>) -> Result<()> {
+/// * `amount` - yield to claim; `0` claims everything, the rest stays in `pending_claim`
+pub fn claim(ctx: Context<ClaimInstructionAccounts>, amount: u64) -> Result<()> {
+    check_not_paused(&ctx.accounts.admin_pda)?;
+
     let vault_pda_info = ctx.accounts.vault_pda.to_account_info();
 
     let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
     let staker: &mut Account<'_, Staker> = &mut ctx.accounts.staker_pda;
 
+    check_vault_mint(vault, &ctx.accounts.token_mint.key())?;
+
+    let (timestamp, slot) = event_clock()?;
+    check_action_cooldown(staker.last_action_ts, vault.action_cooldown, timestamp)?;
+    check_min_stake_age(staker.last_action_ts, vault.min_stake_age, timestamp)?;
+
     let cumulative_yield_per_lp: u128 = vault.cumulative_yield_per_lp;
-    let staker_balance: u64 = staker.staked_amount;
-    let staker_last_cumulative_yield: u128 = staker.last_cumulative_yield;
-    let staker_pending_claim: u64 = staker.pending_claim;
+    let earned: u64 = snapshot_yield(staker, cumulative_yield_per_lp)?;
+    let claimable: u64 = staker.pending_claim;
 
-    let staker_yield: u64 = calculate_staker_yield(cumulative_yield_per_lp, staker_balance, staker_last_cumulative_yield)?;
-    let amount: u64 = staker_yield
-        .checked_add(staker_pending_claim)
-        .ok_or(OxediumError::OverflowInAdd)?;
+    require!(claimable > 0, OxediumError::ZeroAmount);
 
-    require!(amount > 0, OxediumError::ZeroAmount);
+    emit!(YieldCheckpointEvent {
+        user: staker.owner,
+        vault: vault.key(),
+        earned_since_last: earned,
+        new_pending_claim: claimable,
+        cumulative_yield_per_lp
+    });
+
+    let (amount, remaining_pending) = split_claim(claimable, amount)?;
 
     let mint_key = ctx.accounts.token_mint.key();
     let seeds = &[VAULT_SEED.as_bytes(), mint_key.as_ref(), &[ctx.bumps.vault_pda]];
     let signer_seeds = &[&seeds[..]];
 
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.vault_ata.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
         to: ctx.accounts.signer_ata.to_account_info(),
         authority: vault_pda_info
     };
 
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
             signer_seeds),
-        amount)?;
+        amount,
+        ctx.accounts.token_mint.decimals)?;
 
     vault.current_balance = vault.current_balance
         .checked_sub(amount)
         .ok_or(OxediumError::OverflowInSub)?;
+    release_lp_yield(vault, amount);
 
-    staker.last_cumulative_yield = cumulative_yield_per_lp;
-    staker.pending_claim = 0;
+    staker.pending_claim = remaining_pending;
+    staker.last_action_ts = timestamp;
 
     emit!(ClaimEvent {
         user: ctx.accounts.signer.key(),
         mint: vault.token_mint.key(),
-        amount: amount
+        amount,
+        remaining_pending,
+        timestamp,
+        slot
     });
 
     Ok(())
@@ -64,10 +86,18 @@ pub struct ClaimInstructionAccounts<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
 
-    #[account(mut, token::authority = signer, token::mint = token_mint)]
-    pub signer_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccountInterface>,
 
     #[account(
         mut,
@@ -81,9 +111,14 @@ pub struct ClaimInstructionAccounts<'info> {
     #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
     pub vault_pda: Account<'info, Vault>,
 
-    #[account(mut, token::authority = vault_pda, token::mint = token_mint)]
-    pub vault_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::authority = vault_pda,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccountInterface>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use anchor_lang::AccountSerialize;
+use anchor_spl::token_interface::{
+    self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+    TransferChecked,
+};
+
+use crate::{
+    components::{check_action_cooldown, check_min_stake_age, check_not_paused, check_staker_position, check_vault_mint, snapshot_yield, take_claimable_yield},
+    events::{event_clock, ClaimEvent, YieldCheckpointEvent},
+    states::{Admin, Staker, Vault},
+    utils::{ADMIN_SEED, OXEDIUM_SEED, VAULT_SEED, OxediumError},
+};
+
+/// Accounts per vault in `remaining_accounts`
+const CLAIM_GROUP_LEN: usize = 5;
+
+/// Claim accumulated yield from several vaults in one transaction.
+///
+/// `remaining_accounts` must be provided in groups of five:
+/// `[vault_pda, staker_pda, token_mint, signer_ata, vault_ata, ...]`.
+/// Each group runs the same settlement as a full `claim`; groups with nothing
+/// to claim are skipped instead of failing the whole batch. Every mint in the
+/// batch must belong to the passed `token_program` (SPL Token or Token-2022).
+///
+/// # Arguments
+/// * `ctx` - context containing the signer and token program
+pub fn claim_all<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAllInstructionAccounts<'info>>) -> Result<()> {
+    check_not_paused(&ctx.accounts.admin_pda)?;
+
+    let signer_key = ctx.accounts.signer.key();
+    let (timestamp, slot) = event_clock()?;
+
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len().is_multiple_of(CLAIM_GROUP_LEN),
+        OxediumError::InvalidVault
+    );
+
+    for group in ctx.remaining_accounts.chunks(CLAIM_GROUP_LEN) {
+        let vault_info      = &group[0];
+        let staker_info     = &group[1];
+        let mint_info       = &group[2];
+        let signer_ata_info = &group[3];
+        let vault_ata_info  = &group[4];
+
+        require!(vault_info.is_writable && staker_info.is_writable, OxediumError::InvalidVault);
+        require!(vault_info.owner  == &crate::ID, OxediumError::InvalidVault);
+        require!(staker_info.owner == &crate::ID, OxediumError::InvalidStaker);
+
+        let mint_key = mint_info.key();
+        let (expected_vault, vault_bump) = Pubkey::find_program_address(
+            &[VAULT_SEED.as_bytes(), mint_key.as_ref()],
+            &crate::ID,
+        );
+        require!(vault_info.key() == expected_vault, OxediumError::InvalidVault);
+
+        let mut vault = {
+            let data = vault_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            Vault::try_deserialize(&mut slice)?
+        };
+        let mut staker = {
+            let data = staker_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            Staker::try_deserialize(&mut slice)?
+        };
+
+        check_staker_position(&staker, &signer_key, vault_info.key)?;
+        check_vault_mint(&vault, &mint_key)?;
+
+        let token_program_key = ctx.accounts.token_program.key();
+        require!(mint_info.owner       == &token_program_key, OxediumError::InvalidVault);
+        require!(signer_ata_info.owner == &token_program_key, OxediumError::InvalidStaker);
+        require!(vault_ata_info.owner  == &token_program_key, OxediumError::InvalidVault);
+        let mint = {
+            let data = mint_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            MintInterface::try_deserialize(&mut slice)?
+        };
+        let signer_ata = {
+            let data = signer_ata_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            TokenAccountInterface::try_deserialize(&mut slice)?
+        };
+        let vault_ata = {
+            let data = vault_ata_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            TokenAccountInterface::try_deserialize(&mut slice)?
+        };
+        require!(signer_ata.owner == signer_key && signer_ata.mint == mint_key, OxediumError::InvalidStaker);
+        require!(vault_ata.owner == vault_info.key() && vault_ata.mint == mint_key, OxediumError::InvalidVault);
+
+        let earned: u64 = snapshot_yield(&mut staker, vault.cumulative_yield_per_lp)?;
+        let new_pending_claim: u64 = staker.pending_claim;
+        let amount: u64 = take_claimable_yield(&mut staker, &mut vault)?;
+        if amount == 0 {
+            continue;
+        }
+        check_action_cooldown(staker.last_action_ts, vault.action_cooldown, timestamp)?;
+        check_min_stake_age(staker.last_action_ts, vault.min_stake_age, timestamp)?;
+        staker.last_action_ts = timestamp;
+
+        emit!(YieldCheckpointEvent {
+            user: signer_key,
+            vault: vault_info.key(),
+            earned_since_last: earned,
+            new_pending_claim,
+            cumulative_yield_per_lp: vault.cumulative_yield_per_lp
+        });
+
+        let seeds = &[VAULT_SEED.as_bytes(), mint_key.as_ref(), &[vault_bump]];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_accounts = TransferChecked {
+            from: vault_ata_info.clone(),
+            mint: mint_info.clone(),
+            to: signer_ata_info.clone(),
+            authority: vault_info.clone(),
+        };
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                cpi_accounts,
+                signer_seeds),
+            amount,
+            mint.decimals)?;
+
+        {
+            let mut data = vault_info.try_borrow_mut_data()?;
+            vault.try_serialize(&mut data.as_mut())?;
+        }
+        {
+            let mut data = staker_info.try_borrow_mut_data()?;
+            staker.try_serialize(&mut data.as_mut())?;
+        }
+
+        emit!(ClaimEvent {
+            user: signer_key,
+            mint: mint_key,
+            amount,
+            remaining_pending: 0,
+            timestamp,
+            slot
+        });
+    }
+
+    Ok(())
+}
+
+/// Accounts context for the claim_all instruction
+#[derive(Accounts)]
+pub struct ClaimAllInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+use crate::{components::{check_staker_closable, refund_rent_subsidy, rent_subsidy_refund}, states::{RentReserve, Staker, Vault}, utils::*};
+
+/// Close a fully unstaked and claimed Staker PDA and reclaim its rent.
+///
+/// Rent goes back to the signer. If the PDA's rent was paid by the rent reserve,
+/// the subsidized amount is returned to the reserve and the subsidy slot freed;
+/// the signer still gets any lamports beyond it.
+///
+/// # Arguments
+/// * `ctx` - context containing the staker, vault and optional rent reserve
+pub fn close_staker(ctx: Context<CloseStakerInstructionAccounts>) -> Result<()> {
+    let staker: &Account<'_, Staker> = &ctx.accounts.staker_pda;
+
+    check_staker_closable(staker, ctx.accounts.vault_pda.cumulative_yield_per_lp)?;
+
+    if staker.rent_subsidized {
+        let rent_reserve = ctx.accounts.rent_reserve_pda
+            .as_mut()
+            .ok_or(OxediumError::InsufficientRentReserve)?;
+
+        let staker_info = ctx.accounts.staker_pda.to_account_info();
+        let refund: u64 = rent_subsidy_refund(staker_info.lamports(), ctx.accounts.staker_pda.subsidized_rent);
+
+        refund_rent_subsidy(rent_reserve, refund)?;
+
+        // Return the subsidy to the reserve; `close = signer` then moves the rest to the owner
+        **staker_info.try_borrow_mut_lamports()? -= refund;
+        **rent_reserve.to_account_info().try_borrow_mut_lamports()? += refund;
+    }
+
+    msg!("CloseStaker {{owner: {}, vault: {}}}", ctx.accounts.signer.key(), ctx.accounts.vault_pda.key());
+
+    Ok(())
+}
+
+/// Accounts required for the close_staker instruction
+#[derive(Accounts)]
+pub struct CloseStakerInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        close = signer,
+        seeds = [STAKER_SEED.as_bytes(), vault_pda.key().as_ref(), signer.key().as_ref()],
+        bump,
+        constraint = staker_pda.owner == signer.key() @ OxediumError::InvalidStaker,
+        constraint = staker_pda.vault == vault_pda.key() @ OxediumError::InvalidVault,
+    )]
+    pub staker_pda: Account<'info, Staker>,
+
+    /// Required only when the staker's rent was subsidized
+    #[account(mut, seeds = [OXEDIUM_SEED.as_bytes(), RENT_RESERVE_SEED.as_bytes()], bump)]
+    pub rent_reserve_pda: Option<Account<'info, RentReserve>>,
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+use crate::{components::{check_action_cooldown, check_deposit_cap, check_min_stake_age, check_not_paused, check_vault_active, check_vault_mint, compound_yield, snapshot_yield}, events::{CompoundEvent, YieldCheckpointEvent}, states::{Admin, Staker, Vault}, utils::{ADMIN_SEED, OXEDIUM_SEED, VAULT_SEED, STAKER_SEED, OxediumError}};
+
+/// Restake accumulated yield into the staker's position without a token transfer.
+/// Compounding adds principal, so it is blocked while the program or the vault is paused.
+///
+/// # Arguments
+/// * `ctx` - context containing the staker and vault
+pub fn compound(ctx: Context<CompoundInstructionAccounts>) -> Result<()> {
+    check_not_paused(&ctx.accounts.admin_pda)?;
+
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+    let staker: &mut Account<'_, Staker> = &mut ctx.accounts.staker_pda;
+
+    check_vault_mint(vault, &ctx.accounts.token_mint.key())?;
+    check_vault_active(vault)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    check_action_cooldown(staker.last_action_ts, vault.action_cooldown, now)?;
+    check_min_stake_age(staker.last_action_ts, vault.min_stake_age, now)?;
+
+    // Snapshot separately so the checkpoint is emitted; compound_yield then
+    // restakes the whole pending_claim
+    let earned: u64 = snapshot_yield(staker, vault.cumulative_yield_per_lp)?;
+    emit!(YieldCheckpointEvent {
+        user: staker.owner,
+        vault: vault.key(),
+        earned_since_last: earned,
+        new_pending_claim: staker.pending_claim,
+        cumulative_yield_per_lp: vault.cumulative_yield_per_lp
+    });
+
+    let amount: u64 = compound_yield(staker, vault)?;
+    staker.last_action_ts = now;
+
+    require!(amount > 0, OxediumError::ZeroAmount);
+    check_deposit_cap(vault.initial_balance, vault.max_total_deposits)?;
+
+    emit!(CompoundEvent {
+        user: ctx.accounts.signer.key(),
+        mint: vault.token_mint.key(),
+        amount
+    });
+
+    Ok(())
+}
+
+/// Accounts context for the compound instruction
+#[derive(Accounts)]
+pub struct CompoundInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        mut,
+        seeds = [STAKER_SEED.as_bytes(), vault_pda.key().as_ref(), signer.key().as_ref()],
+        bump,
+        constraint = staker_pda.owner == signer.key() @ OxediumError::InvalidStaker,
+        constraint = staker_pda.vault == vault_pda.key() @ OxediumError::InvalidVault,
+    )]
+    pub staker_pda: Account<'info, Staker>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+}
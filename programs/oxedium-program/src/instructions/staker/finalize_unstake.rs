@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+    TransferChecked,
+};
+use crate::{components::{check_not_paused, check_vault_mint, take_queued_withdrawal}, events::{event_clock, WithdrawFinalizedEvent}, states::{Admin, Staker, Vault}, utils::*};
+
+/// Pay out an unstake queued by `request_unstake` once its delay has passed
+///
+/// # Arguments
+/// * `ctx` - context containing all accounts required for the payout
+pub fn finalize_unstake(ctx: Context<FinalizeUnstakeInstructionAccounts>) -> Result<()> {
+    check_not_paused(&ctx.accounts.admin_pda)?;
+
+    let vault_pda_info = ctx.accounts.vault_pda.to_account_info();
+
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+    let staker: &mut Account<'_, Staker> = &mut ctx.accounts.staker_pda;
+
+    check_vault_mint(vault, &ctx.accounts.token_mint.key())?;
+
+    let (timestamp, slot) = event_clock()?;
+    let amount: u64 = take_queued_withdrawal(staker, vault, timestamp)?;
+
+    let mint_key = ctx.accounts.token_mint.key();
+    let seeds = &[VAULT_SEED.as_bytes(), mint_key.as_ref(), &[ctx.bumps.vault_pda]];
+    let signer_seeds = &[&seeds[..]];
+
+    let cpi_accounts = TransferChecked {
+        from: ctx.accounts.vault_ata.to_account_info(),
+        mint: ctx.accounts.token_mint.to_account_info(),
+        to: ctx.accounts.signer_ata.to_account_info(),
+        authority: vault_pda_info
+    };
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds),
+        amount,
+        ctx.accounts.token_mint.decimals)?;
+
+    emit!(WithdrawFinalizedEvent {
+        user: ctx.accounts.signer.key(),
+        mint: vault.token_mint,
+        amount,
+        timestamp,
+        slot
+    });
+
+    Ok(())
+}
+
+/// Accounts required for the finalize_unstake instruction
+#[derive(Accounts)]
+pub struct FinalizeUnstakeInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccountInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [STAKER_SEED.as_bytes(), vault_pda.key().as_ref(), signer.key().as_ref()],
+        bump,
+        constraint = staker_pda.owner == signer.key() @ OxediumError::InvalidStaker,
+        constraint = staker_pda.vault == vault_pda.key() @ OxediumError::InvalidVault,
+    )]
+    pub staker_pda: Account<'info, Staker>,
+
+    #[account(
+        mut,
+        token::authority = vault_pda,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccountInterface>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+use crate::{
+    components::claimable_yield,
+    events::ClaimableEvent,
+    states::{Staker, Vault},
+    utils::{OxediumError, VAULT_SEED},
+};
+
+/// Report a staker's claimable LP yield without mutating anything.
+/// Uses the same formula as `claim`, so it can be called via `simulateTransaction`
+/// to show pending rewards.
+///
+/// # Arguments
+/// * `ctx` - context containing the vault and staker position
+pub fn get_claimable(ctx: Context<GetClaimableInstructionAccounts>) -> Result<()> {
+    let vault: &Account<'_, Vault> = &ctx.accounts.vault_pda;
+    let staker: &Account<'_, Staker> = &ctx.accounts.staker_pda;
+
+    let claimable: u64 = claimable_yield(staker, vault)?;
+
+    emit!(ClaimableEvent {
+        user: staker.owner,
+        mint: vault.token_mint,
+        claimable
+    });
+
+    Ok(())
+}
+
+/// Accounts required for the get_claimable instruction
+#[derive(Accounts)]
+pub struct GetClaimableInstructionAccounts<'info> {
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(constraint = staker_pda.vault == vault_pda.key() @ OxediumError::InvalidVault)]
+    pub staker_pda: Account<'info, Staker>,
+}
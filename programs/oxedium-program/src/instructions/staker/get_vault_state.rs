@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+
+use crate::{
+    components::vault_state_event,
+    states::Vault,
+    utils::VAULT_SEED,
+};
+
+/// Report a vault's balances, health and fee settings without mutating anything.
+/// Health comes from `Vault::health_bps`, so dashboards calling this via
+/// `simulateTransaction` see the same figure the fee curves use.
+///
+/// # Arguments
+/// * `ctx` - context containing the vault
+pub fn get_vault_state(ctx: Context<GetVaultStateInstructionAccounts>) -> Result<()> {
+    let vault: &Account<'_, Vault> = &ctx.accounts.vault_pda;
+
+    emit!(vault_state_event(vault));
+
+    Ok(())
+}
+
+/// Accounts required for the get_vault_state instruction
+#[derive(Accounts)]
+pub struct GetVaultStateInstructionAccounts<'info> {
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+}
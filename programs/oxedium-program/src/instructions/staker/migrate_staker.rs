@@ -0,0 +1,72 @@
+use anchor_lang::{prelude::*, system_program::{self, Transfer}};
+use anchor_spl::token_interface::Mint as MintInterface;
+
+use crate::{components::migrate_staker_data, utils::*};
+
+/// Move a Staker PDA stored in an older layout to the current one
+///
+/// Old accounts no longer deserialize as `Staker`, so every other staker
+/// instruction rejects them until they are migrated. The account is grown to
+/// `STAKER_SPACE`, with the signer topping up rent, and rewritten with the new
+/// fields defaulted (see `migrate_staker_data`). Accounts already on the
+/// current layout revert with `StakerUpToDate`.
+///
+/// # Arguments
+/// * `ctx` - context containing the signer's Staker PDA
+pub fn migrate_staker(ctx: Context<MigrateStakerInstructionAccounts>) -> Result<()> {
+    let staker_info = ctx.accounts.staker_pda.to_account_info();
+
+    let staker = migrate_staker_data(&staker_info.try_borrow_data()?)?;
+    require!(staker.owner == ctx.accounts.signer.key(), OxediumError::InvalidStaker);
+    require!(staker.vault == ctx.accounts.vault_pda.key(), OxediumError::InvalidVault);
+
+    let rent = Rent::get()?.minimum_balance(STAKER_SPACE);
+    let shortfall = rent.saturating_sub(staker_info.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.signer.to_account_info(),
+                    to: staker_info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+
+    staker_info.resize(STAKER_SPACE)?;
+    {
+        let mut data = staker_info.try_borrow_mut_data()?;
+        staker.try_serialize(&mut data.as_mut())?;
+    }
+
+    msg!("MigrateStaker {{owner: {}, vault: {}, version: {}}}", staker.owner, staker.vault, staker.version);
+
+    Ok(())
+}
+
+/// Accounts required for the migrate_staker instruction
+#[derive(Accounts)]
+pub struct MigrateStakerInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    /// CHECK: only its address is used; the vault itself may still be on an older layout
+    #[account(seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: UncheckedAccount<'info>,
+
+    /// CHECK: an older-layout Staker that can't deserialize as `Staker`; the
+    /// seeds, program ownership and discriminator are checked instead
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [STAKER_SEED.as_bytes(), vault_pda.key().as_ref(), signer.key().as_ref()],
+        bump,
+    )]
+    pub staker_pda: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -1,7 +1,27 @@
 pub use staking::*;
 pub use unstaking::*;
+pub use request_unstake::*;
+pub use finalize_unstake::*;
 pub use claim::*;
+pub use quote_lp_yield_from_swap::*;
+pub use close_staker::*;
+pub use compound::*;
+pub use claim_all::*;
+pub use get_claimable::*;
+pub use get_vault_state::*;
+pub use migrate_staker::*;
+pub use stake_many::*;
 
 pub mod staking;
 pub mod unstaking;
-pub mod claim;
\ No newline at end of file
+pub mod request_unstake;
+pub mod finalize_unstake;
+pub mod claim;
+pub mod quote_lp_yield_from_swap;
+pub mod close_staker;
+pub mod compound;
+pub mod claim_all;
+pub mod get_claimable;
+pub mod get_vault_state;
+pub mod migrate_staker;
+pub mod stake_many;
\ No newline at end of file
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{
+    components::{check_distinct_mints, lp_yield_for_stake, price_swap, with_hypothetical_stake},
+    states::{Admin, Vault},
+    utils::{ADMIN_SEED, OXEDIUM_SEED, VAULT_SEED},
+};
+
+/// Quote the yield a hypothetical LP stake in the output vault would earn from a projected swap.
+/// Read-only: intended for `simulateTransaction`, result is returned via return data.
+///
+/// The stake is added to the output vault first, then the swap is priced through
+/// `price_swap` like a full-size `swap` with no impact limit, so the quote fails
+/// wherever that swap would.
+///
+/// # Arguments
+/// * `ctx` - context containing the vaults, mints and oracles of the projected swap; fallback
+///   price accounts go in `remaining_accounts` as for `swap`
+/// * `amount_in` - projected swap input amount
+/// * `hypothetical_stake` - amount the LP is considering staking into the output vault
+pub fn quote_lp_yield_from_swap(
+    ctx: Context<QuoteLpYieldFromSwapInstructionAccounts>,
+    amount_in: u64,
+    hypothetical_stake: u64,
+) -> Result<u64> {
+    check_distinct_mints(&ctx.accounts.token_mint_in.key(), &ctx.accounts.token_mint_out.key())?;
+
+    let clock: Clock = Clock::get()?;
+    let staked_vault_out: Vault = with_hypothetical_stake(&ctx.accounts.vault_pda_out, hypothetical_stake)?;
+
+    let quote = price_swap(
+        &ctx.accounts.admin_pda,
+        &ctx.accounts.token_mint_in,
+        &ctx.accounts.token_mint_out,
+        &ctx.accounts.pyth_price_account_in,
+        &ctx.accounts.pyth_price_account_out,
+        ctx.remaining_accounts,
+        &ctx.accounts.vault_pda_in,
+        &staked_vault_out,
+        amount_in,
+        false,
+        10_000,
+        &clock,
+    )?;
+    let lp_yield = lp_yield_for_stake(quote.result.lp_fee_amount, &staked_vault_out, hypothetical_stake)?;
+
+    msg!("QuoteLpYieldFromSwap {{amount_in: {}, hypothetical_stake: {}, lp_yield: {}}}",
+        amount_in,
+        hypothetical_stake,
+        lp_yield
+    );
+
+    Ok(lp_yield)
+}
+
+/// Accounts required for the quote_lp_yield_from_swap instruction
+#[derive(Accounts)]
+pub struct QuoteLpYieldFromSwapInstructionAccounts<'info> {
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint_in: InterfaceAccount<'info, MintInterface>,
+    pub token_mint_out: InterfaceAccount<'info, MintInterface>,
+
+    pub pyth_price_account_in: Account<'info, PriceUpdateV2>,
+    pub pyth_price_account_out: Account<'info, PriceUpdateV2>,
+
+    #[account(seeds = [VAULT_SEED.as_bytes(), token_mint_in.key().as_ref()], bump)]
+    pub vault_pda_in: Account<'info, Vault>,
+
+    #[account(seeds = [VAULT_SEED.as_bytes(), token_mint_out.key().as_ref()], bump)]
+    pub vault_pda_out: Account<'info, Vault>,
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+use crate::{components::{calculate_fee_amount, check_action_cooldown, check_not_paused, check_unstake_amount, check_vault_mint, credit_lp_or_protocol_yield, exit_fee_bps, lockup_penalty_bps, queue_withdrawal, snapshot_yield, update_staker_count}, events::{event_clock, WithdrawQueuedEvent, YieldCheckpointEvent}, states::{Admin, Staker, Vault}, utils::*};
+
+/// Queue an unstake to be paid out after the vault's `withdraw_delay`
+///
+/// Settles like `unstaking` at request time: the exit fee and lock-up penalty
+/// are charged on current vault health and credited, and the principal leaves
+/// `staked_amount` and `initial_balance`. The payout is taken out of
+/// `current_balance` into `reserved_for_withdrawals`, so swaps can't draw on it,
+/// and stays in the vault ATA until `finalize_unstake`. One request per staker
+/// can be queued at a time.
+///
+/// # Arguments
+/// * `ctx` - context containing the staker and vault
+/// * `amount` - staked amount to withdraw
+pub fn request_unstake(ctx: Context<RequestUnstakeInstructionAccounts>, amount: u64) -> Result<()> {
+    check_not_paused(&ctx.accounts.admin_pda)?;
+    require!(amount > 0, OxediumError::ZeroAmount);
+
+    let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
+    let staker: &mut Account<'_, Staker> = &mut ctx.accounts.staker_pda;
+
+    check_vault_mint(vault, &ctx.accounts.token_mint.key())?;
+    check_unstake_amount(staker.staked_amount, amount)?;
+
+    let (timestamp, slot) = event_clock()?;
+    check_action_cooldown(staker.last_action_ts, vault.action_cooldown, timestamp)?;
+
+    // Both fees are capped at 10%, so their sum stays well under 100%
+    let total_fee_bps = exit_fee_bps(vault.health_bps(), vault.max_exit_fee_bps)
+        + lockup_penalty_bps(vault, staker.stake_unlock_ts, timestamp);
+    let payout = if total_fee_bps > 0 {
+        calculate_fee_amount(amount, total_fee_bps, 0)?.0
+    } else {
+        amount
+    };
+
+    let cumulative_yield: u128 = vault.cumulative_yield_per_lp;
+    let earned: u64 = snapshot_yield(staker, cumulative_yield)?;
+    emit!(YieldCheckpointEvent {
+        user: staker.owner,
+        vault: vault.key(),
+        earned_since_last: earned,
+        new_pending_claim: staker.pending_claim,
+        cumulative_yield_per_lp: cumulative_yield
+    });
+
+    staker.last_action_ts = timestamp;
+    let staked_before = staker.staked_amount;
+    staker.staked_amount = staker.staked_amount
+        .checked_sub(amount)
+        .ok_or(OxediumError::OverflowInSub)?;
+    update_staker_count(vault, staked_before, staker.staked_amount)?;
+
+    vault.initial_balance = vault.initial_balance
+        .checked_sub(amount)
+        .ok_or(OxediumError::OverflowInSub)?;
+    vault.current_balance = vault.current_balance
+        .checked_sub(payout)
+        .ok_or(OxediumError::OverflowInSub)?;
+
+    let exit_fee = amount - payout;
+    credit_lp_or_protocol_yield(vault, exit_fee)?;
+
+    let available_ts = queue_withdrawal(staker, vault, payout, timestamp)?;
+
+    emit!(WithdrawQueuedEvent {
+        user: ctx.accounts.signer.key(),
+        mint: vault.token_mint,
+        amount,
+        payout,
+        exit_fee,
+        available_ts,
+        timestamp,
+        slot
+    });
+
+    Ok(())
+}
+
+/// Accounts required for the request_unstake instruction
+#[derive(Accounts)]
+pub struct RequestUnstakeInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
+    pub vault_pda: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [STAKER_SEED.as_bytes(), vault_pda.key().as_ref(), signer.key().as_ref()],
+        bump,
+        constraint = staker_pda.owner == signer.key() @ OxediumError::InvalidStaker,
+        constraint = staker_pda.vault == vault_pda.key() @ OxediumError::InvalidVault,
+    )]
+    pub staker_pda: Account<'info, Staker>,
+}
@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount, Transfer};
+use anchor_lang::AccountSerialize;
+use anchor_spl::token_interface::{
+    self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+    TransferChecked,
+};
+
+use crate::{
+    components::{check_action_cooldown, check_batch_amounts, check_deposit_cap, check_min_stake, check_not_paused, check_vault_active, check_vault_mint, credit_stake, reconcile_received, staking_event, transfer_fee},
+    events::{event_clock, YieldCheckpointEvent},
+    states::{Admin, Staker, Vault},
+    utils::{ADMIN_SEED, OXEDIUM_SEED, STAKER_SEED, STAKER_SPACE, STAKER_VERSION, VAULT_SEED, OxediumError},
+};
+
+/// Accounts per vault in `remaining_accounts`
+const STAKE_GROUP_LEN: usize = 5;
+
+/// Stake into several vaults in one transaction, e.g. to seed protocol-owned
+/// liquidity at launch.
+///
+/// `remaining_accounts` must be provided in groups of five:
+/// `[token_mint, vault_pda, signer_ata, staker_pda, vault_ata, ...]`, with
+/// `amounts[i]` staked through the i-th group. Each group runs the same checks
+/// and accounting as `staking`, creating the Staker PDA if needed, and emits its
+/// own `StakingEvent`. The rent reserve doesn't subsidize batch stakers. Every
+/// mint in the batch must belong to the passed `token_program` (SPL Token or
+/// Token-2022).
+///
+/// # Arguments
+/// * `ctx` - context containing the signer, admin PDA, token and system programs
+/// * `amounts` - amount of vault tokens to stake into each group's vault
+pub fn stake_many<'info>(ctx: Context<'_, '_, '_, 'info, StakeManyInstructionAccounts<'info>>, amounts: Vec<u64>) -> Result<()> {
+    check_not_paused(&ctx.accounts.admin_pda)?;
+    check_batch_amounts(ctx.remaining_accounts.len(), STAKE_GROUP_LEN, amounts.len())?;
+
+    let signer_key = ctx.accounts.signer.key();
+    let (timestamp, slot) = event_clock()?;
+    let epoch = Clock::get()?.epoch;
+
+    for (group, &amount) in ctx.remaining_accounts.chunks(STAKE_GROUP_LEN).zip(amounts.iter()) {
+        let mint_info       = &group[0];
+        let vault_info      = &group[1];
+        let signer_ata_info = &group[2];
+        let staker_info     = &group[3];
+        let vault_ata_info  = &group[4];
+
+        require!(amount > 0, OxediumError::ZeroAmount);
+        require!(vault_info.is_writable && staker_info.is_writable, OxediumError::InvalidVault);
+        require!(vault_info.owner == &crate::ID, OxediumError::InvalidVault);
+
+        let mint_key = mint_info.key();
+        let (expected_vault, _) = Pubkey::find_program_address(
+            &[VAULT_SEED.as_bytes(), mint_key.as_ref()],
+            &crate::ID,
+        );
+        require!(vault_info.key() == expected_vault, OxediumError::InvalidVault);
+
+        let mut vault = {
+            let data = vault_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            Vault::try_deserialize(&mut slice)?
+        };
+        check_vault_active(&vault)?;
+        check_vault_mint(&vault, &mint_key)?;
+
+        let (expected_staker, staker_bump) = Pubkey::find_program_address(
+            &[STAKER_SEED.as_bytes(), vault_info.key.as_ref(), signer_key.as_ref()],
+            &crate::ID,
+        );
+        require!(staker_info.key() == expected_staker, OxediumError::InvalidStaker);
+
+        let mut staker = if staker_info.owner == &crate::ID {
+            let data = staker_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            Staker::try_deserialize(&mut slice)?
+        } else {
+            let staker_seeds: &[&[u8]] = &[STAKER_SEED.as_bytes(), vault_info.key.as_ref(), signer_key.as_ref(), &[staker_bump]];
+            create_staker_account(
+                &ctx.accounts.signer.to_account_info(),
+                staker_info,
+                &ctx.accounts.system_program.to_account_info(),
+                staker_seeds,
+            )?;
+            Staker {
+                owner: Pubkey::default(),
+                vault: Pubkey::default(),
+                staked_amount: 0,
+                last_cumulative_yield: 0,
+                avg_entry_cumulative_yield: 0,
+                pending_claim: 0,
+                stake_unlock_ts: 0,
+                last_action_ts: 0,
+                rent_subsidized: false,
+                version: STAKER_VERSION,
+                pending_unstake_amount: 0,
+                unstake_available_ts: 0,
+                subsidized_rent: 0,
+            }
+        };
+        require!(staker.owner == signer_key || staker.owner == Pubkey::default(), OxediumError::InvalidStaker);
+
+        let token_program_key = ctx.accounts.token_program.key();
+        require!(mint_info.owner       == &token_program_key, OxediumError::InvalidVault);
+        require!(signer_ata_info.owner == &token_program_key, OxediumError::InvalidStaker);
+        require!(vault_ata_info.owner  == &token_program_key, OxediumError::InvalidVault);
+        let mint = {
+            let data = mint_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            MintInterface::try_deserialize(&mut slice)?
+        };
+        let signer_ata = {
+            let data = signer_ata_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            TokenAccountInterface::try_deserialize(&mut slice)?
+        };
+        let vault_ata = {
+            let data = vault_ata_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            TokenAccountInterface::try_deserialize(&mut slice)?
+        };
+        require!(signer_ata.owner == signer_key && signer_ata.mint == mint_key, OxediumError::InvalidStaker);
+        require!(vault_ata.owner == vault_info.key() && vault_ata.mint == mint_key, OxediumError::InvalidVault);
+
+        check_action_cooldown(staker.last_action_ts, vault.action_cooldown, timestamp)?;
+        let expected = amount
+            .checked_sub(transfer_fee(mint_info, amount, epoch)?)
+            .ok_or(OxediumError::OverflowInSub)?;
+        let balance_before = vault_ata.amount;
+
+        let cpi_accounts = TransferChecked {
+            from: signer_ata_info.clone(),
+            mint: mint_info.clone(),
+            to: vault_ata_info.clone(),
+            authority: ctx.accounts.signer.to_account_info(),
+        };
+
+        token_interface::transfer_checked(
+            CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+            amount,
+            mint.decimals,
+        )?;
+
+        let balance_after = {
+            let data = vault_ata_info.try_borrow_data()?;
+            let mut slice: &[u8] = &data;
+            TokenAccountInterface::try_deserialize(&mut slice)?.amount
+        };
+        let amount = reconcile_received(balance_before, balance_after, expected)?;
+
+        check_min_stake(staker.staked_amount, amount, vault.min_stake_amount)?;
+
+        staker.owner = signer_key;
+        staker.vault = vault_info.key();
+        staker.version = STAKER_VERSION;
+
+        let earned: u64 = credit_stake(&mut staker, &mut vault, amount, timestamp)?;
+        check_deposit_cap(vault.initial_balance, vault.max_total_deposits)?;
+
+        emit!(YieldCheckpointEvent {
+            user: signer_key,
+            vault: vault_info.key(),
+            earned_since_last: earned,
+            new_pending_claim: staker.pending_claim,
+            cumulative_yield_per_lp: vault.cumulative_yield_per_lp
+        });
+
+        {
+            let mut data = vault_info.try_borrow_mut_data()?;
+            vault.try_serialize(&mut data.as_mut())?;
+        }
+        {
+            let mut data = staker_info.try_borrow_mut_data()?;
+            staker.try_serialize(&mut data.as_mut())?;
+        }
+
+        emit!(staking_event(&staker, &vault, amount, timestamp, slot));
+    }
+
+    Ok(())
+}
+
+/// Creates a Staker PDA owned by this program, paid by `payer`. Like Anchor's
+/// `init`, it also takes over an address someone pre-funded with lamports,
+/// which `create_account` alone would refuse.
+fn create_staker_account<'info>(
+    payer: &AccountInfo<'info>,
+    staker_info: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    staker_seeds: &[&[u8]],
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(STAKER_SPACE);
+    let signer_seeds = &[staker_seeds];
+
+    if staker_info.lamports() == 0 {
+        return system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount { from: payer.clone(), to: staker_info.clone() },
+                signer_seeds,
+            ),
+            rent,
+            STAKER_SPACE as u64,
+            &crate::ID,
+        );
+    }
+
+    let top_up = rent.saturating_sub(staker_info.lamports());
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(system_program.clone(), Transfer { from: payer.clone(), to: staker_info.clone() }),
+            top_up,
+        )?;
+    }
+    system_program::allocate(
+        CpiContext::new_with_signer(system_program.clone(), Allocate { account_to_allocate: staker_info.clone() }, signer_seeds),
+        STAKER_SPACE as u64,
+    )?;
+    system_program::assign(
+        CpiContext::new_with_signer(system_program.clone(), Assign { account_to_assign: staker_info.clone() }, signer_seeds),
+        &crate::ID,
+    )
+}
+
+/// Accounts context for the stake_many instruction
+#[derive(Accounts)]
+pub struct StakeManyInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
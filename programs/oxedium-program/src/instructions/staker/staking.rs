@@ -1,57 +1,98 @@
 use anchor_lang::prelude::*;
-use anchor_spl::{associated_token::AssociatedToken, token::{self, Mint, Token, TokenAccount}};
-use crate::{components::calculate_staker_yield, events::StakingEvent, states::{Staker, Vault}, utils::*};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+        TransferChecked,
+    },
+};
+use crate::{components::{apply_rent_subsidy, check_deposit_cap, check_min_stake, check_action_cooldown, check_not_paused, check_vault_active, check_vault_mint, credit_stake, reconcile_received, staking_event, transfer_fee}, events::YieldCheckpointEvent, states::{Admin, RentReserve, Staker, Vault}, utils::*};
 
 /// Stake a given amount of vault tokens
 ///
+/// When the optional `rent_reserve_pda` is supplied and this is the staker's first
+/// deposit, the reserve reimburses the Staker PDA rent to the signer if the deposit
+/// qualifies (see `apply_rent_subsidy`).
+///
+/// For Token-2022 mints with a transfer fee the position and vault are credited
+/// with the amount that actually reached `vault_ata`, not `amount`.
+///
 /// # Arguments
 /// * `ctx` - context containing all accounts for staking
 /// * `amount` - amount of vault tokens to stake
 #[inline(never)]
 pub fn staking(ctx: Context<StakingInstructionAccounts>, amount: u64) -> Result<()> {
+    check_not_paused(&ctx.accounts.admin_pda)?;
     require!(amount > 0, OxediumError::ZeroAmount);
 
     let vault_pda_key = ctx.accounts.vault_pda.key();
+    let staker_data_len = ctx.accounts.staker_pda.to_account_info().data_len();
 
     let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
     let staker: &mut Account<'_, Staker> = &mut ctx.accounts.staker_pda;
 
+    check_vault_active(vault)?;
+    check_vault_mint(vault, &ctx.accounts.token_mint.key())?;
+
     let cumulative_yield: u128 = vault.cumulative_yield_per_lp;
     let staker_balance: u64 = staker.staked_amount;
-    let last_cumulative_yield: u128 = staker.last_cumulative_yield;
 
-    let cpi_accounts = token::Transfer {
+    let clock = Clock::get()?;
+    let epoch = clock.epoch;
+    check_action_cooldown(staker.last_action_ts, vault.action_cooldown, clock.unix_timestamp)?;
+    let mint_info = ctx.accounts.token_mint.to_account_info();
+    let expected = math::sub(amount, transfer_fee(&mint_info, amount, epoch)?)?;
+    let balance_before = ctx.accounts.vault_ata.amount;
+
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.signer_ata.to_account_info(),
+        mint: mint_info,
         to: ctx.accounts.vault_ata.to_account_info(),
         authority: ctx.accounts.signer.to_account_info(),
     };
 
-    token::transfer(CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts), amount)?;
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    ctx.accounts.vault_ata.reload()?;
+    let amount = reconcile_received(balance_before, ctx.accounts.vault_ata.amount, expected)?;
+
+    check_min_stake(staker_balance, amount, vault.min_stake_amount)?;
+
+    // First-time stakers: reimburse the PDA rent from the reserve when eligible
+    if staker.owner == Pubkey::default() {
+        if let Some(rent_reserve) = ctx.accounts.rent_reserve_pda.as_mut() {
+            let rent = Rent::get()?.minimum_balance(staker_data_len);
+            let subsidy = apply_rent_subsidy(rent_reserve, amount, rent)?;
+            if subsidy > 0 {
+                **rent_reserve.to_account_info().try_borrow_mut_lamports()? -= subsidy;
+                **ctx.accounts.signer.to_account_info().try_borrow_mut_lamports()? += subsidy;
+                staker.rent_subsidized = true;
+                staker.subsidized_rent = subsidy;
+            }
+        }
+    }
 
     staker.owner = ctx.accounts.signer.key();
     staker.vault = vault_pda_key;
+    staker.version = STAKER_VERSION;
+
+    let earned: u64 = credit_stake(staker, vault, amount, clock.unix_timestamp)?;
+    check_deposit_cap(vault.initial_balance, vault.max_total_deposits)?;
 
-    staker.pending_claim = staker.pending_claim
-        .checked_add(calculate_staker_yield(cumulative_yield, staker_balance, last_cumulative_yield)?)
-        .ok_or(OxediumError::OverflowInAdd)?;
-    staker.last_cumulative_yield = cumulative_yield;
-    staker.staked_amount = staker.staked_amount
-        .checked_add(amount)
-        .ok_or(OxediumError::OverflowInAdd)?;
-
-    vault.initial_balance = vault.initial_balance
-        .checked_add(amount)
-        .ok_or(OxediumError::OverflowInAdd)?;
-    vault.current_balance = vault.current_balance
-        .checked_add(amount)
-        .ok_or(OxediumError::OverflowInAdd)?;
-
-    emit!(StakingEvent {
-        user: ctx.accounts.signer.key(),
-        mint: vault.token_mint.key(),
-        amount: amount
+    emit!(YieldCheckpointEvent {
+        user: staker.owner,
+        vault: vault_pda_key,
+        earned_since_last: earned,
+        new_pending_claim: staker.pending_claim,
+        cumulative_yield_per_lp: cumulative_yield
     });
 
+    emit!(staking_event(staker, vault, amount, clock.unix_timestamp, clock.slot));
+
     Ok(())
 }
 
@@ -61,10 +102,18 @@ pub struct StakingInstructionAccounts<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
 
-    #[account(mut, token::authority = signer, token::mint = token_mint)]
-    pub signer_ata: Account<'info, TokenAccount>,
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccountInterface>,
 
     #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
     pub vault_pda: Account<'info, Vault>,
@@ -74,19 +123,24 @@ pub struct StakingInstructionAccounts<'info> {
         payer = signer,
         seeds = [STAKER_SEED.as_bytes(), vault_pda.key().as_ref(), signer.key().as_ref()],
         bump,
-        space = 8 + 32 + 32 + 8 + 16 + 8,
+        space = STAKER_SPACE,
     )]
     pub staker_pda: Account<'info, Staker>,
 
+    /// Optional: pays Staker PDA rent for qualifying first-time stakers
+    #[account(mut, seeds = [OXEDIUM_SEED.as_bytes(), RENT_RESERVE_SEED.as_bytes()], bump)]
+    pub rent_reserve_pda: Option<Account<'info, RentReserve>>,
+
     #[account(
         init_if_needed,
         payer = signer,
         associated_token::mint = token_mint,
         associated_token::authority = vault_pda,
+        associated_token::token_program = token_program,
     )]
-    pub vault_ata: Account<'info, TokenAccount>,
+    pub vault_ata: InterfaceAccount<'info, TokenAccountInterface>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
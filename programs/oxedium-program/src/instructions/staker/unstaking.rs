@@ -1,9 +1,38 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use crate::{components::{calculate_fee_amount, calculate_staker_yield}, events::UnstakingEvent, states::{Staker, Vault}, utils::*};
+use anchor_spl::token_interface::{
+    self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+    TransferChecked,
+};
+use crate::{components::{calculate_fee_amount, check_action_cooldown, check_min_out, check_not_paused, check_instant_unstake, check_unstake_amount, check_vault_mint, credit_lp_or_protocol_yield, exit_fee_bps, lockup_penalty_bps, snapshot_yield, transfer_fee, unstake_amount_for_bps, update_staker_count}, events::{UnstakingEvent, YieldCheckpointEvent}, states::{Admin, Staker, Vault}, utils::*};
+
+/// Withdraw a share of a staker's position, in bps of its current `staked_amount`
+///
+/// The amount is computed on-chain at execution, so it can't race with the
+/// position changing between reading it and sending the transaction. Exit
+/// fees, lock-up penalty and `min_out` apply exactly as in `unstaking`.
+///
+/// # Arguments
+/// * `ctx` - context containing all accounts required for unstaking
+/// * `bps` - share of the position to withdraw; `10_000` exits fully
+/// * `min_out` - minimum tokens to receive after exit fees and any Token-2022 transfer fee
+pub fn unstake_bps(ctx: Context<UnstakingInstructionAccounts>, bps: u64, min_out: u64) -> Result<()> {
+    let amount = unstake_amount_for_bps(ctx.accounts.staker_pda.staked_amount, bps)?;
+    unstaking(ctx, amount, min_out)
+}
 
+/// Withdraw staked tokens from a vault, minus the health-based exit fee and,
+/// while the position is still locked, the lock-up penalty
+///
+/// A vault with a `withdraw_delay` only pays out through `request_unstake` /
+/// `finalize_unstake`.
+///
+/// # Arguments
+/// * `ctx` - context containing all accounts required for unstaking
+/// * `amount` - staked amount to withdraw
+/// * `min_out` - minimum tokens to receive after exit fees and any Token-2022 transfer fee
 #[inline(never)]
-pub fn unstaking(ctx: Context<UnstakingInstructionAccounts>, amount: u64) -> Result<()> {
+pub fn unstaking(ctx: Context<UnstakingInstructionAccounts>, amount: u64, min_out: u64) -> Result<()> {
+    check_not_paused(&ctx.accounts.admin_pda)?;
     require!(amount > 0, OxediumError::ZeroAmount);
 
     let vault_pda_info = ctx.accounts.vault_pda.to_account_info();
@@ -11,69 +40,79 @@ pub fn unstaking(ctx: Context<UnstakingInstructionAccounts>, amount: u64) -> Res
     let vault: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda;
     let staker: &mut Account<'_, Staker> = &mut ctx.accounts.staker_pda;
 
-    require!(staker.staked_amount >= amount, OxediumError::InsufficientBalance);
+    check_vault_mint(vault, &ctx.accounts.token_mint.key())?;
+    check_instant_unstake(vault.withdraw_delay)?;
+    check_unstake_amount(staker.staked_amount, amount)?;
 
     let cumulative_yield: u128 = vault.cumulative_yield_per_lp;
-    let last_cumulative_yield: u128 = staker.last_cumulative_yield;
 
     let mut unstake_amount = amount;
-    let health = if vault.initial_balance == 0 {
-        100u128
-    } else {
-        (vault.current_balance as u128 * 100) / vault.initial_balance as u128
-    };
-    let deficit = 100u128.saturating_sub(health);
-    let curved  = deficit * deficit / 100;
-    let exit_fee_bps = (vault.max_exit_fee_bps as u128 * curved / 100) as u64;
+    let exit_fee_bps = exit_fee_bps(vault.health_bps(), vault.max_exit_fee_bps);
 
-    if exit_fee_bps > 0 {
-        unstake_amount = calculate_fee_amount(unstake_amount, exit_fee_bps, 0)?.0;
+    let clock = Clock::get()?;
+    check_action_cooldown(staker.last_action_ts, vault.action_cooldown, clock.unix_timestamp)?;
+    let lockup_fee_bps = lockup_penalty_bps(vault, staker.stake_unlock_ts, clock.unix_timestamp);
+
+    // Both fees are capped at 10%, so their sum stays well under 100%
+    let total_fee_bps = exit_fee_bps + lockup_fee_bps;
+    if total_fee_bps > 0 {
+        unstake_amount = calculate_fee_amount(unstake_amount, total_fee_bps, 0)?.0;
     }
 
+    let mint_info = ctx.accounts.token_mint.to_account_info();
+    let delivered = math::sub(unstake_amount, transfer_fee(&mint_info, unstake_amount, clock.epoch)?)?;
+    check_min_out(delivered, min_out)?;
+
     let mint_key = ctx.accounts.token_mint.key();
     let seeds = &[VAULT_SEED.as_bytes(), mint_key.as_ref(), &[ctx.bumps.vault_pda]];
     let signer_seeds = &[&seeds[..]];
 
-    let cpi_accounts = Transfer {
+    let cpi_accounts = TransferChecked {
         from: ctx.accounts.vault_ata.to_account_info(),
+        mint: mint_info,
         to: ctx.accounts.signer_ata.to_account_info(),
         authority: vault_pda_info
     };
 
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
             cpi_accounts,
             signer_seeds),
-        unstake_amount)?;
-
-    staker.pending_claim = staker.pending_claim
-        .checked_add(calculate_staker_yield(cumulative_yield, staker.staked_amount, last_cumulative_yield)?)
-        .ok_or(OxediumError::OverflowInAdd)?;
-    staker.last_cumulative_yield = cumulative_yield;
-    staker.staked_amount = staker.staked_amount
-        .checked_sub(amount)
-        .ok_or(OxediumError::OverflowInSub)?;
-
-    vault.initial_balance = vault.initial_balance
-        .checked_sub(amount)
-        .ok_or(OxediumError::OverflowInSub)?;
-    vault.current_balance = vault.current_balance
-        .checked_sub(unstake_amount)
-        .ok_or(OxediumError::OverflowInSub)?;
+        unstake_amount,
+        ctx.accounts.token_mint.decimals)?;
+
+    let earned: u64 = snapshot_yield(staker, cumulative_yield)?;
+    emit!(YieldCheckpointEvent {
+        user: staker.owner,
+        vault: vault.key(),
+        earned_since_last: earned,
+        new_pending_claim: staker.pending_claim,
+        cumulative_yield_per_lp: cumulative_yield
+    });
+
+    staker.last_action_ts = clock.unix_timestamp;
+    let staked_before = staker.staked_amount;
+    staker.staked_amount = math::sub(staker.staked_amount, amount)?;
+    update_staker_count(vault, staked_before, staker.staked_amount)?;
 
+    vault.initial_balance = math::sub(vault.initial_balance, amount)?;
+    vault.current_balance = math::sub(vault.current_balance, unstake_amount)?;
+
+    // Exit fees go to the LPs who stay; only a last LP's fee goes to protocol_yield
     let exit_fee = amount - unstake_amount;
-    if exit_fee > 0 && vault.initial_balance > 0 {
-        vault.cumulative_yield_per_lp = vault.cumulative_yield_per_lp
-            .checked_add((exit_fee as u128 * SCALE) / vault.initial_balance as u128)
-            .ok_or(OxediumError::OverflowInAdd)?;
-    }
+    let exit_fee_to_lps = credit_lp_or_protocol_yield(vault, exit_fee)?;
 
     emit!(UnstakingEvent {
         user: ctx.accounts.signer.key(),
         mint: vault.token_mint.key(),
         amount: unstake_amount,
-        extra_fee_bps: exit_fee_bps
+        extra_fee_bps: exit_fee_bps,
+        lockup_penalty_bps: lockup_fee_bps,
+        exit_fee,
+        exit_fee_to_lps,
+        timestamp: clock.unix_timestamp,
+        slot: clock.slot
     });
 
     Ok(())
@@ -85,10 +124,18 @@ pub struct UnstakingInstructionAccounts<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    pub token_mint: Account<'info, Mint>,
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
 
-    #[account(mut, token::authority = signer, token::mint = token_mint)]
-    pub signer_ata: Account<'info, TokenAccount>,
+    pub token_mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub signer_ata: InterfaceAccount<'info, TokenAccountInterface>,
 
     #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint.key().as_ref()], bump)]
     pub vault_pda: Account<'info, Vault>,
@@ -102,9 +149,14 @@ pub struct UnstakingInstructionAccounts<'info> {
     )]
     pub staker_pda: Account<'info, Staker>,
 
-    #[account(mut, token::authority = vault_pda, token::mint = token_mint)]
-    pub vault_ata: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::authority = vault_pda,
+        token::mint = token_mint,
+        token::token_program = token_program,
+    )]
+    pub vault_ata: InterfaceAccount<'info, TokenAccountInterface>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
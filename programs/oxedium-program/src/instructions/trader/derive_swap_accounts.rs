@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::components::{check_distinct_mints, derive_swap_accounts_with_programs, SwapAccounts};
+
+/// Return every PDA and ATA required to build a `swap` for the signer.
+/// Read-only: intended for `simulateTransaction`, result is returned via return data.
+///
+/// # Arguments
+/// * `ctx` - context containing the signer whose ATAs are derived
+/// * `mint_in` - mint of the token being sold
+/// * `mint_out` - mint of the token being bought
+/// * `token_program_in` - token program owning `mint_in` (SPL Token or Token-2022)
+/// * `token_program_out` - token program owning `mint_out`
+pub fn derive_swap_accounts(
+    ctx: Context<DeriveSwapAccountsInstructionAccounts>,
+    mint_in: Pubkey,
+    mint_out: Pubkey,
+    token_program_in: Pubkey,
+    token_program_out: Pubkey,
+) -> Result<SwapAccounts> {
+    check_distinct_mints(&mint_in, &mint_out)?;
+
+    Ok(derive_swap_accounts_with_programs(
+        &ctx.accounts.signer.key(),
+        &mint_in,
+        &mint_out,
+        &token_program_in,
+        &token_program_out,
+    ))
+}
+
+/// Accounts required for the derive_swap_accounts instruction
+#[derive(Accounts)]
+pub struct DeriveSwapAccountsInstructionAccounts<'info> {
+    pub signer: Signer<'info>,
+}
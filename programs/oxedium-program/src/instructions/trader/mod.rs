@@ -1,3 +1,9 @@
 pub use swap::*;
+pub use derive_swap_accounts::*;
+pub use quote_swap::*;
+pub use swap_two_hop::*;
 
-pub mod swap;
\ No newline at end of file
+pub mod swap;
+pub mod derive_swap_accounts;
+pub mod quote_swap;
+pub mod swap_two_hop;
\ No newline at end of file
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint as MintInterface;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{
+    components::price_swap,
+    events::QuoteEvent,
+    states::{Admin, Vault},
+    utils::{OxediumError, ADMIN_SEED, OXEDIUM_SEED, VAULT_SEED},
+};
+
+/// Quote a swap without moving tokens or mutating vault state.
+/// Prices through `price_swap` exactly like `swap` (pause, feed and fallback
+/// oracle checks, transfer fees, partial fills and output limits), so it can be
+/// called via `simulateTransaction` to preview the on-chain result. Only the
+/// trader's own `minimum_out` is not checked.
+///
+/// # Arguments
+/// * `ctx` - context containing the vaults, mints and oracles of the swap; fallback
+///   price accounts go in `remaining_accounts` as for `swap`
+/// * `amount_in` - amount of input tokens to quote
+/// * `allow_partial` - as in `swap`
+/// * `max_impact_bps` - as in `swap`
+pub fn quote_swap(
+    ctx: Context<QuoteSwapInstructionAccounts>,
+    amount_in: u64,
+    allow_partial: bool,
+    max_impact_bps: u64,
+) -> Result<()> {
+    let clock: Clock = Clock::get()?;
+
+    let quote = price_swap(
+        &ctx.accounts.admin_pda,
+        &ctx.accounts.token_mint_in,
+        &ctx.accounts.token_mint_out,
+        &ctx.accounts.pyth_price_account_in,
+        &ctx.accounts.pyth_price_account_out,
+        ctx.remaining_accounts,
+        &ctx.accounts.vault_pda_in,
+        &ctx.accounts.vault_pda_out,
+        amount_in,
+        allow_partial,
+        max_impact_bps,
+        &clock,
+    )?;
+
+    emit!(QuoteEvent {
+        amount_in: quote.gross_amount_in,
+        raw_amount_out: quote.result.raw_amount_out,
+        net_amount_out: quote.result.net_amount_out,
+        swap_fee_bps: quote.result.swap_fee_bps,
+        lp_fee: quote.result.lp_fee_amount,
+        protocol_fee: quote.result.protocol_fee_amount,
+        delivered_amount_out: quote.delivered_out,
+    });
+
+    Ok(())
+}
+
+/// Accounts required for the quote_swap instruction
+#[derive(Accounts)]
+pub struct QuoteSwapInstructionAccounts<'info> {
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    pub token_mint_in: InterfaceAccount<'info, MintInterface>,
+
+    #[account(constraint = token_mint_out.key() != token_mint_in.key() @ OxediumError::SameMint)]
+    pub token_mint_out: InterfaceAccount<'info, MintInterface>,
+
+    pub pyth_price_account_in: Account<'info, PriceUpdateV2>,
+    pub pyth_price_account_out: Account<'info, PriceUpdateV2>,
+
+    #[account(seeds = [VAULT_SEED.as_bytes(), token_mint_in.key().as_ref()], bump)]
+    pub vault_pda_in: Account<'info, Vault>,
+
+    #[account(seeds = [VAULT_SEED.as_bytes(), token_mint_out.key().as_ref()], bump)]
+    pub vault_pda_out: Account<'info, Vault>,
+}
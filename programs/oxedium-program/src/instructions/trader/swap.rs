@@ -1,123 +1,165 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount},
+    token_interface::{
+        self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+        TransferChecked,
+    },
 };
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
 use crate::{
-    components::compute_swap_math,
+    components::{check_solvency, log_fee_clamp, price_swap, reconcile_received, select_price, settle_swap, slippage_min_out, split_referral_fee, SwapQuote},
     events::SwapEvent,
-    states::{OxeGlobal, Vault},
-    utils::{OxediumError, OXEDIUM_SEED, OXE_GLOBAL_SEED, SCALE, VAULT_SEED},
+    states::{Admin, OxeGlobal, Vault},
+    utils::{math, OxediumError, ADMIN_SEED, OXEDIUM_SEED, OXE_GLOBAL_SEED, VAULT_SEED},
 };
 
 /// Swap tokens from one vault to another
 ///
+/// If a vault's `pyth_price_account` is stale or future-dated, the leg is priced
+/// from its `fallback_pyth_price_account` instead, when that account is passed
+/// in `remaining_accounts` (in any order) and is fresh. The program logs which
+/// feed priced each leg.
+///
+/// Token-2022 transfer fees are priced in on both legs: the swap math runs on
+/// what reaches `vault_ata_in`, and `minimum_out` is checked against what reaches
+/// `signer_ata_out`.
+///
 /// # Arguments
 /// * `ctx` - context containing all accounts
 /// * `amount_in` - amount of input tokens from user
-/// * `minimum_out` - minimum amount output
+/// * `minimum_out` - minimum amount output, net of the output mint's transfer fee
+/// * `allow_partial` - if the full `amount_in` doesn't fit the output vault, fill the
+///   largest amount that does (see `max_fillable_amount_in`) and leave the rest with the user
+/// * `referral_fee_bps` - share of the protocol fee paid to `referrer_ata`, clamped to the
+///   output vault's `max_referral_fee_bps`; ignored without a referrer
+/// * `max_impact_bps` - most the utilization curve may add on top of the base fee,
+///   in bps, whatever `minimum_out` allows; 10 000 for no limit
 pub fn swap(
     ctx: Context<SwapInstructionAccounts>,
     amount_in: u64,
     minimum_out: u64,
+    allow_partial: bool,
+    referral_fee_bps: u64,
+    max_impact_bps: u64,
 ) -> Result<()> {
-    require!(amount_in > 0, OxediumError::ZeroAmount);
-    require!(ctx.accounts.token_mint_in.key() != ctx.accounts.token_mint_out.key(), OxediumError::SameMint);
+    execute_swap(ctx, amount_in, minimum_out, 10_000, allow_partial, referral_fee_bps, max_impact_bps)
+}
 
+/// Swap tokens from one vault to another, bounding slippage in relative terms
+///
+/// Same as `swap`, but instead of an absolute `minimum_out` the program derives
+/// the minimum from the oracle-implied output: `raw_amount_out × (10 000 −
+/// max_slippage_bps) / 10 000` (see `slippage_min_out`). The bound covers fees,
+/// utilization impact and the output mint's transfer fee together, so clients
+/// don't need a quote first.
+///
+/// # Arguments
+/// * `ctx` - context containing all accounts
+/// * `amount_in` - amount of input tokens from user
+/// * `max_slippage_bps` - largest total deviation from the oracle price, in bps;
+///   10 000 for no limit
+/// * `allow_partial` - as in `swap`; the bound applies to the filled amount
+/// * `referral_fee_bps` - as in `swap`
+/// * `max_impact_bps` - as in `swap`
+pub fn swap_with_slippage_bps(
+    ctx: Context<SwapInstructionAccounts>,
+    amount_in: u64,
+    max_slippage_bps: u64,
+    allow_partial: bool,
+    referral_fee_bps: u64,
+    max_impact_bps: u64,
+) -> Result<()> {
+    execute_swap(ctx, amount_in, 0, max_slippage_bps, allow_partial, referral_fee_bps, max_impact_bps)
+}
+
+/// Body shared by `swap` and `swap_with_slippage_bps`: the delivered output must
+/// meet both `minimum_out` and the `max_slippage_bps` bound.
+fn execute_swap(
+    ctx: Context<SwapInstructionAccounts>,
+    amount_in: u64,
+    minimum_out: u64,
+    max_slippage_bps: u64,
+    allow_partial: bool,
+    referral_fee_bps: u64,
+    max_impact_bps: u64,
+) -> Result<()> {
     let vault_pda_out_info = ctx.accounts.vault_pda_out.to_account_info();
 
     let vault_in: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda_in;
     let vault_out: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda_out;
 
-    if ctx.accounts.pyth_price_account_in.key() != vault_in.pyth_price_account {
-        return Err(OxediumError::InvalidPythAccount.into());
-    }
-    if ctx.accounts.pyth_price_account_out.key() != vault_out.pyth_price_account {
-        return Err(OxediumError::InvalidPythAccount.into());
-    }
-
     let clock: Clock = Clock::get()?;
     let current_timestamp: i64 = clock.unix_timestamp;
 
-    let publish_time_in = ctx.accounts.pyth_price_account_in.price_message.publish_time;
-    let publish_time_out = ctx.accounts.pyth_price_account_out.price_message.publish_time;
-
-    if publish_time_in > current_timestamp || publish_time_out > current_timestamp {
-        return Err(OxediumError::OracleDataTooOld.into());
-    }
-
-    let max_age_vault_in = current_timestamp - publish_time_in;
-    let max_age_vault_out = current_timestamp - publish_time_out;
-
-    if max_age_vault_in > vault_in.max_age_price as i64 {
-        msg!("Vault In: Price feed stale by {} seconds", max_age_vault_in);
-        return Err(OxediumError::OracleDataTooOld.into());
-    }
-    if max_age_vault_out > vault_out.max_age_price as i64 {
-        msg!(
-            "Vault Out: Price feed stale by {} seconds",
-            max_age_vault_out
-        );
-        return Err(OxediumError::OracleDataTooOld.into());
-    }
-
-    let result = compute_swap_math(
-        amount_in,
-        ctx.accounts.pyth_price_account_in.price_message,
-        ctx.accounts.pyth_price_account_out.price_message,
-        ctx.accounts.token_mint_in.decimals,
-        ctx.accounts.token_mint_out.decimals,
+    let requested_amount_in = amount_in;
+    let SwapQuote { gross_amount_in, amount_in, result, delivered_out, oracle_in, oracle_out, fallback_in, fallback_out } = price_swap(
+        &ctx.accounts.admin_pda,
+        &ctx.accounts.token_mint_in,
+        &ctx.accounts.token_mint_out,
+        &ctx.accounts.pyth_price_account_in,
+        &ctx.accounts.pyth_price_account_out,
+        ctx.remaining_accounts,
         vault_in,
-        vault_out
+        vault_out,
+        requested_amount_in,
+        allow_partial,
+        max_impact_bps,
+        &clock,
     )?;
+    log_fee_clamp(&result);
+    msg!("PriceFeeds {{in: {}, out: {}}}",
+        if fallback_in { vault_in.fallback_pyth_price_account } else { vault_in.pyth_price_account },
+        if fallback_out { vault_out.fallback_pyth_price_account } else { vault_out.pyth_price_account },
+    );
+
+    let mint_in_info = ctx.accounts.token_mint_in.to_account_info();
+    let mint_out_info = ctx.accounts.token_mint_out.to_account_info();
 
-    if result.net_amount_out < minimum_out {
+    let minimum_out = minimum_out.max(slippage_min_out(result.raw_amount_out, max_slippage_bps));
+    if delivered_out < minimum_out {
         return Err(OxediumError::HighSlippage.into());
     }
 
-    vault_in.current_balance = vault_in.current_balance
-        .checked_add(amount_in)
-        .ok_or(OxediumError::OverflowInAdd)?;
-    vault_out.current_balance = vault_out.current_balance
-        .checked_sub(result.net_amount_out)
-        .ok_or(OxediumError::OverflowInSub)?;
-
-    // When initial_balance == 0 the vault has no LPs yet; fees are not distributed
-    // but remain in current_balance as excess liquidity, improving the health ratio
-    // for when LPs eventually join (bootstrap behaviour — intentional).
-    if vault_out.initial_balance > 0 {
-        vault_out.cumulative_yield_per_lp = vault_out.cumulative_yield_per_lp
-            .checked_add((result.lp_fee_amount as u128 * SCALE) / vault_out.initial_balance as u128)
-            .ok_or(OxediumError::OverflowInAdd)?;
-
-        // Accumulate protocol fee per OXE staker using the same SCALE pattern.
-        // If no OXE has been staked yet, protocol fees remain in the vault as
-        // excess liquidity (improves vault health until stakers join).
-        let total_oxe = ctx.accounts.oxe_global_pda.total_oxe_staked;
-        if total_oxe > 0 && result.protocol_fee_amount > 0 {
-            let protocol_per_oxe = (result.protocol_fee_amount as u128)
-                .checked_mul(SCALE)
-                .ok_or(OxediumError::OverflowInMul)?
-                .checked_div(total_oxe as u128)
-                .ok_or(OxediumError::OverflowInDiv)?;
-            vault_out.oxe_cumulative_yield_per_staker = vault_out.oxe_cumulative_yield_per_staker
-                .checked_add(protocol_per_oxe)
-                .ok_or(OxediumError::OverflowInAdd)?;
-        }
-    }
+    // The referrer's cut comes out of the protocol fee, so the trader pays the same
+    let referral_fee_bps = if ctx.accounts.referrer_ata.is_some() { referral_fee_bps } else { 0 };
+    let (protocol_fee_amount, referral_fee_amount) =
+        split_referral_fee(result.protocol_fee_amount, referral_fee_bps, vault_out.max_referral_fee_bps)?;
+
+    // preview_swap has already rejected non-positive prices
+    let price_in = select_price(oracle_in, vault_in.use_ema_price).price as u64;
+    let price_out = select_price(oracle_out, vault_out.use_ema_price).price as u64;
+    settle_swap(
+        vault_in,
+        vault_out,
+        &result,
+        protocol_fee_amount,
+        referral_fee_amount,
+        price_in,
+        price_out,
+        ctx.accounts.oxe_global_pda.total_oxe_staked,
+        current_timestamp,
+    )?;
+    check_solvency(vault_out)?;
 
-    let cpi_accounts: token::Transfer<'_> = token::Transfer {
+    let balance_before_in = ctx.accounts.vault_ata_in.amount;
+    let cpi_accounts: TransferChecked<'_> = TransferChecked {
         from: ctx.accounts.signer_ata_in.to_account_info(),
+        mint: mint_in_info,
         to: ctx.accounts.vault_ata_in.to_account_info(),
         authority: ctx.accounts.signer.to_account_info(),
     };
-    token::transfer(
-        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts),
-        amount_in,
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program_in.to_account_info(), cpi_accounts),
+        gross_amount_in,
+        ctx.accounts.token_mint_in.decimals,
     )?;
 
+    ctx.accounts.vault_ata_in.reload()?;
+    let amount_in = reconcile_received(balance_before_in, ctx.accounts.vault_ata_in.amount, amount_in)?;
+    vault_in.current_balance = math::add(vault_in.current_balance, amount_in)?;
+
     let mint_out_key = ctx.accounts.token_mint_out.key();
     let seeds: &[&[u8]; 3] = &[
         VAULT_SEED.as_bytes(),
@@ -126,31 +168,66 @@ pub fn swap(
     ];
     let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
 
-    let cpi_accounts_out: token::Transfer<'_> = token::Transfer {
+    let cpi_accounts_out: TransferChecked<'_> = TransferChecked {
         from: ctx.accounts.vault_ata_out.to_account_info(),
+        mint: mint_out_info.clone(),
         to: ctx.accounts.signer_ata_out.to_account_info(),
-        authority: vault_pda_out_info,
+        authority: vault_pda_out_info.clone(),
     };
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.token_program_out.to_account_info(),
             cpi_accounts_out,
             signer_seeds,
         ),
         result.net_amount_out,
+        ctx.accounts.token_mint_out.decimals,
     )?;
 
+    if let Some(referrer_ata) = &ctx.accounts.referrer_ata {
+        if referral_fee_amount > 0 {
+            let cpi_accounts_referral: TransferChecked<'_> = TransferChecked {
+                from: ctx.accounts.vault_ata_out.to_account_info(),
+                mint: mint_out_info,
+                to: referrer_ata.to_account_info(),
+                authority: vault_pda_out_info,
+            };
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program_out.to_account_info(),
+                    cpi_accounts_referral,
+                    signer_seeds,
+                ),
+                referral_fee_amount,
+                ctx.accounts.token_mint_out.decimals,
+            )?;
+        }
+    }
+
     emit!(SwapEvent {
         user: ctx.accounts.signer.key(),
         fee_bps: result.swap_fee_bps,
         token_in: vault_in.token_mint,
         token_out: vault_out.token_mint,
-        amount_in: amount_in,
+        requested_amount_in,
+        amount_in,
         amount_out: result.net_amount_out,
-        price_in: ctx.accounts.pyth_price_account_in.price_message.price.unsigned_abs(),
-        price_out: ctx.accounts.pyth_price_account_out.price_message.price.unsigned_abs(),
+        price_in: oracle_in.price.unsigned_abs(),
+        price_out: oracle_out.price.unsigned_abs(),
         lp_fee: result.lp_fee_amount,
-        protocol_fee: result.protocol_fee_amount
+        protocol_fee: protocol_fee_amount,
+        referral_fee: referral_fee_amount,
+        price_impact_bps: result.price_impact_bps,
+        decimals_in: ctx.accounts.token_mint_in.decimals,
+        decimals_out: ctx.accounts.token_mint_out.decimals,
+        vault_in_balance_after: vault_in.current_balance,
+        vault_out_balance_after: vault_out.current_balance,
+        base_fee_bps: result.vault_base_fee_bps,
+        imbalance_fee_bps: result.imbalance_fee_bps,
+        liquidity_impact_bps: result.liquidity_impact_bps,
+        oracle_conf_bps: result.oracle_conf_bps,
+        timestamp: current_timestamp,
+        slot: clock.slot
     });
 
     Ok(())
@@ -162,22 +239,39 @@ pub struct SwapInstructionAccounts<'info> {
     #[account(mut)]
     pub signer: Signer<'info>,
 
-    pub token_mint_in: Account<'info, Mint>,
-    pub token_mint_out: Account<'info, Mint>,
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    #[account(mint::token_program = token_program_in)]
+    pub token_mint_in: InterfaceAccount<'info, MintInterface>,
+
+    /// Must differ from `token_mint_in`; checked during account validation so the
+    /// swap is rejected before any ATA is created or token moved
+    #[account(
+        mint::token_program = token_program_out,
+        constraint = token_mint_out.key() != token_mint_in.key() @ OxediumError::SameMint,
+    )]
+    pub token_mint_out: InterfaceAccount<'info, MintInterface>,
 
     pub pyth_price_account_in: Account<'info, PriceUpdateV2>,
     pub pyth_price_account_out: Account<'info, PriceUpdateV2>,
 
-    #[account(mut, token::authority = signer, token::mint = token_mint_in)]
-    pub signer_ata_in: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = token_mint_in,
+        token::token_program = token_program_in,
+    )]
+    pub signer_ata_in: InterfaceAccount<'info, TokenAccountInterface>,
 
     #[account(
         init_if_needed,
         payer = signer,
         associated_token::mint = token_mint_out,
         associated_token::authority = signer,
+        associated_token::token_program = token_program_out,
     )]
-    pub signer_ata_out: Account<'info, TokenAccount>,
+    pub signer_ata_out: InterfaceAccount<'info, TokenAccountInterface>,
 
     #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint_in.key().as_ref()], bump)]
     pub vault_pda_in: Account<'info, Vault>,
@@ -185,16 +279,37 @@ pub struct SwapInstructionAccounts<'info> {
     #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint_out.key().as_ref()], bump)]
     pub vault_pda_out: Account<'info, Vault>,
 
-    #[account(mut, token::authority = vault_pda_in, token::mint = token_mint_in)]
-    pub vault_ata_in: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::authority = vault_pda_in,
+        token::mint = token_mint_in,
+        token::token_program = token_program_in,
+    )]
+    pub vault_ata_in: InterfaceAccount<'info, TokenAccountInterface>,
 
-    #[account(mut, token::authority = vault_pda_out, token::mint = token_mint_out)]
-    pub vault_ata_out: Account<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::authority = vault_pda_out,
+        token::mint = token_mint_out,
+        token::token_program = token_program_out,
+    )]
+    pub vault_ata_out: InterfaceAccount<'info, TokenAccountInterface>,
 
     #[account(seeds = [OXEDIUM_SEED.as_bytes(), OXE_GLOBAL_SEED.as_bytes()], bump)]
     pub oxe_global_pda: Account<'info, OxeGlobal>,
 
+    /// Optional: receives `referral_fee_bps` of the protocol fee, in `token_mint_out`
+    #[account(
+        mut,
+        token::mint = token_mint_out,
+        token::token_program = token_program_out,
+    )]
+    pub referrer_ata: Option<InterfaceAccount<'info, TokenAccountInterface>>,
+
     pub associated_token_program: Program<'info, AssociatedToken>,
-    pub token_program: Program<'info, Token>,
+    /// Owner of `token_mint_in`: SPL Token or Token-2022
+    pub token_program_in: Interface<'info, TokenInterface>,
+    /// Owner of `token_mint_out`; may differ from `token_program_in`
+    pub token_program_out: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,277 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        self, Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface,
+        TransferChecked,
+    },
+};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::{
+    components::{check_feed_id, check_not_paused, check_vault_mint, execute_two_hop, log_fee_clamp, reconcile_received, transfer_fee},
+    events::{MultiHopEvent, SwapEvent},
+    states::{Admin, OxeGlobal, Vault},
+    utils::{OxediumError, ADMIN_SEED, OXEDIUM_SEED, OXE_GLOBAL_SEED, VAULT_SEED},
+};
+
+/// Swap `token_mint_in` for `token_mint_out` through the `token_mint_mid` vault
+/// in one transaction
+///
+/// Each leg is priced and charged exactly like a single `swap` (see
+/// `execute_two_hop`), but only the outer tokens move: the mid token paid out
+/// by leg 1 is what leg 2 takes in, so it stays in the mid vault's ATA.
+/// Referral fees and partial fills aren't supported on this path.
+///
+/// # Arguments
+/// * `ctx` - context containing all accounts
+/// * `amount_in` - amount of input tokens from user
+/// * `minimum_out` - minimum end-to-end output, net of the output mint's transfer fee
+#[inline(never)]
+pub fn swap_two_hop(ctx: Context<SwapTwoHopInstructionAccounts>, amount_in: u64, minimum_out: u64) -> Result<()> {
+    check_not_paused(&ctx.accounts.admin_pda)?;
+    require!(amount_in > 0, OxediumError::ZeroAmount);
+
+    let vault_pda_out_info = ctx.accounts.vault_pda_out.to_account_info();
+
+    let vault_in: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda_in;
+    let vault_mid: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda_mid;
+    let vault_out: &mut Account<'_, Vault> = &mut ctx.accounts.vault_pda_out;
+
+    check_vault_mint(vault_in, &ctx.accounts.token_mint_in.key())?;
+    check_vault_mint(vault_mid, &ctx.accounts.token_mint_mid.key())?;
+    check_vault_mint(vault_out, &ctx.accounts.token_mint_out.key())?;
+
+    if ctx.accounts.pyth_price_account_in.key() != vault_in.pyth_price_account
+        || ctx.accounts.pyth_price_account_mid.key() != vault_mid.pyth_price_account
+        || ctx.accounts.pyth_price_account_out.key() != vault_out.pyth_price_account
+    {
+        return Err(OxediumError::InvalidPythAccount.into());
+    }
+    check_feed_id(&ctx.accounts.pyth_price_account_in.price_message.feed_id, &vault_in.feed_id)?;
+    check_feed_id(&ctx.accounts.pyth_price_account_mid.price_message.feed_id, &vault_mid.feed_id)?;
+    check_feed_id(&ctx.accounts.pyth_price_account_out.price_message.feed_id, &vault_out.feed_id)?;
+
+    let clock: Clock = Clock::get()?;
+    let current_timestamp: i64 = clock.unix_timestamp;
+
+    let mint_in_info = ctx.accounts.token_mint_in.to_account_info();
+    let mint_out_info = ctx.accounts.token_mint_out.to_account_info();
+
+    // Price the swap on what the vault will actually receive
+    let net_amount_in = amount_in
+        .checked_sub(transfer_fee(&mint_in_info, amount_in, clock.epoch)?)
+        .ok_or(OxediumError::OverflowInSub)?;
+
+    let (leg_in, leg_out) = execute_two_hop(
+        net_amount_in,
+        ctx.accounts.pyth_price_account_in.price_message,
+        ctx.accounts.pyth_price_account_mid.price_message,
+        ctx.accounts.pyth_price_account_out.price_message,
+        ctx.accounts.token_mint_in.decimals,
+        ctx.accounts.token_mint_mid.decimals,
+        ctx.accounts.token_mint_out.decimals,
+        vault_in,
+        vault_mid,
+        vault_out,
+        ctx.accounts.oxe_global_pda.total_oxe_staked,
+        current_timestamp,
+    )?;
+    log_fee_clamp(&leg_in);
+    log_fee_clamp(&leg_out);
+
+    let delivered_out = leg_out.net_amount_out
+        .checked_sub(transfer_fee(&mint_out_info, leg_out.net_amount_out, clock.epoch)?)
+        .ok_or(OxediumError::OverflowInSub)?;
+    if delivered_out < minimum_out {
+        return Err(OxediumError::HighSlippage.into());
+    }
+
+    let balance_before_in = ctx.accounts.vault_ata_in.amount;
+    let cpi_accounts: TransferChecked<'_> = TransferChecked {
+        from: ctx.accounts.signer_ata_in.to_account_info(),
+        mint: mint_in_info,
+        to: ctx.accounts.vault_ata_in.to_account_info(),
+        authority: ctx.accounts.signer.to_account_info(),
+    };
+    token_interface::transfer_checked(
+        CpiContext::new(ctx.accounts.token_program_in.to_account_info(), cpi_accounts),
+        amount_in,
+        ctx.accounts.token_mint_in.decimals,
+    )?;
+
+    ctx.accounts.vault_ata_in.reload()?;
+    let received_in = reconcile_received(balance_before_in, ctx.accounts.vault_ata_in.amount, net_amount_in)?;
+    vault_in.current_balance = vault_in.current_balance
+        .checked_add(received_in)
+        .ok_or(OxediumError::OverflowInAdd)?;
+
+    let mint_out_key = ctx.accounts.token_mint_out.key();
+    let seeds: &[&[u8]; 3] = &[
+        VAULT_SEED.as_bytes(),
+        mint_out_key.as_ref(),
+        &[ctx.bumps.vault_pda_out],
+    ];
+    let signer_seeds: &[&[&[u8]]; 1] = &[&seeds[..]];
+
+    let cpi_accounts_out: TransferChecked<'_> = TransferChecked {
+        from: ctx.accounts.vault_ata_out.to_account_info(),
+        mint: mint_out_info,
+        to: ctx.accounts.signer_ata_out.to_account_info(),
+        authority: vault_pda_out_info,
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program_out.to_account_info(),
+            cpi_accounts_out,
+            signer_seeds,
+        ),
+        leg_out.net_amount_out,
+        ctx.accounts.token_mint_out.decimals,
+    )?;
+
+    let user = ctx.accounts.signer.key();
+    emit!(SwapEvent {
+        user,
+        fee_bps: leg_in.swap_fee_bps,
+        token_in: vault_in.token_mint,
+        token_out: vault_mid.token_mint,
+        requested_amount_in: amount_in,
+        amount_in: received_in,
+        amount_out: leg_in.net_amount_out,
+        price_in: ctx.accounts.pyth_price_account_in.price_message.price.unsigned_abs(),
+        price_out: ctx.accounts.pyth_price_account_mid.price_message.price.unsigned_abs(),
+        lp_fee: leg_in.lp_fee_amount,
+        protocol_fee: leg_in.protocol_fee_amount,
+        referral_fee: 0,
+        price_impact_bps: leg_in.price_impact_bps,
+        decimals_in: ctx.accounts.token_mint_in.decimals,
+        decimals_out: ctx.accounts.token_mint_mid.decimals,
+        vault_in_balance_after: vault_in.current_balance,
+        vault_out_balance_after: vault_mid.current_balance,
+        base_fee_bps: leg_in.vault_base_fee_bps,
+        imbalance_fee_bps: leg_in.imbalance_fee_bps,
+        liquidity_impact_bps: leg_in.liquidity_impact_bps,
+        oracle_conf_bps: leg_in.oracle_conf_bps,
+        timestamp: current_timestamp,
+        slot: clock.slot
+    });
+    emit!(SwapEvent {
+        user,
+        fee_bps: leg_out.swap_fee_bps,
+        token_in: vault_mid.token_mint,
+        token_out: vault_out.token_mint,
+        requested_amount_in: leg_in.net_amount_out,
+        amount_in: leg_in.net_amount_out,
+        amount_out: leg_out.net_amount_out,
+        price_in: ctx.accounts.pyth_price_account_mid.price_message.price.unsigned_abs(),
+        price_out: ctx.accounts.pyth_price_account_out.price_message.price.unsigned_abs(),
+        lp_fee: leg_out.lp_fee_amount,
+        protocol_fee: leg_out.protocol_fee_amount,
+        referral_fee: 0,
+        price_impact_bps: leg_out.price_impact_bps,
+        decimals_in: ctx.accounts.token_mint_mid.decimals,
+        decimals_out: ctx.accounts.token_mint_out.decimals,
+        vault_in_balance_after: vault_mid.current_balance,
+        vault_out_balance_after: vault_out.current_balance,
+        base_fee_bps: leg_out.vault_base_fee_bps,
+        imbalance_fee_bps: leg_out.imbalance_fee_bps,
+        liquidity_impact_bps: leg_out.liquidity_impact_bps,
+        oracle_conf_bps: leg_out.oracle_conf_bps,
+        timestamp: current_timestamp,
+        slot: clock.slot
+    });
+    emit!(MultiHopEvent {
+        user,
+        token_in: vault_in.token_mint,
+        token_mid: vault_mid.token_mint,
+        token_out: vault_out.token_mint,
+        amount_in: received_in,
+        amount_mid: leg_in.net_amount_out,
+        amount_out: leg_out.net_amount_out,
+        timestamp: current_timestamp,
+        slot: clock.slot
+    });
+
+    Ok(())
+}
+
+/// Accounts required for the swap_two_hop instruction
+#[derive(Accounts)]
+pub struct SwapTwoHopInstructionAccounts<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()], bump)]
+    pub admin_pda: Account<'info, Admin>,
+
+    #[account(mint::token_program = token_program_in)]
+    pub token_mint_in: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// Never transferred, so its token program isn't needed
+    #[account(constraint = token_mint_mid.key() != token_mint_in.key() @ OxediumError::SameMint)]
+    pub token_mint_mid: Box<InterfaceAccount<'info, MintInterface>>,
+
+    #[account(
+        mint::token_program = token_program_out,
+        constraint = token_mint_out.key() != token_mint_in.key() @ OxediumError::SameMint,
+        constraint = token_mint_out.key() != token_mint_mid.key() @ OxediumError::SameMint,
+    )]
+    pub token_mint_out: Box<InterfaceAccount<'info, MintInterface>>,
+
+    pub pyth_price_account_in: Box<Account<'info, PriceUpdateV2>>,
+    pub pyth_price_account_mid: Box<Account<'info, PriceUpdateV2>>,
+    pub pyth_price_account_out: Box<Account<'info, PriceUpdateV2>>,
+
+    #[account(
+        mut,
+        token::authority = signer,
+        token::mint = token_mint_in,
+        token::token_program = token_program_in,
+    )]
+    pub signer_ata_in: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = token_mint_out,
+        associated_token::authority = signer,
+        associated_token::token_program = token_program_out,
+    )]
+    pub signer_ata_out: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint_in.key().as_ref()], bump)]
+    pub vault_pda_in: Box<Account<'info, Vault>>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint_mid.key().as_ref()], bump)]
+    pub vault_pda_mid: Box<Account<'info, Vault>>,
+
+    #[account(mut, seeds = [VAULT_SEED.as_bytes(), token_mint_out.key().as_ref()], bump)]
+    pub vault_pda_out: Box<Account<'info, Vault>>,
+
+    #[account(
+        mut,
+        token::authority = vault_pda_in,
+        token::mint = token_mint_in,
+        token::token_program = token_program_in,
+    )]
+    pub vault_ata_in: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(
+        mut,
+        token::authority = vault_pda_out,
+        token::mint = token_mint_out,
+        token::token_program = token_program_out,
+    )]
+    pub vault_ata_out: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    #[account(seeds = [OXEDIUM_SEED.as_bytes(), OXE_GLOBAL_SEED.as_bytes()], bump)]
+    pub oxe_global_pda: Account<'info, OxeGlobal>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// Owner of `token_mint_in`: SPL Token or Token-2022
+    pub token_program_in: Interface<'info, TokenInterface>,
+    /// Owner of `token_mint_out`; may differ from `token_program_in`
+    pub token_program_out: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -2,6 +2,7 @@
 
 use anchor_lang::prelude::*;
 
+use components::{SwapAccounts, VaultConfig, VaultConfigUpdate};
 use instructions::admin::*;
 use instructions::staker::*;
 use instructions::trader::*;
@@ -25,33 +26,169 @@ pub mod oxedium_program {
         instructions::admin::init_admin(ctx)
     }
 
-    pub fn update_admin(ctx: Context<UpdateAdminInstructionAccounts>) -> Result<()> {
-        instructions::admin::update_admin(ctx)
+    pub fn propose_admin(ctx: Context<ProposeAdminInstructionAccounts>) -> Result<()> {
+        instructions::admin::propose_admin(ctx)
     }
 
-    pub fn init_vault(ctx: Context<InitVaultInstructionAccounts>, base_fee_bps: u64, protocol_fee_bps: u64, max_age_price: u64, max_exit_fee_bps: u64) -> Result<()> {
-        instructions::admin::init_vault(ctx, base_fee_bps, protocol_fee_bps, max_age_price, max_exit_fee_bps)
+    pub fn accept_admin(ctx: Context<AcceptAdminInstructionAccounts>) -> Result<()> {
+        instructions::admin::accept_admin(ctx)
     }
 
-    pub fn update_vault(ctx: Context<UpdateVaultInstructionAccounts>, base_fee_bps: u64, protocol_fee_bps: u64, max_age_price: u64, max_exit_fee_bps: u64) -> Result<()> {
-        instructions::admin::update_vault(ctx, base_fee_bps, protocol_fee_bps, max_age_price, max_exit_fee_bps)
+    pub fn set_pause(ctx: Context<SetPauseInstructionAccounts>, paused: bool) -> Result<()> {
+        instructions::admin::set_pause(ctx, paused)
+    }
+
+    pub fn init_vault(ctx: Context<InitVaultInstructionAccounts>, config: VaultConfig, expected_feed_id: [u8; 32]) -> Result<()> {
+        instructions::admin::init_vault(ctx, config, expected_feed_id)
+    }
+
+    pub fn update_vault(ctx: Context<UpdateVaultInstructionAccounts>, update: VaultConfigUpdate, expected_feed_id: [u8; 32]) -> Result<()> {
+        instructions::admin::update_vault(ctx, update, expected_feed_id)
+    }
+
+    pub fn set_vault_pause(ctx: Context<SetVaultPauseInstructionAccounts>, paused: bool) -> Result<()> {
+        instructions::admin::set_vault_pause(ctx, paused)
+    }
+
+    pub fn set_fee_waiver(ctx: Context<SetFeeWaiverInstructionAccounts>, waiver_until: i64) -> Result<()> {
+        instructions::admin::set_fee_waiver(ctx, waiver_until)
+    }
+
+    pub fn set_action_cooldown(ctx: Context<SetActionCooldownInstructionAccounts>, action_cooldown: i64) -> Result<()> {
+        instructions::admin::set_action_cooldown(ctx, action_cooldown)
+    }
+
+    pub fn set_min_stake_age(ctx: Context<SetMinStakeAgeInstructionAccounts>, min_stake_age: i64) -> Result<()> {
+        instructions::admin::set_min_stake_age(ctx, min_stake_age)
+    }
+
+    pub fn set_max_price_jump(ctx: Context<SetMaxPriceJumpInstructionAccounts>, max_price_jump_bps: u64) -> Result<()> {
+        instructions::admin::set_max_price_jump(ctx, max_price_jump_bps)
+    }
+
+    pub fn set_invert_price(ctx: Context<SetInvertPriceInstructionAccounts>, invert_price: bool) -> Result<()> {
+        instructions::admin::set_invert_price(ctx, invert_price)
+    }
+
+    pub fn set_min_protocol_fee(ctx: Context<SetMinProtocolFeeInstructionAccounts>, min_protocol_fee: u64) -> Result<()> {
+        instructions::admin::set_min_protocol_fee(ctx, min_protocol_fee)
+    }
+
+    pub fn set_stable_mode(ctx: Context<SetStableModeInstructionAccounts>, stable_mode: bool) -> Result<()> {
+        instructions::admin::set_stable_mode(ctx, stable_mode)
+    }
+
+    pub fn set_withdraw_delay(ctx: Context<SetWithdrawDelayInstructionAccounts>, withdraw_delay: i64) -> Result<()> {
+        instructions::admin::set_withdraw_delay(ctx, withdraw_delay)
+    }
+
+    pub fn resize_vault(ctx: Context<ResizeVaultInstructionAccounts>) -> Result<()> {
+        instructions::admin::resize_vault(ctx)
+    }
+
+    pub fn resize_admin(ctx: Context<ResizeAdminInstructionAccounts>) -> Result<()> {
+        instructions::admin::resize_admin(ctx)
+    }
+
+    pub fn sweep_dust(ctx: Context<SweepDustInstructionAccounts>, dust_threshold: u64) -> Result<()> {
+        instructions::admin::sweep_dust(ctx, dust_threshold)
+    }
+
+    pub fn seed_liquidity(ctx: Context<SeedLiquidityInstructionAccounts>, amount: u64) -> Result<()> {
+        instructions::admin::seed_liquidity(ctx, amount)
+    }
+
+    pub fn close_vault(ctx: Context<CloseVaultInstructionAccounts>) -> Result<()> {
+        instructions::admin::close_vault(ctx)
     }
 
     pub fn init_oxe_global(ctx: Context<InitOxeGlobalInstructionAccounts>) -> Result<()> {
         instructions::admin::init_oxe_global(ctx)
     }
 
+    pub fn init_rent_reserve(ctx: Context<InitRentReserveInstructionAccounts>, min_subsidized_deposit: u64, max_subsidized_stakers: u64) -> Result<()> {
+        instructions::admin::init_rent_reserve(ctx, min_subsidized_deposit, max_subsidized_stakers)
+    }
+
+    pub fn update_rent_reserve(ctx: Context<UpdateRentReserveInstructionAccounts>, min_subsidized_deposit: u64, max_subsidized_stakers: u64) -> Result<()> {
+        instructions::admin::update_rent_reserve(ctx, min_subsidized_deposit, max_subsidized_stakers)
+    }
+
+    pub fn fund_rent_reserve(ctx: Context<FundRentReserveInstructionAccounts>, amount: u64) -> Result<()> {
+        instructions::admin::fund_rent_reserve(ctx, amount)
+    }
+
+    pub fn collect(ctx: Context<CollectInstructionAccounts>) -> Result<()> {
+        instructions::admin::collect(ctx)
+    }
+
+    pub fn collect_all<'info>(ctx: Context<'_, '_, '_, 'info, CollectAllInstructionAccounts<'info>>) -> Result<()> {
+        instructions::admin::collect_all(ctx)
+    }
+
+    pub fn reconcile_vault(ctx: Context<ReconcileVaultInstructionAccounts>) -> Result<()> {
+        instructions::admin::reconcile_vault(ctx)
+    }
+
+    pub fn set_collector(ctx: Context<SetCollectorInstructionAccounts>) -> Result<()> {
+        instructions::admin::set_collector(ctx)
+    }
+
     // LP staker instructions
     pub fn staking(ctx: Context<StakingInstructionAccounts>, amount: u64) -> Result<()> {
         instructions::staker::staking(ctx, amount)
     }
 
-    pub fn unstaking(ctx: Context<UnstakingInstructionAccounts>, amount: u64) -> Result<()> {
-        instructions::staker::unstaking(ctx, amount)
+    pub fn unstaking(ctx: Context<UnstakingInstructionAccounts>, amount: u64, min_out: u64) -> Result<()> {
+        instructions::staker::unstaking(ctx, amount, min_out)
+    }
+
+    pub fn unstake_bps(ctx: Context<UnstakingInstructionAccounts>, bps: u64, min_out: u64) -> Result<()> {
+        instructions::staker::unstake_bps(ctx, bps, min_out)
+    }
+
+    pub fn request_unstake(ctx: Context<RequestUnstakeInstructionAccounts>, amount: u64) -> Result<()> {
+        instructions::staker::request_unstake(ctx, amount)
+    }
+
+    pub fn finalize_unstake(ctx: Context<FinalizeUnstakeInstructionAccounts>) -> Result<()> {
+        instructions::staker::finalize_unstake(ctx)
+    }
+
+    pub fn claim(ctx: Context<ClaimInstructionAccounts>, amount: u64) -> Result<()> {
+        instructions::staker::claim(ctx, amount)
+    }
+
+    pub fn claim_all<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAllInstructionAccounts<'info>>) -> Result<()> {
+        instructions::staker::claim_all(ctx)
+    }
+
+    pub fn stake_many<'info>(ctx: Context<'_, '_, '_, 'info, StakeManyInstructionAccounts<'info>>, amounts: Vec<u64>) -> Result<()> {
+        instructions::staker::stake_many(ctx, amounts)
+    }
+
+    pub fn compound(ctx: Context<CompoundInstructionAccounts>) -> Result<()> {
+        instructions::staker::compound(ctx)
+    }
+
+    pub fn close_staker(ctx: Context<CloseStakerInstructionAccounts>) -> Result<()> {
+        instructions::staker::close_staker(ctx)
     }
 
-    pub fn claim(ctx: Context<ClaimInstructionAccounts>) -> Result<()> {
-        instructions::staker::claim(ctx)
+    pub fn migrate_staker(ctx: Context<MigrateStakerInstructionAccounts>) -> Result<()> {
+        instructions::staker::migrate_staker(ctx)
+    }
+
+    pub fn get_claimable(ctx: Context<GetClaimableInstructionAccounts>) -> Result<()> {
+        instructions::staker::get_claimable(ctx)
+    }
+
+    pub fn get_vault_state(ctx: Context<GetVaultStateInstructionAccounts>) -> Result<()> {
+        instructions::staker::get_vault_state(ctx)
+    }
+
+    pub fn quote_lp_yield_from_swap(ctx: Context<QuoteLpYieldFromSwapInstructionAccounts>, amount_in: u64, hypothetical_stake: u64) -> Result<u64> {
+        instructions::staker::quote_lp_yield_from_swap(ctx, amount_in, hypothetical_stake)
     }
 
     // OXE staker instructions
@@ -67,9 +204,25 @@ pub mod oxedium_program {
         instructions::oxe_staker::oxe_claim(ctx)
     }
 
-    // Trader instruction
-    pub fn swap(ctx: Context<SwapInstructionAccounts>, amount_in: u64, minimum_out: u64) -> Result<()> {
-        instructions::trader::swap(ctx, amount_in, minimum_out)
+    // Trader instructions
+    pub fn swap(ctx: Context<SwapInstructionAccounts>, amount_in: u64, minimum_out: u64, allow_partial: bool, referral_fee_bps: u64, max_impact_bps: u64) -> Result<()> {
+        instructions::trader::swap(ctx, amount_in, minimum_out, allow_partial, referral_fee_bps, max_impact_bps)
+    }
+
+    pub fn swap_with_slippage_bps(ctx: Context<SwapInstructionAccounts>, amount_in: u64, max_slippage_bps: u64, allow_partial: bool, referral_fee_bps: u64, max_impact_bps: u64) -> Result<()> {
+        instructions::trader::swap_with_slippage_bps(ctx, amount_in, max_slippage_bps, allow_partial, referral_fee_bps, max_impact_bps)
+    }
+
+    pub fn swap_two_hop(ctx: Context<SwapTwoHopInstructionAccounts>, amount_in: u64, minimum_out: u64) -> Result<()> {
+        instructions::trader::swap_two_hop(ctx, amount_in, minimum_out)
+    }
+
+    pub fn quote_swap(ctx: Context<QuoteSwapInstructionAccounts>, amount_in: u64, allow_partial: bool, max_impact_bps: u64) -> Result<()> {
+        instructions::trader::quote_swap(ctx, amount_in, allow_partial, max_impact_bps)
+    }
+
+    pub fn derive_swap_accounts(ctx: Context<DeriveSwapAccountsInstructionAccounts>, mint_in: Pubkey, mint_out: Pubkey, token_program_in: Pubkey, token_program_out: Pubkey) -> Result<SwapAccounts> {
+        instructions::trader::derive_swap_accounts(ctx, mint_in, mint_out, token_program_in, token_program_out)
     }
 
 }
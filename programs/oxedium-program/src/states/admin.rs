@@ -2,5 +2,11 @@ use anchor_lang::prelude::*;
 
 #[account]
 pub struct Admin {
-    pub pubkey: Pubkey
-}
\ No newline at end of file
+    pub pubkey: Pubkey,
+    /// Proposed next admin; `Pubkey::default()` when no handoff is pending
+    pub pending_admin: Pubkey,
+    /// Only key allowed to `collect` protocol yield; set by the admin via `set_collector`
+    pub collector: Pubkey,
+    /// Emergency stop for swaps, staking, unstaking and claims
+    pub paused: bool,
+}
@@ -4,6 +4,7 @@ pub use staker::*;
 pub use oxe_global::*;
 pub use oxe_staker::*;
 pub use oxe_vault_position::*;
+pub use rent_reserve::*;
 
 pub mod admin;
 pub mod vault;
@@ -11,3 +12,4 @@ pub mod staker;
 pub mod oxe_global;
 pub mod oxe_staker;
 pub mod oxe_vault_position;
+pub mod rent_reserve;
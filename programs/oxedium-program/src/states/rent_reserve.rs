@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// Program-funded pool that pays `Staker` PDA rent for first-time LPs.
+/// The PDA itself holds the lamports; `rent_reserve` tracks how much of that
+/// balance is spendable so the account's own rent-exempt minimum is never touched.
+/// Seeds: [OXEDIUM_SEED, RENT_RESERVE_SEED]
+/// Space: 8 + 8 + 8 + 8 + 8 = 40
+#[account]
+pub struct RentReserve {
+    /// Lamports available to subsidize new Staker PDAs
+    pub rent_reserve: u64,
+    /// Smallest first deposit that qualifies for a subsidy
+    pub min_subsidized_deposit: u64,
+    /// Maximum number of Staker PDAs carrying subsidized rent at once
+    pub max_subsidized_stakers: u64,
+    /// Staker PDAs currently carrying subsidized rent
+    pub subsidized_stakers: u64,
+}
@@ -6,5 +6,21 @@ pub struct Staker {
     pub vault: Pubkey,
     pub staked_amount: u64,
     pub last_cumulative_yield: u128,
-    pub pending_claim: u64
+    /// Deposit-weighted `cumulative_yield_per_lp` at entry; cost basis for reporting only
+    pub avg_entry_cumulative_yield: u128,
+    pub pending_claim: u64,
+    /// Unstaking before this pays the vault's lock-up penalty
+    pub stake_unlock_ts: i64,
+    /// Last staking, unstaking, claim or compound; the vault's `action_cooldown` runs from here
+    pub last_action_ts: i64,
+    /// Rent for this PDA was paid by the RentReserve and is refunded to it on close
+    pub rent_subsidized: bool,
+    /// Account layout version; see STAKER_VERSION
+    pub version: u8,
+    /// Payout queued by `request_unstake`, already net of exit fees; 0 if none
+    pub pending_unstake_amount: u64,
+    /// `finalize_unstake` can pay out `pending_unstake_amount` from this unix timestamp
+    pub unstake_available_ts: i64,
+    /// Lamports the RentReserve paid for this PDA; only this much goes back to it on close
+    pub subsidized_rent: u64,
 }
\ No newline at end of file
@@ -4,16 +4,135 @@ use anchor_lang::prelude::*;
 pub struct Vault {
     pub base_fee_bps: u64,
     pub protocol_fee_bps: u64,
+    /// Scale the protocol fee with the liquidity-impact curve up to `max_protocol_fee_bps`
+    pub protocol_fee_follows_curve: bool,
+    pub max_protocol_fee_bps: u64,
+    /// Protocol fee is waived on swaps out of this vault until this unix timestamp (set by `set_fee_waiver`)
+    pub protocol_fee_waiver_until: i64,
+    /// Cap on the referrer's cut, in bps of the protocol fee (10_000 = all of it)
+    pub max_referral_fee_bps: u64,
     pub max_exit_fee_bps: u64,
+    /// Opt-in lock-up: unstaking before `stake_unlock_ts` pays an extra penalty
+    pub lockup_active: bool,
+    /// Seconds a deposit stays locked
+    pub lockup_duration: i64,
+    /// Lock-up penalty at the start of the lock, decaying linearly to 0 at unlock
+    pub max_lockup_penalty_bps: u64,
+    /// Minimum seconds between a staker's staking, unstaking, claim and compound; 0 disables
+    pub action_cooldown: i64,
+    /// Seconds after a staker's last action before it can claim or compound; 0 disables
+    pub min_stake_age: i64,
+    /// Utilization at which the liquidity-impact fee curve starts (bps, < 10_000)
+    pub impact_threshold_bps: u64,
 
     pub token_mint: Pubkey,
 
     pub pyth_price_account: Pubkey,
+    /// Pyth feed id of `pyth_price_account`, re-checked on every swap; zero skips the check
+    pub feed_id: [u8; 32],
     pub max_age_price: u64,
+    /// Max gap between the two oracles' publish times in a swap (seconds); the stricter vault's bound applies, 0 disables
+    pub max_oracle_skew: i64,
+    /// Price swaps off the oracle EMA instead of the spot price
+    pub use_ema_price: bool,
+    /// Reject oracle updates reporting zero confidence (usually a stale or stub feed)
+    pub require_nonzero_conf: bool,
+    /// Max oracle confidence as bps of price; 0 disables the check
+    pub max_conf_bps: u64,
+    /// Max deviation of the oracle price from the vault TWAP while the TWAP is
+    /// fresher than `TWAP_WINDOW_SECS`; 0 disables the check
+    pub max_twap_deviation_bps: u64,
+    /// Balances below this are treated as empty (swaps out fail with InsufficientLiquidity)
+    pub dust_floor: u64,
+    /// Cap on LP principal (`initial_balance`); 0 means unlimited
+    pub max_total_deposits: u64,
+    /// Smallest first deposit that opens a new Staker position
+    pub min_stake_amount: u64,
+    /// Swaps out of this vault paying less than this revert with OutputTooSmall; 0 disables
+    pub min_swap_output: u64,
 
     pub initial_balance: u64,
     pub current_balance: u64,
+    /// Positions with a nonzero stake
+    pub total_stakers: u64,
     
     pub cumulative_yield_per_lp: u128,
+    /// Fee × SCALE left over from flooring `cumulative_yield_per_lp` increments, carried into the next credit
+    pub yield_remainder: u128,
+    /// LP yield credited but not yet claimed or compounded; swaps can't pay it out
+    pub unclaimed_lp_yield: u64,
     pub oxe_cumulative_yield_per_staker: u128,
-}
\ No newline at end of file
+    /// Sum of all swap LP fees and exit fees credited to LPs
+    pub lifetime_yield_distributed: u64,
+    /// Fees with no LP left to receive them, withdrawable by the admin via `collect`
+    pub protocol_yield: u64,
+
+    pub swap_count: u64,
+    pub last_swap_ts: i64,
+
+    /// Oracle price recorded at the last swap and the time it was recorded
+    pub last_price: u64,
+    pub last_price_ts: i64,
+    /// Sum of `last_price × seconds held`, the running TWAP accumulator
+    pub price_cumulative: u128,
+    /// Accumulator snapshot the on-chain TWAP is measured from
+    pub twap_anchor_cumulative: u128,
+    pub twap_anchor_ts: i64,
+
+    /// Blocks swaps and new deposits; unstaking and claims stay open
+    pub paused: bool,
+    /// Account layout version; see VAULT_VERSION
+    pub version: u8,
+    /// Pyth account `swap` falls back to while `pyth_price_account` is stale or
+    /// future-dated; must carry the same `feed_id`. Default pubkey disables it
+    pub fallback_pyth_price_account: Pubkey,
+    /// Max move of the oracle price from `last_price` within `PRICE_JUMP_WINDOW_SECS`
+    /// of it being recorded, in bps; 0 disables the check
+    pub max_price_jump_bps: u64,
+    /// Seconds `request_unstake` payouts wait before `finalize_unstake`
+    pub withdraw_delay: i64,
+    /// Queued unstake payouts: held in the vault ATA but already taken out of
+    /// `current_balance`, so swaps can't use them
+    pub reserved_for_withdrawals: u64,
+    /// The Pyth feed quotes tokens per USD rather than USD per token, so swaps
+    /// price with its reciprocal
+    pub invert_price: bool,
+    /// Minimum protocol fee per swap out of this vault, in token units; the
+    /// bps fee applies when larger. 0 disables it
+    pub min_protocol_fee: u64,
+    /// Pegged asset priced against other stable-mode vaults: a swap between two
+    /// of them pays the base fee whatever the imbalance
+    pub stable_mode: bool,
+}
+impl Vault {
+    /// `current_balance / initial_balance` in bps. A vault with no LP principal
+    /// counts as fully healthy (10_000). Not capped: inflows push it above 10_000.
+    pub fn health_bps(&self) -> u64 {
+        if self.initial_balance == 0 {
+            return 10_000;
+        }
+
+        (self.current_balance as u128 * 10_000 / self.initial_balance as u128)
+            .min(u64::MAX as u128) as u64
+    }
+
+    /// Share of the LP principal (`initial_balance`) a position of `staked_amount`
+    /// holds, in bps. 0 for a vault with no LP principal.
+    pub fn share_bps(&self, staked_amount: u64) -> u64 {
+        if self.initial_balance == 0 {
+            return 0;
+        }
+
+        (staked_amount as u128 * 10_000 / self.initial_balance as u128).min(10_000) as u64
+    }
+
+    /// Share of `current_balance` an outflow of `out_amount` would take, in bps,
+    /// capped at 10_000. An empty vault is fully utilized.
+    pub fn utilization_bps(&self, out_amount: u64) -> u64 {
+        if self.current_balance == 0 {
+            return 10_000;
+        }
+
+        (out_amount as u128 * 10_000 / self.current_balance as u128).min(10_000) as u64
+    }
+}
@@ -2,9 +2,45 @@ pub const VAULT_SEED: &str = "vault-seed";
 pub const STAKER_SEED: &str = "staker-seed";
 pub const OXEDIUM_SEED: &str = "oxedium-seed";
 pub const ADMIN_SEED: &str = "admin-seed";
+pub const RENT_RESERVE_SEED: &str = "rent-reserve-seed";
 
 pub const OXE_GLOBAL_SEED: &str = "oxe-global-seed";
 pub const OXE_STAKER_SEED: &str = "oxe-staker-seed";
 pub const OXE_POSITION_SEED: &str = "oxe-position-seed";
 
 pub const SCALE: u128 = 1_000_000_000_000;
+
+/// Allowed range for a vault's `max_age_price`, in seconds: tighter than a few
+/// slots rejects most swaps, looser than an hour makes staleness checks moot
+pub const MIN_MAX_AGE_PRICE: u64 = 5;
+pub const MAX_MAX_AGE_PRICE: u64 = 3_600;
+
+/// Once the TWAP anchor is this old it is rolled forward to the current accumulator
+pub const TWAP_WINDOW_SECS: i64 = 300;
+
+/// A vault's `max_price_jump_bps` only applies to swaps this soon after its last
+/// recorded price, so slow drift between swaps is never blocked
+pub const PRICE_JUMP_WINDOW_SECS: i64 = 60;
+
+/// Seconds a drained position must go without any owner action before the
+/// admin can `sweep_dust` it; the owner can still claim or close it until then
+pub const DUST_SWEEP_GRACE_PERIOD: i64 = 90 * 24 * 60 * 60;
+
+/// Largest `dust_threshold` `sweep_dust` accepts, in raw token units, so an
+/// admin can't forfeit a real claim just because it went unattended
+pub const MAX_DUST_THRESHOLD: u64 = 10_000;
+
+/// Current `Vault` and `Staker` account layouts. New fields are appended at the
+/// end; bump only once a layout has been deployed, and teach
+/// `migrate_vault_data` / `migrate_staker_data` to read the deployed one
+pub const VAULT_VERSION: u8 = 1;
+pub const STAKER_VERSION: u8 = 1;
+
+/// Size of the current `Admin` account, discriminator included
+pub const ADMIN_SPACE: usize = 8 + 32 + 32 + 32 + 1;
+
+/// Size of a current-version `Vault` account, discriminator included
+pub const VAULT_SPACE: usize = 8 + 8 + 8 + 1 + 8 + 8 + 8 + 32 + 32 + 8 + 1 + 1 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 16 + 16 + 8 + 8 + 8 + 8 + 16 + 16 + 8 + 1 + 8 + 8 + 8 + 8 + 1 + 8 + 8 + 8 + 32 + 8 + 16 + 8 + 8 + 8 + 8 + 1 + 32 + 8 + 8 + 8 + 1 + 8 + 1;
+
+/// Size of a current-version `Staker` account, discriminator included
+pub const STAKER_SPACE: usize = 8 + 32 + 32 + 8 + 16 + 16 + 8 + 8 + 8 + 1 + 1 + 8 + 8 + 8;
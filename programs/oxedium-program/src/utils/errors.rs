@@ -59,4 +59,133 @@ pub enum OxediumError {
 
     #[msg("Insufficient staked balance")]
     InsufficientBalance,
-}
\ No newline at end of file
+
+    #[msg("Insufficient lamports in the rent reserve")]
+    InsufficientRentReserve,
+
+    #[msg("Staker still holds stake or unclaimed yield")]
+    StakerNotEmpty,
+
+    #[msg("Claim amount exceeds claimable yield")]
+    ClaimExceedsYield,
+
+    #[msg("Program is paused")]
+    ProgramPaused,
+
+    #[msg("Vault is paused")]
+    VaultPaused,
+
+    #[msg("Deposit would exceed the vault's deposit cap")]
+    DepositCapExceeded,
+
+    #[msg("First deposit is below the vault's minimum stake")]
+    BelowMinimumStake,
+
+    #[msg("Oracle reported zero confidence")]
+    SuspiciousOracle,
+
+    #[msg("Oracle confidence interval is too wide relative to price")]
+    OracleTooUncertain,
+
+    #[msg("Impact threshold must be below 10_000 bps")]
+    InvalidImpactThreshold,
+
+    #[msg("Swap output is below the vault's minimum")]
+    OutputTooSmall,
+
+    #[msg("Tokens received differ from the amount the transfer was priced at")]
+    TransferAmountMismatch,
+
+    #[msg("Lock-up duration must not be negative")]
+    InvalidLockup,
+
+    #[msg("Protocol yield exceeds the vault's current balance")]
+    ProtocolYieldExceedsBalance,
+
+    #[msg("Oracle publish times are too far apart")]
+    OracleSkewTooLarge,
+
+    #[msg("Swap amount is too small to produce any output")]
+    OutputRoundsToZero,
+
+    #[msg("Liquidity impact fee exceeds the trader's maximum")]
+    ImpactTooHigh,
+
+    #[msg("Signer is not the fee collector")]
+    InvalidCollector,
+
+    #[msg("Max price age must be between 5 and 3600 seconds")]
+    InvalidMaxAgePrice,
+
+    #[msg("Vault token account holds less than the vault's current balance")]
+    VaultBalanceDeficit,
+
+    #[msg("Swap would leave the vault unable to cover unclaimed yield")]
+    SolvencyViolation,
+
+    #[msg("Unstake share must not exceed 10_000 bps")]
+    InvalidUnstakeBps,
+
+    #[msg("Staker action cooldown has not elapsed")]
+    CooldownActive,
+
+    #[msg("Action cooldown must not be negative")]
+    InvalidCooldown,
+
+    #[msg("Position is too young to claim or compound")]
+    StakeTooYoung,
+
+    #[msg("Minimum stake age must not be negative")]
+    InvalidMinStakeAge,
+
+    #[msg("Staker account is already on the current layout")]
+    StakerUpToDate,
+
+    #[msg("Vault account is already on the current layout")]
+    VaultUpToDate,
+
+    #[msg("Staker still has a stake or more than dust pending")]
+    StakerNotDust,
+
+    #[msg("Staker was active too recently to be swept")]
+    SweepGracePeriodActive,
+
+    #[msg("Oracle price moved too far from the vault's last swap price")]
+    PriceJumpTooLarge,
+
+    #[msg("Staker already has an unstake queued")]
+    WithdrawAlreadyQueued,
+
+    #[msg("Staker has no unstake queued")]
+    NoWithdrawQueued,
+
+    #[msg("Queued unstake is still in its withdraw delay")]
+    WithdrawNotReady,
+
+    #[msg("Withdraw delay must not be negative")]
+    InvalidWithdrawDelay,
+
+    #[msg("Vault still holds stake, queued withdrawals or unpaid yield")]
+    VaultNotEmpty,
+
+    #[msg("Batch amounts do not match the account groups passed")]
+    BatchLengthMismatch,
+
+    #[msg("Unstake amount exceeds the staked amount")]
+    InsufficientStake,
+
+    #[msg("Admin account is already on the current layout")]
+    AdminUpToDate,
+
+    #[msg("Vault has a withdraw delay; unstake with request_unstake")]
+    WithdrawQueueRequired,
+
+    #[msg("Oracle price deviates too far from the vault TWAP")]
+    TwapDeviation,
+
+    #[msg("Dust threshold exceeds MAX_DUST_THRESHOLD")]
+    InvalidDustThreshold,
+
+    #[msg("Admin account is already initialized")]
+    AdminAlreadyInitialized,
+}
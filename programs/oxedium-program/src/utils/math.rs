@@ -0,0 +1,65 @@
+use crate::utils::OxediumError;
+
+pub use u256::U256;
+
+mod u256 {
+    // The macro's generated code trips this lint; keep the allow local to it
+    #![allow(clippy::manual_div_ceil)]
+
+    // 256-bit unsigned integer for intermediates whose product can exceed u128
+    uint::construct_uint! {
+        pub struct U256(4);
+    }
+}
+
+/// `a + b`, or `OverflowInAdd`
+pub fn add(a: u64, b: u64) -> Result<u64, OxediumError> {
+    a.checked_add(b).ok_or(OxediumError::OverflowInAdd)
+}
+
+/// `a - b`, or `OverflowInSub` if `b > a`
+pub fn sub(a: u64, b: u64) -> Result<u64, OxediumError> {
+    a.checked_sub(b).ok_or(OxediumError::OverflowInSub)
+}
+
+/// `a * b`, or `OverflowInMul`
+pub fn mul(a: u64, b: u64) -> Result<u64, OxediumError> {
+    a.checked_mul(b).ok_or(OxediumError::OverflowInMul)
+}
+
+/// `a / b` rounded down, or `OverflowInDiv` if `b` is 0
+pub fn div(a: u64, b: u64) -> Result<u64, OxediumError> {
+    a.checked_div(b).ok_or(OxediumError::OverflowInDiv)
+}
+
+/// `a * b / denom` rounded down, through a u128 intermediate so the product
+/// may exceed u64 as long as the quotient fits.
+/// Returns `OverflowInDiv` if `denom` is 0 and `OverflowInCast` if the
+/// quotient exceeds u64.
+pub fn mul_div(a: u64, b: u64, denom: u64) -> Result<u64, OxediumError> {
+    if denom == 0 {
+        return Err(OxediumError::OverflowInDiv);
+    }
+
+    // u64 × u64 always fits in u128
+    let quotient = a as u128 * b as u128 / denom as u128;
+    u64::try_from(quotient).map_err(|_| OxediumError::OverflowInCast)
+}
+
+/// `a * b / denom` rounded down for u128 operands, through a U256 intermediate
+/// so a fee × `SCALE` product may exceed u128 as long as the quotient fits.
+/// A zero `denom` is a caller error (`OverflowInDiv`); accrual sites with no
+/// one to accrue to route the fee elsewhere before calling this.
+/// Returns `OverflowInMul` if the quotient exceeds u128.
+pub fn mul_div_u128(a: u128, b: u128, denom: u128) -> Result<u128, OxediumError> {
+    if denom == 0 {
+        return Err(OxediumError::OverflowInDiv);
+    }
+
+    let quotient = U256::from(a) * U256::from(b) / U256::from(denom);
+    if quotient > U256::from(u128::MAX) {
+        return Err(OxediumError::OverflowInMul);
+    }
+
+    Ok(quotient.as_u128())
+}
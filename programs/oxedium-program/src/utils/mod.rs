@@ -1,5 +1,7 @@
 pub use constants::*;
 pub use errors::*;
+pub use math::*;
 
 pub mod constants;
-pub mod errors;
\ No newline at end of file
+pub mod errors;
+pub mod math;
\ No newline at end of file
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::{AnchorDeserialize, Pubkey};
+use anchor_lang::{Discriminator, Event};
+use oxedium_program::components::{accept_pending_admin, set_pending_admin};
+use oxedium_program::events::AdminUpdateEvent;
+use oxedium_program::states::Admin;
+use oxedium_program::utils::OxediumError;
+
+fn make_admin(pubkey: Pubkey) -> Admin {
+    Admin {
+        pubkey,
+        pending_admin: Pubkey::default(),
+        collector: Pubkey::default(),
+        paused: false,
+    }
+}
+
+#[test]
+fn proposed_admin_takes_over_on_accept() {
+    let current = Pubkey::new_unique();
+    let next = Pubkey::new_unique();
+    let mut admin = make_admin(current);
+
+    set_pending_admin(&mut admin, next);
+    assert_eq!(admin.pubkey, current);
+
+    accept_pending_admin(&mut admin, &next).unwrap();
+    assert_eq!(admin.pubkey, next);
+    assert_eq!(admin.pending_admin, Pubkey::default());
+}
+
+#[test]
+fn accept_from_wrong_signer_is_rejected() {
+    let current = Pubkey::new_unique();
+    let mut admin = make_admin(current);
+    set_pending_admin(&mut admin, Pubkey::new_unique());
+
+    let result = accept_pending_admin(&mut admin, &Pubkey::new_unique());
+    assert_eq!(result.unwrap_err(), OxediumError::InvalidAdmin.into());
+    assert_eq!(admin.pubkey, current);
+}
+
+#[test]
+fn accept_without_proposal_is_rejected() {
+    let mut admin = make_admin(Pubkey::new_unique());
+
+    let result = accept_pending_admin(&mut admin, &Pubkey::default());
+    assert_eq!(result.unwrap_err(), OxediumError::InvalidAdmin.into());
+}
+
+#[test]
+fn reproposal_overwrites_stale_pending_key() {
+    let mut admin = make_admin(Pubkey::new_unique());
+    let typo = Pubkey::new_unique();
+    let fixed = Pubkey::new_unique();
+
+    set_pending_admin(&mut admin, typo);
+    set_pending_admin(&mut admin, fixed);
+
+    let result = accept_pending_admin(&mut admin, &typo);
+    assert_eq!(result.unwrap_err(), OxediumError::InvalidAdmin.into());
+
+    accept_pending_admin(&mut admin, &fixed).unwrap();
+    assert_eq!(admin.pubkey, fixed);
+}
+
+#[test]
+fn admin_update_event_carries_old_and_new_admin() {
+    let current = Pubkey::new_unique();
+    let next = Pubkey::new_unique();
+    let mut admin = make_admin(current);
+    set_pending_admin(&mut admin, next);
+
+    // Built the way accept_admin emits it
+    let old_admin = accept_pending_admin(&mut admin, &next).unwrap();
+    let event = AdminUpdateEvent { old_admin, new_admin: admin.pubkey, timestamp: 0, slot: 0 };
+
+    let data = event.data();
+    assert_eq!(&data[..8], AdminUpdateEvent::DISCRIMINATOR);
+    let decoded = AdminUpdateEvent::try_from_slice(&data[8..]).unwrap();
+    assert_eq!(decoded.old_admin, current);
+    assert_eq!(decoded.new_admin, next);
+}
@@ -0,0 +1,41 @@
+use oxedium_program::components::blend_entry_basis;
+use oxedium_program::utils::OxediumError;
+
+#[test]
+fn first_deposit_takes_current_accumulator() {
+    assert_eq!(blend_entry_basis(0, 0, 5_000, 1_000).unwrap(), 5_000);
+}
+
+#[test]
+fn unequal_deposits_blend_by_amount() {
+    // 3 000 at 1 000, then 1 000 at 5 000 → (3 000·1 000 + 1 000·5 000) / 4 000
+    let basis = blend_entry_basis(0, 0, 1_000, 3_000).unwrap();
+    let basis = blend_entry_basis(basis, 3_000, 5_000, 1_000).unwrap();
+    assert_eq!(basis, 2_000);
+}
+
+#[test]
+fn blend_rounds_down() {
+    // (1·0 + 2·10) / 3 = 6.67
+    assert_eq!(blend_entry_basis(0, 1, 10, 2).unwrap(), 6);
+}
+
+#[test]
+fn zero_amount_keeps_basis() {
+    assert_eq!(blend_entry_basis(700, 1_000, 9_000, 0).unwrap(), 700);
+    assert_eq!(blend_entry_basis(700, 0, 9_000, 0).unwrap(), 700);
+}
+
+#[test]
+fn large_values_do_not_overflow() {
+    let basis = blend_entry_basis(u64::MAX as u128, u64::MAX, u64::MAX as u128 * 3, u64::MAX).unwrap();
+    assert_eq!(basis, u64::MAX as u128 * 2);
+}
+
+#[test]
+fn accumulator_below_basis_is_rejected() {
+    assert!(matches!(
+        blend_entry_basis(5_000, 1_000, 4_000, 1_000),
+        Err(OxediumError::OverflowInSub)
+    ));
+}
@@ -1,4 +1,4 @@
-use oxedium_program::components::calculate_fee_amount;
+use oxedium_program::components::{calculate_fee_amount, calculate_fee_amount_with_rounding, RoundingMode};
 
 // --- zero cases ---
 
@@ -98,11 +98,58 @@ fn large_amount_large_fees_no_overflow() {
     assert_eq!(protocol_fee, 0);
 }
 
-// --- rounding (FLOOR, not CEIL) ---
+// --- rounding (CEIL, not FLOOR) ---
 
 #[test]
-fn fee_rounds_floor() {
-    // 10_001 * 1 / 10_000 = 1 (integer division, no rounding up to 2)
+fn fee_rounds_ceil() {
+    // ceil(10_001 * 1 / 10_000) = 2 (any remainder rounds the fee up)
     let (_, lp_fee, _) = calculate_fee_amount(10_001, 1, 0).unwrap();
+    assert_eq!(lp_fee, 2);
+}
+
+// --- explicit rounding modes ---
+
+#[test]
+fn fee_rounds_floor() {
+    // floor(10_001 * 1 / 10_000) = 1
+    let (_, lp_fee, _) =
+        calculate_fee_amount_with_rounding(10_001, 1, RoundingMode::Floor, 0, RoundingMode::Floor).unwrap();
     assert_eq!(lp_fee, 1);
 }
+
+#[test]
+fn floor_lets_dust_pay_zero() {
+    // floor(9_999 * 1 / 10_000) = 0, where CeilMin1 would charge 1
+    let (amount_out, lp_fee, protocol_fee) =
+        calculate_fee_amount_with_rounding(9_999, 1, RoundingMode::Floor, 1, RoundingMode::Floor).unwrap();
+    assert_eq!(lp_fee, 0);
+    assert_eq!(protocol_fee, 0);
+    assert_eq!(amount_out, 9_999);
+}
+
+#[test]
+fn protocol_floor_with_lp_ceil() {
+    // Dust swap: LP keeps its min-1 fee, protocol fee floors to 0
+    let (amount_out, lp_fee, protocol_fee) =
+        calculate_fee_amount_with_rounding(1_000, 30, RoundingMode::CeilMin1, 5, RoundingMode::Floor).unwrap();
+    assert_eq!(lp_fee, 3);
+    assert_eq!(protocol_fee, 0);
+    assert_eq!(amount_out, 997);
+
+    let (_, _, ceil_protocol_fee) = calculate_fee_amount(1_000, 30, 5).unwrap();
+    assert_eq!(ceil_protocol_fee, 1);
+}
+
+#[test]
+fn default_matches_ceil_min1_on_both_legs() {
+    assert_eq!(
+        calculate_fee_amount(12_345, 17, 3).unwrap(),
+        calculate_fee_amount_with_rounding(12_345, 17, RoundingMode::CeilMin1, 3, RoundingMode::CeilMin1).unwrap()
+    );
+}
+
+#[test]
+fn floor_and_ceil_agree_on_exact_multiples() {
+    let floor = calculate_fee_amount_with_rounding(10_000, 30, RoundingMode::Floor, 10, RoundingMode::Floor).unwrap();
+    assert_eq!(floor, calculate_fee_amount(10_000, 30, 10).unwrap());
+}
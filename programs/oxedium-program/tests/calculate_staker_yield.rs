@@ -1,5 +1,5 @@
-use oxedium_program::components::calculate_staker_yield;
-use oxedium_program::utils::SCALE;
+use oxedium_program::components::{calculate_staker_yield, try_calculate_staker_yield};
+use oxedium_program::utils::{OxediumError, SCALE};
 
 // --- zero / identity cases ---
 
@@ -78,10 +78,36 @@ fn current_less_than_last_returns_zero() {
 // --- saturation / overflow protection ---
 
 #[test]
-fn overflow_in_mul_returns_err() {
-    // delta = u128::MAX, balance = 2 → multiplication overflows → returns Err
-    let result = calculate_staker_yield(u128::MAX, 2, 0);
-    assert!(result.is_err());
+fn product_above_u128_saturates_instead_of_erroring() {
+    // delta = u128::MAX, balance = 2 → the product needs 129 bits, and the
+    // quotient is far above u64::MAX → clamps to u64::MAX
+    let yield_amount = calculate_staker_yield(u128::MAX, 2, 0).unwrap();
+    assert_eq!(yield_amount, u64::MAX);
+}
+
+#[test]
+fn product_just_above_u128_saturates() {
+    // delta = 2^100, balance = 2^30 → product = 2^130 overflows u128.
+    // Since u128::MAX / SCALE > u64::MAX, any such product saturates.
+    let delta: u128 = 1u128 << 100;
+    let balance: u64 = 1 << 30;
+    assert!(delta.checked_mul(balance as u128).is_none());
+    let yield_amount = calculate_staker_yield(delta, balance, 0).unwrap();
+    assert_eq!(yield_amount, u64::MAX);
+}
+
+#[test]
+fn max_balance_with_exact_u64_max_result() {
+    // delta = SCALE, balance = u64::MAX → yield = u64::MAX exactly, no clamping
+    let yield_amount = calculate_staker_yield(SCALE, u64::MAX, 0).unwrap();
+    assert_eq!(yield_amount, u64::MAX);
+}
+
+#[test]
+fn quotient_one_above_u64_max_saturates() {
+    // delta = 2 * SCALE, balance = u64::MAX / 2 + 1 → yield = u64::MAX + 1 → clamps
+    let yield_amount = calculate_staker_yield(2 * SCALE, u64::MAX / 2 + 1, 0).unwrap();
+    assert_eq!(yield_amount, u64::MAX);
 }
 
 #[test]
@@ -110,3 +136,36 @@ fn result_saturates_at_u64_max() {
     // total = delta * SCALE / SCALE = delta, which fits in u64
     assert_eq!(yield_amount, delta as u64);
 }
+
+// --- checked variant ---
+
+#[test]
+fn checked_matches_saturating_in_range() {
+    let yield_amount = try_calculate_staker_yield(11 * SCALE, 1_000_000, 10 * SCALE).unwrap();
+    assert_eq!(yield_amount, calculate_staker_yield(11 * SCALE, 1_000_000, 10 * SCALE).unwrap());
+    assert_eq!(try_calculate_staker_yield(SCALE, u64::MAX, 0).unwrap(), u64::MAX);
+}
+
+#[test]
+fn checked_errors_when_current_less_than_last() {
+    // the saturating variant returns 0 here
+    assert_eq!(calculate_staker_yield(100 * SCALE, 1_000_000, 500 * SCALE).unwrap(), 0);
+    assert!(matches!(
+        try_calculate_staker_yield(100 * SCALE, 1_000_000, 500 * SCALE),
+        Err(OxediumError::OverflowInSub)
+    ));
+}
+
+#[test]
+fn checked_errors_when_result_exceeds_u64() {
+    // the saturating variant clamps to u64::MAX here
+    assert_eq!(calculate_staker_yield(u128::MAX, 2, 0).unwrap(), u64::MAX);
+    assert!(matches!(
+        try_calculate_staker_yield(u128::MAX, 2, 0),
+        Err(OxediumError::OverflowInMul)
+    ));
+    assert!(matches!(
+        try_calculate_staker_yield(2 * SCALE, u64::MAX / 2 + 1, 0),
+        Err(OxediumError::OverflowInMul)
+    ));
+}
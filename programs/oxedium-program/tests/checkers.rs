@@ -0,0 +1,565 @@
+use anchor_lang::prelude::Pubkey;
+use oxedium_program::components::{check_action_cooldown, check_admin, check_collector, check_conf_ratio, check_deposit_cap, check_distinct_mints, check_feed_id, check_instant_unstake, check_lockup_config, check_max_impact, check_min_stake, check_min_stake_age, check_min_swap_output, check_not_paused, check_oracle_conf, check_oracle_skew, check_price_age, check_staker_closable, check_staker_position, check_solvency, check_unstake_amount, check_vault_config, check_vault_mint, derive_swap_accounts};
+use oxedium_program::states::{Admin, Staker, Vault};
+use oxedium_program::utils::OxediumError;
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+mod common;
+
+// --- check_distinct_mints ---
+
+#[test]
+fn identical_mints_return_same_mint_error() {
+    let mint = Pubkey::new_unique();
+    let result = check_distinct_mints(&mint, &mint);
+    assert_eq!(result.unwrap_err(), OxediumError::SameMint.into());
+}
+
+#[test]
+fn distinct_mints_pass() {
+    assert!(check_distinct_mints(&Pubkey::new_unique(), &Pubkey::new_unique()).is_ok());
+}
+
+#[test]
+fn identical_mints_would_alias_the_vault_pda() {
+    // Why the guard exists: the same mint maps both swap sides onto one vault PDA,
+    // so `current_balance` would be credited and debited on the same account.
+    let signer = Pubkey::new_unique();
+    let mint = Pubkey::new_unique();
+    let accounts = derive_swap_accounts(&signer, &mint, &mint);
+    assert_eq!(accounts.vault_pda_in, accounts.vault_pda_out);
+
+    // Distinct mints always resolve to distinct vault PDAs.
+    let accounts = derive_swap_accounts(&signer, &mint, &Pubkey::new_unique());
+    assert_ne!(accounts.vault_pda_in, accounts.vault_pda_out);
+}
+
+// --- check_vault_config ---
+
+#[test]
+fn vault_config_within_limits_passes() {
+    assert!(check_vault_config(30, 5, 500, 500, 60, 1_000, 0).is_ok());
+    assert!(check_vault_config(1_000, 500, 500, 1_000, 5, 1_000, 0).is_ok());
+}
+
+#[test]
+fn exit_fee_above_ceiling_is_rejected() {
+    let result = check_vault_config(30, 5, 500, 1_001, 60, 1_000, 0);
+    assert_eq!(result.unwrap_err(), OxediumError::FeeExceeds.into());
+}
+
+#[test]
+fn base_and_protocol_fee_ceilings_are_enforced() {
+    assert_eq!(check_vault_config(1_001, 5, 500, 500, 60, 1_000, 0).unwrap_err(), OxediumError::FeeExceeds.into());
+    assert_eq!(check_vault_config(30, 501, 500, 500, 60, 1_000, 0).unwrap_err(), OxediumError::FeeExceeds.into());
+}
+
+#[test]
+fn zero_max_age_price_is_rejected() {
+    let result = check_vault_config(30, 5, 500, 500, 0, 1_000, 0);
+    assert_eq!(result.unwrap_err(), OxediumError::InvalidMaxAgePrice.into());
+}
+
+#[test]
+fn max_age_price_below_minimum_is_rejected() {
+    // 1 s would fail most swaps on mainnet
+    assert_eq!(check_vault_config(30, 5, 500, 500, 4, 1_000, 0).unwrap_err(), OxediumError::InvalidMaxAgePrice.into());
+    assert!(check_vault_config(30, 5, 500, 500, 5, 1_000, 0).is_ok());
+}
+
+#[test]
+fn max_age_price_above_maximum_is_rejected() {
+    // u64::MAX would switch staleness protection off
+    assert_eq!(check_vault_config(30, 5, 500, 500, 3_601, 1_000, 0).unwrap_err(), OxediumError::InvalidMaxAgePrice.into());
+    assert_eq!(check_vault_config(30, 5, 500, 500, u64::MAX, 1_000, 0).unwrap_err(), OxediumError::InvalidMaxAgePrice.into());
+    assert!(check_vault_config(30, 5, 500, 500, 3_600, 1_000, 0).is_ok());
+}
+
+#[test]
+fn in_range_max_age_price_passes() {
+    assert!(check_vault_config(30, 5, 500, 500, 60, 1_000, 0).is_ok());
+}
+
+// --- check_lockup_config ---
+
+#[test]
+fn lockup_config_within_limits_passes() {
+    assert!(check_lockup_config(0, 0).is_ok());
+    assert!(check_lockup_config(7 * 86_400, 1_000).is_ok());
+}
+
+#[test]
+fn negative_lockup_duration_is_rejected() {
+    assert_eq!(check_lockup_config(-1, 0).unwrap_err(), OxediumError::InvalidLockup.into());
+}
+
+#[test]
+fn lockup_penalty_above_ceiling_is_rejected() {
+    assert_eq!(check_lockup_config(86_400, 1_001).unwrap_err(), OxediumError::FeeExceeds.into());
+}
+
+// --- check_oracle_skew ---
+
+#[test]
+fn oracle_skew_within_bound_passes() {
+    assert!(check_oracle_skew(NOW, NOW, 5).is_ok());
+    assert!(check_oracle_skew(NOW - 5, NOW, 5).is_ok());
+    assert!(check_oracle_skew(NOW, NOW - 5, 5).is_ok());
+}
+
+#[test]
+fn oracle_skew_above_bound_is_rejected() {
+    assert_eq!(check_oracle_skew(NOW - 6, NOW, 5).unwrap_err(), OxediumError::OracleSkewTooLarge.into());
+    assert_eq!(check_oracle_skew(NOW, NOW - 6, 5).unwrap_err(), OxediumError::OracleSkewTooLarge.into());
+}
+
+#[test]
+fn zero_oracle_skew_disables_the_check() {
+    assert!(check_oracle_skew(0, NOW, 0).is_ok());
+    assert!(check_oracle_skew(0, NOW, -1).is_ok());
+}
+
+// --- check_feed_id ---
+
+const SOL_FEED: [u8; 32] = [0xef; 32];
+const USDC_FEED: [u8; 32] = [0xea; 32];
+
+#[test]
+fn matching_feed_id_passes() {
+    assert!(check_feed_id(&SOL_FEED, &SOL_FEED).is_ok());
+}
+
+#[test]
+fn mismatched_feed_id_is_rejected() {
+    // SOL vault wired to the USDC feed
+    assert_eq!(check_feed_id(&USDC_FEED, &SOL_FEED).unwrap_err(), OxediumError::InvalidPythAccount.into());
+}
+
+#[test]
+fn zero_expected_feed_id_skips_the_check() {
+    assert!(check_feed_id(&USDC_FEED, &[0; 32]).is_ok());
+}
+
+// --- check_price_age ---
+
+const NOW: i64 = 1_700_000_000;
+
+#[test]
+fn fresh_price_passes() {
+    assert!(check_price_age(NOW, 60, NOW).is_ok());
+    assert!(check_price_age(NOW - 60, 60, NOW).is_ok());
+}
+
+#[test]
+fn stale_price_is_rejected() {
+    let result = check_price_age(NOW - 61, 60, NOW);
+    assert_eq!(result.unwrap_err(), OxediumError::OracleDataTooOld.into());
+}
+
+#[test]
+fn future_dated_price_is_rejected() {
+    let result = check_price_age(NOW + 1, 60, NOW);
+    assert_eq!(result.unwrap_err(), OxediumError::OracleDataTooOld.into());
+}
+
+// --- check_staker_closable ---
+
+fn empty_staker(last_cumulative_yield: u128) -> Staker {
+    Staker {
+        owner: Pubkey::new_unique(),
+        vault: Pubkey::new_unique(),
+        staked_amount: 0,
+        last_cumulative_yield,
+        avg_entry_cumulative_yield: 0,
+        pending_claim: 0,
+        stake_unlock_ts: 0,
+        last_action_ts: 0,
+        rent_subsidized: false,
+        version: 1,
+        pending_unstake_amount: 0,
+        unstake_available_ts: 0,
+        subsidized_rent: 0,
+    }
+}
+
+#[test]
+fn empty_staker_is_closable_even_after_later_yield() {
+    // Yield accruing after a full unstake isn't owed to a zero-balance staker
+    assert!(check_staker_closable(&empty_staker(5), 1_000).is_ok());
+}
+
+#[test]
+fn staker_with_stake_or_pending_claim_is_not_closable() {
+    let mut staked = empty_staker(0);
+    staked.staked_amount = 1;
+    assert_eq!(check_staker_closable(&staked, 0).unwrap_err(), OxediumError::StakerNotEmpty.into());
+
+    let mut pending = empty_staker(0);
+    pending.pending_claim = 1;
+    assert_eq!(check_staker_closable(&pending, 0).unwrap_err(), OxediumError::StakerNotEmpty.into());
+}
+
+// --- check_staker_position ---
+
+#[test]
+fn own_position_in_the_vault_passes() {
+    let staker = empty_staker(0);
+    assert!(check_staker_position(&staker, &staker.owner, &staker.vault).is_ok());
+}
+
+#[test]
+fn another_owners_position_returns_invalid_staker() {
+    let staker = empty_staker(0);
+    let err = check_staker_position(&staker, &Pubkey::new_unique(), &staker.vault).unwrap_err();
+    assert_eq!(err, OxediumError::InvalidStaker.into());
+    assert_ne!(err, OxediumError::InvalidAdmin.into());
+}
+
+#[test]
+fn position_in_another_vault_returns_invalid_vault() {
+    let staker = empty_staker(0);
+    let err = check_staker_position(&staker, &staker.owner, &Pubkey::new_unique()).unwrap_err();
+    assert_eq!(err, OxediumError::InvalidVault.into());
+    assert_ne!(err, OxediumError::InvalidAdmin.into());
+}
+
+// --- check_not_paused ---
+
+#[test]
+fn paused_program_rejects_then_resumes_after_unpause() {
+    let mut admin = Admin {
+        pubkey: Pubkey::new_unique(),
+        pending_admin: Pubkey::default(),
+        collector: Pubkey::default(),
+        paused: false,
+    };
+    assert!(check_not_paused(&admin).is_ok());
+
+    // set_pause(true)
+    admin.paused = true;
+    assert_eq!(check_not_paused(&admin).unwrap_err(), OxediumError::ProgramPaused.into());
+
+    // set_pause(false)
+    admin.paused = false;
+    assert!(check_not_paused(&admin).is_ok());
+}
+
+// --- check_deposit_cap ---
+
+#[test]
+fn deposit_within_cap_passes() {
+    // initial_balance 600 + deposit 300 against a cap of 1_000
+    assert!(check_deposit_cap(900, 1_000).is_ok());
+}
+
+#[test]
+fn deposit_exactly_hitting_cap_passes() {
+    assert!(check_deposit_cap(1_000, 1_000).is_ok());
+}
+
+#[test]
+fn deposit_overshooting_cap_is_rejected() {
+    assert_eq!(check_deposit_cap(1_001, 1_000).unwrap_err(), OxediumError::DepositCapExceeded.into());
+}
+
+#[test]
+fn zero_cap_means_unlimited() {
+    assert!(check_deposit_cap(u64::MAX, 0).is_ok());
+}
+
+// --- check_min_stake ---
+
+#[test]
+fn fresh_deposit_below_minimum_is_rejected() {
+    assert_eq!(check_min_stake(0, 999, 1_000).unwrap_err(), OxediumError::BelowMinimumStake.into());
+}
+
+#[test]
+fn fresh_deposit_at_minimum_passes() {
+    assert!(check_min_stake(0, 1_000, 1_000).is_ok());
+}
+
+#[test]
+fn small_top_up_on_existing_position_passes() {
+    assert!(check_min_stake(1_000, 1, 1_000).is_ok());
+}
+
+// --- check_unstake_amount ---
+
+#[test]
+fn over_unstake_returns_insufficient_stake() {
+    let err = check_unstake_amount(1_000, 1_001).unwrap_err();
+    assert_eq!(err, OxediumError::InsufficientStake.into());
+    assert_ne!(err, OxediumError::Overflow.into());
+}
+
+#[test]
+fn direct_unstake_with_a_withdraw_delay_returns_withdraw_queue_required() {
+    assert_eq!(check_instant_unstake(3_600).unwrap_err(), OxediumError::WithdrawQueueRequired.into());
+    assert_eq!(check_instant_unstake(1).unwrap_err(), OxediumError::WithdrawQueueRequired.into());
+}
+
+#[test]
+fn direct_unstake_without_a_withdraw_delay_passes() {
+    assert!(check_instant_unstake(0).is_ok());
+}
+
+#[test]
+fn unstaking_the_whole_position_passes() {
+    assert!(check_unstake_amount(1_000, 1_000).is_ok());
+}
+
+// --- check_oracle_conf ---
+
+fn make_price_feed(price: i64, conf: u64, exponent: i32) -> PriceFeedMessage {
+    PriceFeedMessage {
+        feed_id: [0u8; 32],
+        price,
+        conf,
+        exponent,
+        publish_time: 1_700_000_000,
+        prev_publish_time: 1_699_999_999,
+        ema_price: price,
+        ema_conf: conf,
+    }
+}
+
+#[test]
+fn zero_conf_oracle_rejected_when_required() {
+    let oracle = make_price_feed(10_000_000_000, 0, -8);
+    assert_eq!(
+        check_oracle_conf(oracle.conf, true).unwrap_err(),
+        OxediumError::SuspiciousOracle.into()
+    );
+}
+
+#[test]
+fn zero_conf_oracle_allowed_when_not_required() {
+    let oracle = make_price_feed(10_000_000_000, 0, -8);
+    assert!(check_oracle_conf(oracle.conf, false).is_ok());
+}
+
+#[test]
+fn nonzero_conf_oracle_passes_when_required() {
+    let oracle = make_price_feed(10_000_000_000, 5_000_000, -8);
+    assert!(check_oracle_conf(oracle.conf, true).is_ok());
+}
+
+// --- check_conf_ratio ---
+
+#[test]
+fn borderline_conf_ratio_passes() {
+    // conf = 2 % of price, bound = 200 bps
+    let oracle = make_price_feed(10_000_000_000, 200_000_000, -8);
+    assert!(check_conf_ratio(oracle.price, oracle.conf, 200).is_ok());
+}
+
+#[test]
+fn over_threshold_conf_ratio_is_rejected() {
+    let oracle = make_price_feed(10_000_000_000, 200_000_001, -8);
+    assert_eq!(
+        check_conf_ratio(oracle.price, oracle.conf, 200).unwrap_err(),
+        OxediumError::OracleTooUncertain.into()
+    );
+}
+
+#[test]
+fn conf_above_price_is_rejected_not_capped() {
+    let oracle = make_price_feed(1, 1_000_000, -8);
+    assert_eq!(
+        check_conf_ratio(oracle.price, oracle.conf, 10_000).unwrap_err(),
+        OxediumError::OracleTooUncertain.into()
+    );
+}
+
+#[test]
+fn zero_max_conf_bps_disables_check() {
+    assert!(check_conf_ratio(1, 1_000_000, 0).is_ok());
+}
+
+#[test]
+fn impact_threshold_at_or_above_full_utilization_is_rejected() {
+    assert!(check_vault_config(30, 5, 500, 500, 60, 9_999, 0).is_ok());
+    assert_eq!(check_vault_config(30, 5, 500, 500, 60, 10_000, 0).unwrap_err(), OxediumError::InvalidImpactThreshold.into());
+}
+
+#[test]
+fn max_protocol_fee_must_sit_between_protocol_fee_and_ceiling() {
+    assert!(check_vault_config(30, 5, 5, 500, 60, 1_000, 0).is_ok());
+    assert_eq!(check_vault_config(30, 5, 501, 500, 60, 1_000, 0).unwrap_err(), OxediumError::FeeExceeds.into());
+    assert_eq!(check_vault_config(30, 5, 4, 500, 60, 1_000, 0).unwrap_err(), OxediumError::FeeExceeds.into());
+}
+
+#[test]
+fn referral_cap_above_whole_protocol_fee_is_rejected() {
+    assert!(check_vault_config(30, 5, 500, 500, 60, 1_000, 10_000).is_ok());
+    assert_eq!(check_vault_config(30, 5, 500, 500, 60, 1_000, 10_001).unwrap_err(), OxediumError::FeeExceeds.into());
+}
+
+// --- check_min_swap_output ---
+
+#[test]
+fn output_at_vault_minimum_passes() {
+    assert!(check_min_swap_output(1_000, 1_000).is_ok());
+}
+
+#[test]
+fn output_below_vault_minimum_is_rejected() {
+    assert_eq!(check_min_swap_output(999, 1_000).unwrap_err(), OxediumError::OutputTooSmall.into());
+}
+
+#[test]
+fn zero_min_swap_output_allows_dust() {
+    assert!(check_min_swap_output(1, 0).is_ok());
+}
+
+// --- check_max_impact ---
+
+#[test]
+fn impact_within_bound_passes() {
+    // 230 bps liquidity fee on a 30 bps base → 200 bps of curve impact
+    assert!(check_max_impact(230, 30, 200).is_ok());
+    assert!(check_max_impact(30, 30, 0).is_ok());
+}
+
+#[test]
+fn impact_above_bound_returns_impact_too_high() {
+    assert_eq!(check_max_impact(231, 30, 200).unwrap_err(), OxediumError::ImpactTooHigh.into());
+    assert_eq!(check_max_impact(31, 30, 0).unwrap_err(), OxediumError::ImpactTooHigh.into());
+}
+
+// --- check_admin / check_collector ---
+
+fn split_roles() -> (Admin, Pubkey, Pubkey) {
+    let owner = Pubkey::new_unique();
+    let collector = Pubkey::new_unique();
+    let admin = Admin {
+        pubkey: owner,
+        pending_admin: Pubkey::default(),
+        collector,
+        paused: false,
+    };
+    (admin, owner, collector)
+}
+
+#[test]
+fn collector_can_collect_but_not_update_vaults() {
+    let (admin, _, collector) = split_roles();
+    // collect
+    assert!(check_collector(&admin, &collector).is_ok());
+    // init_vault / update_vault
+    assert_eq!(check_admin(&admin, &collector).unwrap_err(), OxediumError::InvalidAdmin.into());
+}
+
+#[test]
+fn owner_can_update_vaults_but_not_collect() {
+    let (admin, owner, _) = split_roles();
+    assert!(check_admin(&admin, &owner).is_ok());
+    assert_eq!(check_collector(&admin, &owner).unwrap_err(), OxediumError::InvalidCollector.into());
+}
+
+#[test]
+fn outsider_holds_neither_role() {
+    let (admin, _, _) = split_roles();
+    let outsider = Pubkey::new_unique();
+    assert_eq!(check_admin(&admin, &outsider).unwrap_err(), OxediumError::InvalidAdmin.into());
+    assert_eq!(check_collector(&admin, &outsider).unwrap_err(), OxediumError::InvalidCollector.into());
+}
+
+// --- check_vault_mint ---
+
+fn make_vault(token_mint: Pubkey) -> Vault {
+    Vault {
+        base_fee_bps: 30,
+        protocol_fee_bps: 10,
+        token_mint,
+        ..common::vault()
+    }
+}
+
+#[test]
+fn vault_of_the_passed_mint_passes() {
+    let mint = Pubkey::new_unique();
+    let vault = make_vault(mint);
+    assert!(check_vault_mint(&vault, &mint).is_ok());
+}
+
+#[test]
+fn vault_of_another_mint_returns_invalid_vault() {
+    // e.g. a reinitialized account whose stored mint no longer matches its seeds
+    let vault = make_vault(Pubkey::new_unique());
+    assert_eq!(check_vault_mint(&vault, &Pubkey::new_unique()).unwrap_err(), OxediumError::InvalidVault.into());
+}
+
+#[test]
+fn swap_pair_with_a_crafted_vault_on_either_side_returns_invalid_vault() {
+    // Mirrors the prologue of swap.rs: each vault must store the mint it was passed with
+    let (mint_in, mint_out) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let check_pair = |vault_in: &Vault, vault_out: &Vault| -> anchor_lang::Result<()> {
+        check_vault_mint(vault_in, &mint_in)?;
+        check_vault_mint(vault_out, &mint_out)
+    };
+
+    assert!(check_pair(&make_vault(mint_in), &make_vault(mint_out)).is_ok());
+    // An account at the mint's PDA whose stored token_mint says otherwise
+    assert_eq!(check_pair(&make_vault(Pubkey::new_unique()), &make_vault(mint_out)).unwrap_err(), OxediumError::InvalidVault.into());
+    assert_eq!(check_pair(&make_vault(mint_in), &make_vault(Pubkey::new_unique())).unwrap_err(), OxediumError::InvalidVault.into());
+    // Vaults passed the wrong way round
+    assert_eq!(check_pair(&make_vault(mint_out), &make_vault(mint_in)).unwrap_err(), OxediumError::InvalidVault.into());
+}
+
+// --- check_solvency ---
+
+#[test]
+fn balance_covering_yield_owed_passes() {
+    let mut vault = make_vault(Pubkey::new_unique());
+    vault.current_balance = 1_000;
+    vault.protocol_yield = 400;
+    vault.unclaimed_lp_yield = 600;
+    assert!(check_solvency(&vault).is_ok());
+}
+
+#[test]
+fn balance_below_yield_owed_returns_solvency_violation() {
+    let mut vault = make_vault(Pubkey::new_unique());
+    vault.current_balance = 999;
+    vault.protocol_yield = 400;
+    vault.unclaimed_lp_yield = 600;
+    assert_eq!(check_solvency(&vault).unwrap_err(), OxediumError::SolvencyViolation.into());
+}
+
+// --- check_action_cooldown ---
+
+#[test]
+fn zero_cooldown_allows_back_to_back_actions() {
+    assert!(check_action_cooldown(1_000, 0, 1_000).is_ok());
+}
+
+#[test]
+fn action_inside_cooldown_returns_cooldown_active() {
+    assert_eq!(check_action_cooldown(1_000, 30, 1_029).unwrap_err(), OxediumError::CooldownActive.into());
+}
+
+#[test]
+fn action_once_cooldown_elapses_passes() {
+    assert!(check_action_cooldown(1_000, 30, 1_030).is_ok());
+    // A position that never acted starts at 0
+    assert!(check_action_cooldown(0, 30, 1_700_000_000).is_ok());
+}
+
+// --- check_min_stake_age ---
+
+#[test]
+fn zero_min_stake_age_allows_immediate_claim() {
+    assert!(check_min_stake_age(1_000, 0, 1_000).is_ok());
+}
+
+#[test]
+fn claim_before_min_stake_age_returns_stake_too_young() {
+    assert_eq!(check_min_stake_age(1_000, 300, 1_299).unwrap_err(), OxediumError::StakeTooYoung.into());
+}
+
+#[test]
+fn claim_once_position_has_aged_passes() {
+    assert!(check_min_stake_age(1_000, 300, 1_300).is_ok());
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::Pubkey;
+use oxedium_program::states::Vault;
+use oxedium_program::utils::VAULT_VERSION;
+
+/// A freshly initialized vault: every optional check and cap off, no balances,
+/// the full exit fee ceiling and the 10% impact threshold. Tests override what
+/// they exercise with struct update syntax: `Vault { base_fee_bps: 30, ..vault() }`
+pub fn vault() -> Vault {
+    Vault {
+        base_fee_bps: 0,
+        protocol_fee_bps: 0,
+        protocol_fee_follows_curve: false,
+        max_protocol_fee_bps: 0,
+        protocol_fee_waiver_until: 0,
+        max_referral_fee_bps: 0,
+        max_exit_fee_bps: 10_000,
+        lockup_active: false,
+        lockup_duration: 0,
+        max_lockup_penalty_bps: 0,
+        action_cooldown: 0,
+        min_stake_age: 0,
+        impact_threshold_bps: 1_000,
+        token_mint: Pubkey::default(),
+        pyth_price_account: Pubkey::default(),
+        feed_id: [0; 32],
+        max_age_price: 0,
+        max_oracle_skew: 0,
+        use_ema_price: false,
+        require_nonzero_conf: false,
+        max_conf_bps: 0,
+        max_twap_deviation_bps: 0,
+        dust_floor: 0,
+        max_total_deposits: 0,
+        min_stake_amount: 0,
+        min_swap_output: 0,
+        initial_balance: 0,
+        current_balance: 0,
+        total_stakers: 0,
+        cumulative_yield_per_lp: 0,
+        yield_remainder: 0,
+        unclaimed_lp_yield: 0,
+        oxe_cumulative_yield_per_staker: 0,
+        lifetime_yield_distributed: 0,
+        protocol_yield: 0,
+        swap_count: 0,
+        last_swap_ts: 0,
+        last_price: 0,
+        last_price_ts: 0,
+        price_cumulative: 0,
+        twap_anchor_cumulative: 0,
+        twap_anchor_ts: 0,
+        paused: false,
+        version: VAULT_VERSION,
+        fallback_pyth_price_account: Pubkey::default(),
+        max_price_jump_bps: 0,
+        withdraw_delay: 0,
+        reserved_for_withdrawals: 0,
+        invert_price: false,
+        min_protocol_fee: 0,
+        stable_mode: false,
+    }
+}
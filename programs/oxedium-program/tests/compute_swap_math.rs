@@ -1,8 +1,10 @@
-use anchor_lang::prelude::Pubkey;
 use oxedium_program::components::compute_swap_math;
 use oxedium_program::states::Vault;
+use oxedium_program::utils::OxediumError;
 use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
 
+mod common;
+
 fn make_price_feed(price: i64, conf: u64, exponent: i32) -> PriceFeedMessage {
     PriceFeedMessage {
         feed_id: [0u8; 32],
@@ -25,14 +27,9 @@ fn make_vault(
     Vault {
         base_fee_bps,
         protocol_fee_bps,
-        max_exit_fee_bps: 10_000,
-        token_mint: Pubkey::default(),
-        pyth_price_account: Pubkey::default(),
-        max_age_price: 0,
         initial_balance,
         current_balance,
-        cumulative_yield_per_lp: 0,
-        oxe_cumulative_yield_per_staker: 0,
+        ..common::vault()
     }
 }
 
@@ -135,11 +132,11 @@ fn oracle_confidence_reduces_raw_out() {
     let vault_out = make_vault(30, 10, 1_000_000, 1_000_000);
 
     let with_conf = compute_swap_math(
-        100, oracle_with_conf.clone(), oracle_with_conf, 6, 6, &vault_in, &vault_out,
+        100, oracle_with_conf, oracle_with_conf, 6, 6, &vault_in, &vault_out,
     ).unwrap();
 
     let no_conf = compute_swap_math(
-        100, oracle_no_conf.clone(), oracle_no_conf, 6, 6, &vault_in, &vault_out,
+        100, oracle_no_conf, oracle_no_conf, 6, 6, &vault_in, &vault_out,
     ).unwrap();
 
     // Fee rate is the same; conf only shrinks the raw amount, not the fee bps
@@ -170,7 +167,7 @@ fn high_utilization_triggers_liquidity_fee_curve() {
 
     let result = compute_swap_math(
         500_000,
-        oracle.clone(),
+        oracle,
         oracle,
         6,
         6,
@@ -197,7 +194,7 @@ fn insufficient_liquidity_returns_error() {
 
     let result = compute_swap_math(
         1_000_000,
-        oracle.clone(),
+        oracle,
         oracle,
         6,
         6,
@@ -208,23 +205,111 @@ fn insufficient_liquidity_returns_error() {
 }
 
 #[test]
-fn fee_exceeds_100_percent_returns_error() {
-    // vault_out with zero balance → liquidity_fee = 10_000 (MAX)
-    // protocol_fee_bps = 10 → total = 10_010 > 10_000 → FeeExceeds
+fn fee_at_exactly_100_percent_is_not_clamped() {
+    // 99-unit swap drains a 99-unit vault → liquidity_fee = 10_000 (MAX);
+    // with no protocol fee the total is exactly 100%, which is allowed as is
     let oracle = make_price_feed(1, 0, 0);
     let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
-    let vault_out = make_vault(30, 10, 1_000_000, 0); // current_balance = 0
+    let vault_out = make_vault(30, 0, 1_000_000, 99);
 
-    let result = compute_swap_math(
-        1_000,
-        oracle.clone(),
-        oracle,
-        6,
-        6,
-        &vault_in,
-        &vault_out,
+    let result = compute_swap_math(99, oracle, oracle, 6, 6, &vault_in, &vault_out).unwrap();
+    assert_eq!(result.swap_fee_bps, 10_000);
+    assert_eq!(result.net_amount_out, 0);
+    assert_eq!(result.lp_fee_amount, 99);
+}
+
+#[test]
+fn fee_over_100_percent_is_clamped_to_leave_room_for_protocol_fee() {
+    // Same swap with protocol_fee_bps = 10 → 10_010 > 10_000 → the liquidity
+    // fee is clamped to 9_990 instead of reverting
+    let oracle = make_price_feed(1, 0, 0);
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let vault_out = make_vault(30, 10, 1_000_000, 99);
+
+    let result = compute_swap_math(99, oracle, oracle, 6, 6, &vault_in, &vault_out).unwrap();
+    assert_eq!(result.swap_fee_bps, 9_990);
+    assert_eq!(result.swap_fee_bps + 10, 10_000);
+    assert_eq!(result.net_amount_out + result.lp_fee_amount + result.protocol_fee_amount, result.raw_amount_out);
+}
+
+#[test]
+fn clamped_fee_breakdown_still_sums_to_the_charged_fee() {
+    // Protocol fee riding the curve reaches 500 bps at full utilization,
+    // so the liquidity fee is clamped to 9_500, all of it out of the curves
+    let oracle = make_price_feed(1, 0, 0);
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let mut vault_out = make_vault(30, 10, 1_000_000, 99);
+    vault_out.protocol_fee_follows_curve = true;
+    vault_out.max_protocol_fee_bps = 500;
+
+    let result = compute_swap_math(99, oracle, oracle, 6, 6, &vault_in, &vault_out).unwrap();
+    assert_eq!(result.swap_fee_bps, 9_500);
+    assert_eq!(result.vault_base_fee_bps, 30);
+    assert_eq!(
+        result.vault_base_fee_bps + result.imbalance_fee_bps + result.liquidity_impact_bps + result.oracle_conf_bps,
+        result.swap_fee_bps
     );
-    assert!(result.is_err());
+}
+
+#[test]
+fn protocol_fee_floor_dominates_a_dust_swap() {
+    // Same swap as balanced_vaults_small_swap: 10 bps of 1_000 is 1 unit,
+    // so a 50-unit floor applies instead and comes out of the trader's output
+    let oracle_in = make_price_feed(SOL_PRICE, 0, SOL_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let mut vault_out = make_vault(30, 10, 1_000_000, 1_000_000);
+    vault_out.min_protocol_fee = 50;
+
+    let result = compute_swap_math(10_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &vault_out).unwrap();
+    assert_eq!(result.raw_amount_out, 1_000);
+    assert_eq!(result.lp_fee_amount, 3);
+    assert_eq!(result.protocol_fee_amount, 50);
+    assert_eq!(result.net_amount_out, 947);
+    assert_eq!(result.price_impact_bps, 530);
+}
+
+#[test]
+fn protocol_fee_bps_dominates_a_large_swap() {
+    // 100 SOL → 10_000 USDC: 10 bps is 10_000_000 units, far above the floor
+    let oracle_in = make_price_feed(SOL_PRICE, 0, SOL_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let vault_in = make_vault(30, 0, 1_000_000_000_000, 1_000_000_000_000);
+    let vault_out = make_vault(30, 10, 1_000_000_000_000, 1_000_000_000_000);
+    let mut floored_out = vault_out.clone();
+    floored_out.min_protocol_fee = 50;
+
+    let flat = compute_swap_math(100_000_000_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &vault_out).unwrap();
+    let floored = compute_swap_math(100_000_000_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &floored_out).unwrap();
+    assert_eq!(floored.protocol_fee_amount, 10_000_000);
+    assert_eq!(floored.protocol_fee_amount, flat.protocol_fee_amount);
+    assert_eq!(floored.net_amount_out, flat.net_amount_out);
+}
+
+#[test]
+fn protocol_fee_floor_never_exceeds_what_the_lp_fee_leaves() {
+    // A floor above raw_out takes the whole output after the LP fee, no more
+    let oracle_in = make_price_feed(SOL_PRICE, 0, SOL_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let mut vault_out = make_vault(30, 10, 1_000_000, 1_000_000);
+    vault_out.min_protocol_fee = 10_000;
+
+    let result = compute_swap_math(10_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &vault_out).unwrap();
+    assert_eq!(result.lp_fee_amount, 3);
+    assert_eq!(result.protocol_fee_amount, 997);
+    assert_eq!(result.net_amount_out, 0);
+}
+
+#[test]
+fn empty_output_vault_still_returns_error() {
+    // vault_out with zero balance → the output can't be paid at all
+    let oracle = make_price_feed(1, 0, 0);
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let vault_out = make_vault(30, 10, 1_000_000, 0); // current_balance = 0
+
+    let result = compute_swap_math(1_000, oracle, oracle, 6, 6, &vault_in, &vault_out);
+    assert!(matches!(result, Err(OxediumError::InsufficientLiquidity)));
 }
 
 #[test]
@@ -245,3 +330,209 @@ fn negative_price_oracle_returns_error() {
     );
     assert!(result.is_err());
 }
+
+// --- dust floor ---
+
+#[test]
+fn dust_balance_below_floor_returns_insufficient_liquidity() {
+    // vault_out holds 99 units with a dust floor of 100 → treated as empty,
+    // so the 99-unit swap fails cleanly instead of being priced at MAX_FEE.
+    let oracle = make_price_feed(1, 0, 0);
+    let vault_in = make_vault(30, 10, 1_000_000, 1_000_000);
+    let mut vault_out = make_vault(30, 10, 1_000_000, 99);
+    vault_out.dust_floor = 100;
+
+    let result = compute_swap_math(99, oracle, oracle, 6, 6, &vault_in, &vault_out);
+    assert!(matches!(result, Err(OxediumError::InsufficientLiquidity)));
+}
+
+#[test]
+fn dust_balance_without_floor_hits_max_fee() {
+    // Same dust vault with the floor disabled: utilization = 100% → MAX_FEE,
+    // clamped so that together with the protocol fee it stays at 100%.
+    let oracle = make_price_feed(1, 0, 0);
+    let vault_in = make_vault(30, 10, 1_000_000, 1_000_000);
+    let vault_out = make_vault(30, 10, 1_000_000, 99);
+
+    let result = compute_swap_math(99, oracle, oracle, 6, 6, &vault_in, &vault_out).unwrap();
+    assert_eq!(result.swap_fee_bps, 9_990);
+    assert_eq!(result.net_amount_out, 0);
+}
+
+#[test]
+fn balance_at_dust_floor_still_swaps() {
+    // current_balance == dust_floor is not below the floor → normal pricing.
+    // utilization = 10 / 1_000 = 1% → base fee path
+    let oracle = make_price_feed(1, 0, 0);
+    let vault_in = make_vault(30, 0, 1_000, 1_000);
+    let mut vault_out = make_vault(30, 0, 1_000, 1_000);
+    vault_out.dust_floor = 1_000;
+
+    let result = compute_swap_math(10, oracle, oracle, 6, 6, &vault_in, &vault_out).unwrap();
+    assert_eq!(result.raw_amount_out, 10);
+    assert_eq!(result.swap_fee_bps, 30);
+}
+
+// --- EMA pricing ---
+
+#[test]
+fn vault_ema_flag_selects_input_ema_price() {
+    // Spot SOL at $120, EMA at $100; only vault_in opts into EMA pricing
+    let oracle_in = PriceFeedMessage {
+        ema_price: SOL_PRICE,
+        ..make_price_feed(12_000_000_000, 0, SOL_EXP)
+    };
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let mut vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let vault_out = make_vault(30, 10, 1_000_000, 1_000_000);
+
+    let spot = compute_swap_math(10_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &vault_out).unwrap();
+    vault_in.use_ema_price = true;
+    let ema = compute_swap_math(10_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &vault_out).unwrap();
+
+    assert_eq!(spot.raw_amount_out, 1_200);
+    assert_eq!(ema.raw_amount_out, 1_000);
+}
+
+// --- configurable impact threshold ---
+
+#[test]
+fn lower_impact_threshold_charges_more_at_same_utilization() {
+    // raw_out = 100_000 micro-USDC against 1_000_000 → 10% utilization
+    let oracle_in = make_price_feed(SOL_PRICE, 0, SOL_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let mut vault_out = make_vault(30, 0, 1_000_000, 1_000_000);
+
+    // default 10% threshold: still flat base fee
+    let default = compute_swap_math(1_000_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &vault_out).unwrap();
+    assert_eq!(default.swap_fee_bps, 30);
+
+    // 5% threshold: adj = 500 * 10_000 / 9_500 = 526, curved = 27
+    // fee = 30 + 9_970 * 27 / 10_000 = 56
+    vault_out.impact_threshold_bps = 500;
+    let tight = compute_swap_math(1_000_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &vault_out).unwrap();
+    assert_eq!(tight.swap_fee_bps, 56);
+    assert!(tight.net_amount_out < default.net_amount_out);
+}
+
+// --- protocol fee following the impact curve ---
+
+#[test]
+fn curved_protocol_fee_matches_flat_at_threshold() {
+    // 1_000_000 lamports → raw_out 100_000 → 10% utilization (= threshold)
+    let oracle_in = make_price_feed(SOL_PRICE, 0, SOL_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let flat = make_vault(30, 10, 1_000_000, 1_000_000);
+    let mut curved = flat.clone();
+    curved.protocol_fee_follows_curve = true;
+    curved.max_protocol_fee_bps = 500;
+
+    let flat_result = compute_swap_math(1_000_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &flat).unwrap();
+    let curved_result = compute_swap_math(1_000_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &curved).unwrap();
+
+    // 100_000 * 10 / 10_000 = 100 either way
+    assert_eq!(flat_result.protocol_fee_amount, 100);
+    assert_eq!(curved_result.protocol_fee_amount, 100);
+}
+
+#[test]
+fn curved_protocol_fee_grows_at_high_utilization() {
+    // 7_000_000 lamports → raw_out 700_000 → 70% utilization
+    // adj = 6_000 * 10_000 / 9_000 = 6_666, curved = 4_443
+    // liquidity fee = 30 + 9_970 * 4_443 / 10_000 = 4_459 bps
+    // curved protocol fee = 10 + 490 * 4_443 / 10_000 = 227 bps
+    let oracle_in = make_price_feed(SOL_PRICE, 0, SOL_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let flat = make_vault(30, 10, 1_000_000, 1_000_000);
+    let mut curved = flat.clone();
+    curved.protocol_fee_follows_curve = true;
+    curved.max_protocol_fee_bps = 500;
+
+    let flat_result = compute_swap_math(7_000_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &flat).unwrap();
+    let curved_result = compute_swap_math(7_000_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &curved).unwrap();
+
+    assert_eq!(flat_result.swap_fee_bps, 4_459);
+    assert_eq!(curved_result.swap_fee_bps, 4_459);
+    assert_eq!(flat_result.protocol_fee_amount, 700);
+    assert_eq!(curved_result.protocol_fee_amount, 15_890);
+    assert_eq!(flat_result.lp_fee_amount, curved_result.lp_fee_amount);
+}
+
+// --- effective price / price impact ---
+
+#[test]
+fn balanced_small_swap_has_no_price_impact() {
+    // Zero fees, 0.1% utilization → net_out == raw_out
+    let oracle = make_price_feed(1, 0, 0);
+    let vault_in = make_vault(0, 0, 1_000_000, 1_000_000);
+    let vault_out = make_vault(0, 0, 1_000_000, 1_000_000);
+
+    let result = compute_swap_math(1_000, oracle, oracle, 6, 6, &vault_in, &vault_out).unwrap();
+
+    assert_eq!(result.price_impact_bps, 0);
+    // 1:1 price → effective price is exactly SCALE
+    assert_eq!(result.effective_price_fp, 1_000_000_000_000);
+}
+
+#[test]
+fn small_swap_price_impact_is_just_the_fee_floor() {
+    // Same setup as balanced_vaults_small_swap: 996 out of 1_000 raw → 40 bps
+    let oracle_in = make_price_feed(SOL_PRICE, 0, SOL_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let vault_out = make_vault(30, 10, 1_000_000, 1_000_000);
+
+    let result = compute_swap_math(10_000, oracle_in, oracle_out, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &vault_out).unwrap();
+
+    assert_eq!(result.price_impact_bps, 40);
+    // 996 micro-USDC per 10_000 lamports
+    assert_eq!(result.effective_price_fp, 996 * 1_000_000_000_000 / 10_000);
+}
+
+#[test]
+fn price_impact_grows_along_liquidity_curve() {
+    let oracle = make_price_feed(1, 0, 0);
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let vault_out = make_vault(30, 0, 1_000_000, 1_000_000);
+
+    let impact_at = |amount_in: u64| {
+        compute_swap_math(amount_in, oracle, oracle, 6, 6, &vault_in, &vault_out)
+            .unwrap()
+            .price_impact_bps
+    };
+
+    let small = impact_at(10_000); // 1% utilization
+    let medium = impact_at(200_000); // 20%
+    let large = impact_at(500_000); // 50%
+
+    assert_eq!(small, 30);
+    assert!(medium > small);
+    assert!(large > medium);
+    // 50% utilization: liquidity fee ≈ 1_998 bps
+    assert!((1_990..=2_000).contains(&large));
+}
+
+// --- inverted feeds ---
+
+#[test]
+fn vault_with_inverted_feed_prices_like_the_usd_feed() {
+    let usdc = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let vault_in = make_vault(30, 0, 1_000_000_000_000, 1_000_000_000_000);
+    let vault_out = make_vault(30, 0, 1_000_000_000_000, 1_000_000_000_000);
+    let direct = compute_swap_math(
+        1_000_000_000, make_price_feed(SOL_PRICE, 0, SOL_EXP), usdc, SOL_DECIMALS, USDC_DECIMALS, &vault_in, &vault_out,
+    ).unwrap();
+
+    // The same SOL feed quoted as 0.01 SOL per USD
+    let mut inverted_in = vault_in.clone();
+    inverted_in.invert_price = true;
+    let inverted = compute_swap_math(
+        1_000_000_000, make_price_feed(1_000_000, 0, SOL_EXP), usdc, SOL_DECIMALS, USDC_DECIMALS, &inverted_in, &vault_out,
+    ).unwrap();
+
+    assert_eq!(inverted.raw_amount_out, direct.raw_amount_out);
+    assert_eq!(inverted.net_amount_out, direct.net_amount_out);
+}
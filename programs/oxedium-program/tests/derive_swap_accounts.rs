@@ -0,0 +1,61 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_spl::associated_token::get_associated_token_address;
+use oxedium_program::components::derive_swap_accounts;
+use oxedium_program::utils::{ADMIN_SEED, OXEDIUM_SEED, OXE_GLOBAL_SEED, VAULT_SEED};
+
+fn vault_pda(mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[VAULT_SEED.as_bytes(), mint.as_ref()], &oxedium_program::ID).0
+}
+
+#[test]
+fn derived_accounts_match_manual_derivation() {
+    let signer = Pubkey::new_unique();
+    let mint_in = Pubkey::new_unique();
+    let mint_out = Pubkey::new_unique();
+
+    let accounts = derive_swap_accounts(&signer, &mint_in, &mint_out);
+
+    let vault_in = vault_pda(&mint_in);
+    let vault_out = vault_pda(&mint_out);
+    let (oxe_global, _) = Pubkey::find_program_address(
+        &[OXEDIUM_SEED.as_bytes(), OXE_GLOBAL_SEED.as_bytes()],
+        &oxedium_program::ID,
+    );
+    let (admin, _) = Pubkey::find_program_address(
+        &[OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()],
+        &oxedium_program::ID,
+    );
+
+    assert_eq!(accounts.vault_pda_in, vault_in);
+    assert_eq!(accounts.vault_pda_out, vault_out);
+    assert_eq!(accounts.vault_ata_in, get_associated_token_address(&vault_in, &mint_in));
+    assert_eq!(accounts.vault_ata_out, get_associated_token_address(&vault_out, &mint_out));
+    assert_eq!(accounts.signer_ata_in, get_associated_token_address(&signer, &mint_in));
+    assert_eq!(accounts.signer_ata_out, get_associated_token_address(&signer, &mint_out));
+    assert_eq!(accounts.oxe_global_pda, oxe_global);
+    assert_eq!(accounts.admin_pda, admin);
+}
+
+#[test]
+fn reversed_direction_swaps_vault_sides() {
+    let signer = Pubkey::new_unique();
+    let mint_a = Pubkey::new_unique();
+    let mint_b = Pubkey::new_unique();
+
+    let forward = derive_swap_accounts(&signer, &mint_a, &mint_b);
+    let reverse = derive_swap_accounts(&signer, &mint_b, &mint_a);
+
+    assert_eq!(forward.vault_pda_in, reverse.vault_pda_out);
+    assert_eq!(forward.vault_ata_out, reverse.vault_ata_in);
+    assert_eq!(forward.signer_ata_in, reverse.signer_ata_out);
+    assert_eq!(forward.oxe_global_pda, reverse.oxe_global_pda);
+}
+
+#[test]
+fn distinct_mints_resolve_to_distinct_vaults() {
+    let signer = Pubkey::new_unique();
+    let accounts = derive_swap_accounts(&signer, &Pubkey::new_unique(), &Pubkey::new_unique());
+
+    assert_ne!(accounts.vault_pda_in, accounts.vault_pda_out);
+    assert_ne!(accounts.vault_ata_in, accounts.vault_ata_out);
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::{AnchorDeserialize, Clock, Pubkey};
+use anchor_lang::Event;
+// anchor_lang doesn't re-export the off-chain syscall stubs; the ATA crate does
+use anchor_spl::associated_token::spl_associated_token_account::solana_program::program_stubs::{
+    set_syscall_stubs, SyscallStubs,
+};
+use oxedium_program::events::{event_clock, ClaimEvent};
+
+const TEST_CLOCK: Clock = Clock {
+    slot: 312_456_789,
+    epoch_start_timestamp: 1_699_990_000,
+    epoch: 720,
+    leader_schedule_epoch: 721,
+    unix_timestamp: 1_700_000_000,
+};
+
+/// Serves `TEST_CLOCK` from the Clock sysvar syscall off-chain.
+struct TestClock;
+
+impl SyscallStubs for TestClock {
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        unsafe { *(var_addr as *mut Clock) = TEST_CLOCK };
+        0
+    }
+}
+
+#[test]
+fn events_are_stamped_with_the_test_clock() {
+    set_syscall_stubs(Box::new(TestClock));
+
+    let (timestamp, slot) = event_clock().unwrap();
+    assert_eq!(timestamp, TEST_CLOCK.unix_timestamp);
+    assert_eq!(slot, TEST_CLOCK.slot);
+
+    // Built the way claim() emits it
+    let event = ClaimEvent {
+        user: Pubkey::new_unique(),
+        mint: Pubkey::new_unique(),
+        amount: 1_000,
+        remaining_pending: 0,
+        timestamp,
+        slot,
+    };
+    let decoded = ClaimEvent::try_from_slice(&event.data()[8..]).unwrap();
+    assert_eq!(decoded.timestamp, 1_700_000_000);
+    assert_eq!(decoded.slot, 312_456_789);
+}
@@ -0,0 +1,58 @@
+use oxedium_program::components::exit_fee_bps;
+
+// Documented examples: max exit fee 5%
+
+#[test]
+fn full_health_charges_nothing() {
+    assert_eq!(exit_fee_bps(10_000, 500), 0);
+}
+
+#[test]
+fn eighty_percent_health_charges_twenty_bps() {
+    assert_eq!(exit_fee_bps(8_000, 500), 20);
+}
+
+#[test]
+fn half_health_charges_125_bps() {
+    assert_eq!(exit_fee_bps(5_000, 500), 125);
+}
+
+#[test]
+fn twenty_percent_health_charges_320_bps() {
+    assert_eq!(exit_fee_bps(2_000, 500), 320);
+}
+
+#[test]
+fn zero_health_charges_the_maximum() {
+    assert_eq!(exit_fee_bps(0, 500), 500);
+}
+
+#[test]
+fn fractional_health_moves_the_fee() {
+    // 50.9% no longer charges the same as 50%
+    // deficit 4_910 → curved 2_410 → 500 × 2_410 / 10_000 = 120
+    assert_eq!(exit_fee_bps(5_090, 500), 120);
+    assert!(exit_fee_bps(5_090, 500) < exit_fee_bps(5_000, 500));
+}
+
+#[test]
+fn small_drawdowns_charge_a_small_fee() {
+    // 99.4%: deficit 60 → curved 0 (60² / 10_000)
+    assert_eq!(exit_fee_bps(9_940, 10_000), 0);
+    // 96.5%: deficit 350 → curved 12 → 12 bps at a 100% max
+    assert_eq!(exit_fee_bps(9_650, 10_000), 12);
+    // 91%: deficit 900 → curved 81
+    assert_eq!(exit_fee_bps(9_100, 10_000), 81);
+}
+
+#[test]
+fn fee_never_decreases_as_health_falls() {
+    let fees: Vec<u64> = (0..=10_000).rev().step_by(7).map(|h| exit_fee_bps(h, 500)).collect();
+    assert!(fees.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[test]
+fn health_above_full_charges_nothing() {
+    // More in the vault than LP principal, e.g. after fees accrue
+    assert_eq!(exit_fee_bps(12_500, 500), 0);
+}
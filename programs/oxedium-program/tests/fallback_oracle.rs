@@ -0,0 +1,60 @@
+use oxedium_program::components::choose_oracle;
+use oxedium_program::utils::OxediumError;
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+const NOW: i64 = 1_700_000_000;
+const MAX_AGE: u64 = 60;
+
+fn feed(price: i64, publish_time: i64) -> PriceFeedMessage {
+    PriceFeedMessage {
+        feed_id: [7u8; 32],
+        price,
+        conf: 0,
+        exponent: -8,
+        publish_time,
+        prev_publish_time: publish_time - 1,
+        ema_price: price,
+        ema_conf: 0,
+    }
+}
+
+#[test]
+fn fresh_primary_is_used_even_with_a_fallback() {
+    let (oracle, fallback) = choose_oracle(feed(100, NOW - 10), Some(feed(200, NOW)), MAX_AGE, NOW).unwrap();
+    assert_eq!(oracle.price, 100);
+    assert!(!fallback);
+
+    let (oracle, fallback) = choose_oracle(feed(100, NOW - 10), None, MAX_AGE, NOW).unwrap();
+    assert_eq!(oracle.price, 100);
+    assert!(!fallback);
+}
+
+#[test]
+fn stale_primary_falls_back_to_a_fresh_fallback() {
+    let (oracle, fallback) = choose_oracle(feed(100, NOW - 61), Some(feed(200, NOW - 5)), MAX_AGE, NOW).unwrap();
+    assert_eq!(oracle.price, 200);
+    assert!(fallback);
+}
+
+#[test]
+fn future_dated_primary_falls_back() {
+    let (oracle, fallback) = choose_oracle(feed(100, NOW + 1), Some(feed(200, NOW)), MAX_AGE, NOW).unwrap();
+    assert_eq!(oracle.price, 200);
+    assert!(fallback);
+}
+
+#[test]
+fn stale_primary_without_a_fresh_fallback_reverts() {
+    assert_eq!(
+        choose_oracle(feed(100, NOW - 61), Some(feed(200, NOW - 61)), MAX_AGE, NOW).unwrap_err(),
+        OxediumError::OracleDataTooOld.into()
+    );
+    assert_eq!(
+        choose_oracle(feed(100, NOW - 61), Some(feed(200, NOW + 1)), MAX_AGE, NOW).unwrap_err(),
+        OxediumError::OracleDataTooOld.into()
+    );
+    assert_eq!(
+        choose_oracle(feed(100, NOW - 61), None, MAX_AGE, NOW).unwrap_err(),
+        OxediumError::OracleDataTooOld.into()
+    );
+}
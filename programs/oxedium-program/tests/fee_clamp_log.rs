@@ -0,0 +1,74 @@
+use std::process::Command;
+
+use oxedium_program::components::{compute_swap_math, log_fee_clamp, max_fillable_amount_in};
+use oxedium_program::states::Vault;
+use oxedium_program::utils::OxediumError;
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+mod common;
+
+/// Set when this binary is re-run as the log-emitting child.
+const CHILD_ENV: &str = "OXEDIUM_FEE_CLAMP_CHILD";
+
+fn make_vault(protocol_fee_bps: u64, current_balance: u64) -> Vault {
+    Vault {
+        base_fee_bps: 30,
+        protocol_fee_bps,
+        initial_balance: 1_000_000,
+        current_balance,
+        ..common::vault()
+    }
+}
+
+/// Off-chain, `msg!` prints to stdout, which the test harness captures per
+/// test. So the check re-runs this binary for `emit_fee_clamp_log` only,
+/// with capture off, and reads the child's stdout.
+#[test]
+fn fee_clamp_logs_the_fee_breakdown() {
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args(["--exact", "emit_fee_clamp_log", "--nocapture", "--test-threads=1"])
+        .env(CHILD_ENV, "1")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // The drained vault's health already pushes the base fee near the cap. The
+    // bisection in max_fillable_amount_in prices clamped swaps too, but only
+    // the handler's single log_fee_clamp call may print
+    let line = "Swap fee clamped to 100% {base_fee_bps: 9998, clamped_liquidity_fee_bps: 9990, protocol_fee_bps: 10, raw_amount_out: 99}";
+    assert_eq!(stdout.matches("Swap fee clamped").count(), 1, "expected one diagnostic log in {stdout}");
+    assert!(stdout.contains(line), "missing diagnostic log in {stdout}");
+}
+
+#[test]
+fn emit_fee_clamp_log() {
+    if std::env::var_os(CHILD_ENV).is_none() {
+        return;
+    }
+
+    let oracle = PriceFeedMessage {
+        feed_id: [0u8; 32],
+        price: 1,
+        conf: 0,
+        exponent: 0,
+        publish_time: 1_700_000_000,
+        prev_publish_time: 1_699_999_999,
+        ema_price: 1,
+        ema_conf: 0,
+    };
+    let vault_in = make_vault(0, 1_000_000);
+    // Near-empty output vault: the 99-unit swap drains it → 100% utilization,
+    // MAX_FEE liquidity fee plus the 10 bps protocol fee
+    let vault_out = make_vault(10, 99);
+
+    // Bisects over clamped swaps, none of which fit a partial fill
+    assert!(matches!(
+        max_fillable_amount_in(99, oracle, oracle, 6, 6, &vault_in, &vault_out),
+        Err(OxediumError::InsufficientLiquidity)
+    ));
+    let result = compute_swap_math(99, oracle, oracle, 6, 6, &vault_in, &vault_out).unwrap();
+    assert!(result.fee_clamped);
+    assert_eq!(result.swap_fee_bps, 9_990);
+    log_fee_clamp(&result);
+}
@@ -1,19 +1,15 @@
-use anchor_lang::prelude::Pubkey;
 use oxedium_program::components::fees_setting;
 use oxedium_program::states::Vault;
 
+mod common;
+
 fn make_vault(base_fee_bps: u64, initial_balance: u64, current_balance: u64) -> Vault {
     Vault {
         base_fee_bps,
         protocol_fee_bps: 10,
-        max_exit_fee_bps: 10_000,
-        token_mint: Pubkey::default(),
-        pyth_price_account: Pubkey::default(),
-        max_age_price: 0,
         initial_balance,
         current_balance,
-        cumulative_yield_per_lp: 0,
-        oxe_cumulative_yield_per_staker: 0,
+        ..common::vault()
     }
 }
 
@@ -116,3 +112,65 @@ fn custom_base_fee_used_as_floor() {
     let vault_out = make_vault(100, 100, 50);
     assert_eq!(fees_setting(&vault_in, &vault_out), 2_575);
 }
+
+#[test]
+fn hundredfold_vault_in_with_slightly_drained_vault_out() {
+    // vault_in: current = 100 × initial → delta_in = +990_000 bps
+    // vault_out: initial=100_000, current=95_000 → delta_out = -500 bps
+    // Only the branch depends on delta_in; the fee comes from vault_out alone
+    //
+    // deviation_bps = 500, curved = 500 * 500 / 10_000 = 25
+    // fee = 30 + 9_970 * 25 / 10_000 = 30 + 24 = 54
+    let vault_in = make_vault(30, 100_000, 10_000_000);
+    let vault_out = make_vault(30, 100_000, 95_000);
+    assert_eq!(fees_setting(&vault_in, &vault_out), 54);
+
+    // Same fee as a merely 1.5× input vault against the same output vault
+    let mild_in = make_vault(30, 100_000, 150_000);
+    assert_eq!(fees_setting(&mild_in, &vault_out), 54);
+}
+
+#[test]
+fn extreme_balances_do_not_overflow() {
+    // (u64::MAX - 1) * 10_000 ≈ 1.8e23, far below i128::MAX ≈ 1.7e38
+    let vault_in = make_vault(30, 1, u64::MAX);
+    let vault_out = make_vault(30, u64::MAX, u64::MAX / 2);
+    // delta_out = -5_000 bps → same fee as the 50% drained case
+    assert_eq!(fees_setting(&vault_in, &vault_out), 2_522);
+
+    // Drained from u64::MAX to 0
+    let vault_out = make_vault(30, u64::MAX, 0);
+    assert_eq!(fees_setting(&vault_in, &vault_out), 10_000);
+
+    // Both sides at the limit and balanced → base fee
+    let full = make_vault(30, u64::MAX, u64::MAX);
+    assert_eq!(fees_setting(&full, &full), 30);
+}
+
+// --- stable mode ---
+
+fn make_stable_vault(base_fee_bps: u64, initial_balance: u64, current_balance: u64) -> Vault {
+    Vault { stable_mode: true, ..make_vault(base_fee_bps, initial_balance, current_balance) }
+}
+
+#[test]
+fn stable_pair_at_heavy_imbalance_returns_base_fee() {
+    // Same 50% shortfall that costs 2_522 bps on a volatile pair
+    let vault_in = make_stable_vault(30, 100, 150);
+    let vault_out = make_stable_vault(30, 100, 50);
+    assert_eq!(fees_setting(&vault_in, &vault_out), 30);
+
+    // Even a drained output vault stays at the base fee
+    let drained = make_stable_vault(30, 100, 0);
+    assert_eq!(fees_setting(&vault_in, &drained), 30);
+}
+
+#[test]
+fn stable_mode_on_one_side_keeps_the_curve() {
+    let stable_in = make_stable_vault(30, 100, 150);
+    let stable_out = make_stable_vault(30, 100, 50);
+
+    assert_eq!(fees_setting(&make_vault(30, 100, 150), &make_vault(30, 100, 50)), 2_522);
+    assert_eq!(fees_setting(&stable_in, &make_vault(30, 100, 50)), 2_522);
+    assert_eq!(fees_setting(&make_vault(30, 100, 150), &stable_out), 2_522);
+}
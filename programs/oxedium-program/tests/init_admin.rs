@@ -0,0 +1,72 @@
+use anchor_lang::prelude::Pubkey;
+use oxedium_program::components::{check_upgrade_authority, initialize_admin};
+use oxedium_program::states::Admin;
+use oxedium_program::utils::{OxediumError, ADMIN_SEED, OXEDIUM_SEED};
+
+/// A freshly created Admin account, as `init` hands it to the instruction
+fn new_admin_account() -> Admin {
+    Admin {
+        pubkey: Pubkey::default(),
+        pending_admin: Pubkey::default(),
+        collector: Pubkey::default(),
+        paused: false,
+    }
+}
+
+#[test]
+fn admin_pda_is_a_single_signer_independent_address() {
+    // The admin PDA is derived from constant seeds only, so there is a single
+    // admin account; a second init_admin from any signer targets the same
+    // address and fails on `init`.
+    let (admin_pda, _) = Pubkey::find_program_address(
+        &[OXEDIUM_SEED.as_bytes(), ADMIN_SEED.as_bytes()],
+        &oxedium_program::ID,
+    );
+    assert!(!admin_pda.is_on_curve());
+}
+
+#[test]
+fn signer_is_stored_as_admin_and_collector() {
+    let signer = Pubkey::new_unique();
+    let mut admin = new_admin_account();
+
+    initialize_admin(&mut admin, signer).unwrap();
+
+    assert_eq!(admin.pubkey, signer);
+    assert_eq!(admin.collector, signer);
+    assert_eq!(admin.pending_admin, Pubkey::default());
+    assert!(!admin.paused);
+}
+
+#[test]
+fn second_init_admin_fails() {
+    let first = Pubkey::new_unique();
+    let mut admin = new_admin_account();
+    initialize_admin(&mut admin, first).unwrap();
+
+    assert_eq!(
+        initialize_admin(&mut admin, Pubkey::new_unique()).unwrap_err(),
+        OxediumError::AdminAlreadyInitialized.into()
+    );
+    assert_eq!((admin.pubkey, admin.collector), (first, first));
+}
+
+#[test]
+fn only_the_upgrade_authority_can_init_admin() {
+    let deployer = Pubkey::new_unique();
+
+    assert!(check_upgrade_authority(Some(deployer), &deployer).is_ok());
+    // A front-runner racing the deployer is rejected
+    assert_eq!(
+        check_upgrade_authority(Some(deployer), &Pubkey::new_unique()).unwrap_err(),
+        OxediumError::InvalidAdmin.into()
+    );
+}
+
+#[test]
+fn immutable_program_cannot_init_admin() {
+    assert_eq!(
+        check_upgrade_authority(None, &Pubkey::new_unique()).unwrap_err(),
+        OxediumError::InvalidAdmin.into()
+    );
+}
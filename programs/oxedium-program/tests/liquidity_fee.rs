@@ -0,0 +1,44 @@
+use oxedium_program::components::{impact_curve_bps, liquidity_fee};
+
+// Documented examples: base fee 30 bps, threshold 10%
+
+#[test]
+fn ten_percent_utilization_charges_base_fee_only() {
+    assert_eq!(liquidity_fee(30, 1_000, 1_000), 30);
+}
+
+#[test]
+fn fifty_percent_utilization_charges_about_twenty_percent() {
+    let fee = liquidity_fee(30, 5_000, 1_000);
+    assert!((1_990..=2_000).contains(&fee), "fee = {fee}");
+}
+
+#[test]
+fn full_utilization_charges_everything() {
+    assert_eq!(liquidity_fee(30, 10_000, 1_000), 10_000);
+}
+
+#[test]
+fn curve_starts_just_above_threshold() {
+    // At the threshold the curve is flat; one step above it starts to rise
+    assert_eq!(impact_curve_bps(1_000, 1_000), 0);
+    assert_eq!(liquidity_fee(30, 1_000, 1_000), 30);
+    assert!(impact_curve_bps(1_100, 1_000) > 0);
+    assert!(liquidity_fee(30, 1_200, 1_000) > 30);
+}
+
+#[test]
+fn fee_rises_with_utilization() {
+    let fees: Vec<u64> = [1_000, 2_000, 5_000, 7_000, 10_000]
+        .iter()
+        .map(|&u| liquidity_fee(30, u, 1_000))
+        .collect();
+    assert!(fees.windows(2).all(|w| w[0] < w[1]), "fees = {fees:?}");
+}
+
+#[test]
+fn threshold_moves_where_the_curve_starts() {
+    // With a 30% threshold, 20% utilization is still on the flat part
+    assert_eq!(liquidity_fee(30, 2_000, 3_000), 30);
+    assert!(liquidity_fee(30, 2_000, 1_000) > 30);
+}
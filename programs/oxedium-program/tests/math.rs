@@ -0,0 +1,79 @@
+use oxedium_program::utils::{math, OxediumError, SCALE};
+
+// --- mul_div ---
+
+#[test]
+fn mul_div_matches_plain_arithmetic_when_nothing_overflows() {
+    assert_eq!(math::mul_div(1_000, 30, 10_000).unwrap(), 3);
+    assert_eq!(math::mul_div(7, 3, 2).unwrap(), 10);
+}
+
+#[test]
+fn mul_div_survives_a_product_above_u64() {
+    // u64::MAX × 10_000 overflows u64, but dividing back by 10_000 fits
+    assert!(u64::MAX.checked_mul(10_000).is_none());
+    assert_eq!(math::mul_div(u64::MAX, 10_000, 10_000).unwrap(), u64::MAX);
+    assert_eq!(math::mul_div(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+    assert_eq!(math::mul_div(1 << 40, 1 << 40, 1 << 30).unwrap(), 1 << 50);
+}
+
+#[test]
+fn mul_div_rounds_down() {
+    assert_eq!(math::mul_div(999, 1, 1_000).unwrap(), 0);
+    assert_eq!(math::mul_div(1_999, 1, 1_000).unwrap(), 1);
+}
+
+#[test]
+fn mul_div_quotient_above_u64_is_a_cast_error() {
+    assert!(matches!(math::mul_div(u64::MAX, 2, 1), Err(OxediumError::OverflowInCast)));
+}
+
+#[test]
+fn mul_div_by_zero_is_a_div_error() {
+    assert!(matches!(math::mul_div(1, 1, 0), Err(OxediumError::OverflowInDiv)));
+}
+
+// --- add / sub / mul / div ---
+
+#[test]
+fn each_helper_reports_its_own_overflow() {
+    assert_eq!(math::add(1, 2).unwrap(), 3);
+    assert!(matches!(math::add(u64::MAX, 1), Err(OxediumError::OverflowInAdd)));
+
+    assert_eq!(math::sub(3, 2).unwrap(), 1);
+    assert!(matches!(math::sub(2, 3), Err(OxediumError::OverflowInSub)));
+
+    assert_eq!(math::mul(3, 4).unwrap(), 12);
+    assert!(matches!(math::mul(u64::MAX, 2), Err(OxediumError::OverflowInMul)));
+
+    assert_eq!(math::div(7, 2).unwrap(), 3);
+    assert!(matches!(math::div(7, 0), Err(OxediumError::OverflowInDiv)));
+}
+
+// --- mul_div_u128 ---
+
+#[test]
+fn mul_div_u128_matches_the_accrual_formula() {
+    // 3 units of fee over 1_000 units of principal
+    assert_eq!(math::mul_div_u128(3, SCALE, 1_000).unwrap(), 3 * SCALE / 1_000);
+}
+
+#[test]
+fn mul_div_u128_survives_a_fee_times_scale_product_above_u128() {
+    // fee × SCALE overflows u128 when multiplied first in u128, but the
+    // per-unit increment fits once divided by a large enough principal
+    let fee = u128::MAX / 1_000;
+    assert!(fee.checked_mul(SCALE).is_none());
+    assert_eq!(math::mul_div_u128(fee, SCALE, SCALE).unwrap(), fee);
+    assert_eq!(math::mul_div_u128(u128::MAX, SCALE, SCALE * 2).unwrap(), u128::MAX / 2);
+}
+
+#[test]
+fn mul_div_u128_quotient_above_u128_is_a_mul_error() {
+    assert!(matches!(math::mul_div_u128(u128::MAX, SCALE, 1), Err(OxediumError::OverflowInMul)));
+}
+
+#[test]
+fn mul_div_u128_zero_denominator_is_a_div_error() {
+    assert!(matches!(math::mul_div_u128(1, SCALE, 0), Err(OxediumError::OverflowInDiv)));
+}
@@ -0,0 +1,104 @@
+use oxedium_program::components::{compute_swap_math, max_fillable_amount_in};
+use oxedium_program::states::Vault;
+use oxedium_program::utils::OxediumError;
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+mod common;
+
+fn make_price_feed(price: i64, conf: u64, exponent: i32) -> PriceFeedMessage {
+    PriceFeedMessage {
+        feed_id: [0u8; 32],
+        price,
+        conf,
+        exponent,
+        publish_time: 1_700_000_000,
+        prev_publish_time: 1_699_999_999,
+        ema_price: price,
+        ema_conf: conf,
+    }
+}
+
+fn make_vault(protocol_fee_bps: u64, current_balance: u64) -> Vault {
+    Vault {
+        base_fee_bps: 30,
+        protocol_fee_bps,
+        max_exit_fee_bps: 0,
+        initial_balance: current_balance,
+        current_balance,
+        ..common::vault()
+    }
+}
+
+// SOL $100 (9 decimals) → USDC $1 (6 decimals)
+fn sol() -> PriceFeedMessage {
+    make_price_feed(10_000_000_000, 0, -8)
+}
+
+fn usdc() -> PriceFeedMessage {
+    make_price_feed(100_000_000, 0, -8)
+}
+
+#[test]
+fn full_amount_is_returned_when_it_fits() {
+    let vault_in = make_vault(0, 1_000_000_000);
+    let vault_out = make_vault(5, 1_000_000_000);
+
+    let fill = max_fillable_amount_in(1_000_000, sol(), usdc(), 9, 6, &vault_in, &vault_out).unwrap();
+    assert_eq!(fill, 1_000_000);
+}
+
+#[test]
+fn oversized_swap_fills_largest_amount_that_fits() {
+    // vault_out holds 1_000 USDC; 20 SOL would need 2_000 USDC
+    let vault_in = make_vault(0, 1_000_000_000);
+    let vault_out = make_vault(0, 1_000_000_000);
+    let requested = 20_000_000_000;
+
+    assert!(compute_swap_math(requested, sol(), usdc(), 9, 6, &vault_in, &vault_out).is_err());
+
+    let fill = max_fillable_amount_in(requested, sol(), usdc(), 9, 6, &vault_in, &vault_out).unwrap();
+    assert!(fill < requested);
+
+    // The fill drains the vault to exactly its available balance and no further
+    let result = compute_swap_math(fill, sol(), usdc(), 9, 6, &vault_in, &vault_out).unwrap();
+    assert_eq!(result.raw_amount_out, vault_out.current_balance);
+    assert!(result.net_amount_out <= vault_out.current_balance);
+
+    // Rounding is down: one more unit no longer fits
+    assert!(compute_swap_math(fill + 1, sol(), usdc(), 9, 6, &vault_in, &vault_out).is_err());
+}
+
+#[test]
+fn protocol_fee_bounds_fill_below_full_utilization() {
+    // At 100% utilization the liquidity fee alone is 10_000 bps, so with a
+    // protocol fee it gets clamped; the largest fill stops short of that
+    let vault_in = make_vault(0, 1_000_000_000);
+    let vault_out = make_vault(5, 1_000_000_000);
+
+    let fill = max_fillable_amount_in(20_000_000_000, sol(), usdc(), 9, 6, &vault_in, &vault_out).unwrap();
+    let result = compute_swap_math(fill, sol(), usdc(), 9, 6, &vault_in, &vault_out).unwrap();
+
+    assert!(result.raw_amount_out < vault_out.current_balance);
+    assert!(result.net_amount_out <= vault_out.current_balance);
+    assert!(!result.fee_clamped);
+    assert!(compute_swap_math(fill + 1, sol(), usdc(), 9, 6, &vault_in, &vault_out).is_ok_and(|r| r.fee_clamped));
+}
+
+#[test]
+fn empty_output_vault_cannot_fill() {
+    let vault_in = make_vault(0, 1_000_000_000);
+    let vault_out = make_vault(5, 0);
+
+    let result = max_fillable_amount_in(1_000_000, sol(), usdc(), 9, 6, &vault_in, &vault_out);
+    assert!(matches!(result, Err(OxediumError::InsufficientLiquidity)));
+}
+
+#[test]
+fn dust_fill_is_not_reported_as_fillable() {
+    // Only inputs whose output floors to 0 would "fit" an almost empty vault
+    let vault_in = make_vault(0, 1_000_000_000);
+    let vault_out = make_vault(5, 1);
+
+    let result = max_fillable_amount_in(1_000_000_000, sol(), usdc(), 9, 6, &vault_in, &vault_out);
+    assert!(matches!(result, Err(OxediumError::InsufficientLiquidity)));
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator};
+use oxedium_program::components::{migrate_admin_data, LEGACY_ADMIN_LEN};
+use oxedium_program::states::Admin;
+use oxedium_program::utils::{OxediumError, ADMIN_SPACE};
+
+fn legacy_account(pubkey: Pubkey) -> Vec<u8> {
+    let mut data = Admin::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(pubkey.as_ref());
+    assert_eq!(data.len(), 40);
+    data
+}
+
+#[test]
+fn legacy_admin_keeps_its_key_and_collects() {
+    let admin_key = Pubkey::new_unique();
+    let data = legacy_account(admin_key);
+    assert_eq!(data.len(), LEGACY_ADMIN_LEN);
+
+    // The old account can't load as the current Admin
+    assert!(Admin::try_deserialize(&mut data.as_slice()).is_err());
+
+    let admin = migrate_admin_data(&data).unwrap();
+
+    assert_eq!(admin.pubkey, admin_key);
+    assert_eq!(admin.collector, admin_key);
+    assert_eq!(admin.pending_admin, Pubkey::default());
+    assert!(!admin.paused);
+}
+
+#[test]
+fn migrated_admin_round_trips_as_current_layout() {
+    let admin_key = Pubkey::new_unique();
+    let admin = migrate_admin_data(&legacy_account(admin_key)).unwrap();
+
+    let mut data = Vec::new();
+    admin.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), ADMIN_SPACE);
+
+    let reloaded = Admin::try_deserialize(&mut data.as_slice()).unwrap();
+    assert_eq!(reloaded.pubkey, admin_key);
+
+    // A second migration is refused
+    assert!(matches!(migrate_admin_data(&data), Err(OxediumError::AdminUpToDate)));
+}
+
+#[test]
+fn unknown_layout_or_other_account_is_rejected() {
+    let mut data = legacy_account(Pubkey::new_unique());
+    data.push(0);
+    assert!(matches!(migrate_admin_data(&data), Err(OxediumError::InvalidAdmin)));
+
+    let mut other = legacy_account(Pubkey::new_unique());
+    other[0] ^= 0xff;
+    assert!(matches!(migrate_admin_data(&other), Err(OxediumError::InvalidAdmin)));
+
+    assert!(matches!(migrate_admin_data(&[0; 4]), Err(OxediumError::InvalidAdmin)));
+}
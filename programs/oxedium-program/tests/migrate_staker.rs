@@ -0,0 +1,73 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator};
+use oxedium_program::components::{migrate_staker_data, LEGACY_STAKER_LEN};
+use oxedium_program::states::Staker;
+use oxedium_program::utils::{OxediumError, STAKER_SPACE, STAKER_VERSION};
+
+fn legacy_account(owner: Pubkey, vault: Pubkey, staked_amount: u64, last_cumulative_yield: u128, pending_claim: u64) -> Vec<u8> {
+    let mut data = Staker::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(owner.as_ref());
+    data.extend_from_slice(vault.as_ref());
+    data.extend_from_slice(&staked_amount.to_le_bytes());
+    data.extend_from_slice(&last_cumulative_yield.to_le_bytes());
+    data.extend_from_slice(&pending_claim.to_le_bytes());
+    assert_eq!(data.len(), LEGACY_STAKER_LEN);
+    data
+}
+
+fn current_account(staker: &Staker) -> Vec<u8> {
+    let mut data = Vec::new();
+    staker.try_serialize(&mut data).unwrap();
+    assert_eq!(data.len(), STAKER_SPACE);
+    data
+}
+
+#[test]
+fn legacy_account_keeps_its_fields_and_defaults_the_new_ones() {
+    let owner = Pubkey::new_unique();
+    let vault = Pubkey::new_unique();
+    let data = legacy_account(owner, vault, 5_000_000, 123_456_789, 42);
+
+    let staker = migrate_staker_data(&data).unwrap();
+
+    assert_eq!(staker.owner, owner);
+    assert_eq!(staker.vault, vault);
+    assert_eq!(staker.staked_amount, 5_000_000);
+    assert_eq!(staker.last_cumulative_yield, 123_456_789);
+    assert_eq!(staker.pending_claim, 42);
+    // Best available cost basis is the last snapshot
+    assert_eq!(staker.avg_entry_cumulative_yield, 123_456_789);
+    assert_eq!(staker.stake_unlock_ts, 0);
+    assert_eq!(staker.last_action_ts, 0);
+    assert!(!staker.rent_subsidized);
+    assert_eq!((staker.pending_unstake_amount, staker.unstake_available_ts, staker.subsidized_rent), (0, 0, 0));
+    assert_eq!(staker.version, STAKER_VERSION);
+}
+
+#[test]
+fn migrated_account_round_trips_as_current_layout() {
+    let data = legacy_account(Pubkey::new_unique(), Pubkey::new_unique(), 7, 8, 9);
+    let staker = migrate_staker_data(&data).unwrap();
+
+    // migrate_staker resizes to STAKER_SPACE and writes the account back
+    let migrated = current_account(&staker);
+    let reloaded = Staker::try_deserialize(&mut migrated.as_slice()).unwrap();
+    assert_eq!(reloaded.staked_amount, 7);
+    assert_eq!(reloaded.version, STAKER_VERSION);
+
+    // A second migration is refused
+    assert!(matches!(migrate_staker_data(&migrated), Err(OxediumError::StakerUpToDate)));
+}
+
+#[test]
+fn unknown_layout_or_other_account_is_rejected() {
+    let mut data = legacy_account(Pubkey::new_unique(), Pubkey::new_unique(), 1, 2, 3);
+    data.push(0);
+    assert!(matches!(migrate_staker_data(&data), Err(OxediumError::InvalidStaker)));
+
+    let mut data = legacy_account(Pubkey::new_unique(), Pubkey::new_unique(), 1, 2, 3);
+    data[0] ^= 0xff;
+    assert!(matches!(migrate_staker_data(&data), Err(OxediumError::InvalidStaker)));
+
+    assert!(matches!(migrate_staker_data(&[]), Err(OxediumError::InvalidStaker)));
+}
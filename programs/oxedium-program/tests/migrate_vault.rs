@@ -0,0 +1,128 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator};
+use oxedium_program::components::{
+    migrate_vault_data, preview_swap, settle_swap, DEFAULT_IMPACT_THRESHOLD_BPS, LEGACY_VAULT_LEN,
+};
+use oxedium_program::states::Vault;
+use oxedium_program::utils::{OxediumError, VAULT_SPACE, VAULT_VERSION};
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+const NOW: i64 = 1_700_000_000;
+
+fn make_price_feed(price: i64, exponent: i32) -> PriceFeedMessage {
+    PriceFeedMessage {
+        feed_id: [0u8; 32],
+        price,
+        conf: 0,
+        exponent,
+        publish_time: NOW,
+        prev_publish_time: NOW - 1,
+        ema_price: price,
+        ema_conf: 0,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn legacy_account(
+    base_fee_bps: u64,
+    protocol_fee_bps: u64,
+    max_exit_fee_bps: u64,
+    token_mint: Pubkey,
+    pyth_price_account: Pubkey,
+    max_age_price: u64,
+    initial_balance: u64,
+    current_balance: u64,
+    cumulative_yield_per_lp: u128,
+    oxe_cumulative_yield_per_staker: u128,
+) -> Vec<u8> {
+    let mut data = Vault::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&base_fee_bps.to_le_bytes());
+    data.extend_from_slice(&protocol_fee_bps.to_le_bytes());
+    data.extend_from_slice(&max_exit_fee_bps.to_le_bytes());
+    data.extend_from_slice(token_mint.as_ref());
+    data.extend_from_slice(pyth_price_account.as_ref());
+    data.extend_from_slice(&max_age_price.to_le_bytes());
+    data.extend_from_slice(&initial_balance.to_le_bytes());
+    data.extend_from_slice(&current_balance.to_le_bytes());
+    data.extend_from_slice(&cumulative_yield_per_lp.to_le_bytes());
+    data.extend_from_slice(&oxe_cumulative_yield_per_staker.to_le_bytes());
+    assert_eq!(data.len(), LEGACY_VAULT_LEN);
+    data
+}
+
+/// What resize_vault leaves in the account: the migrated vault at VAULT_SPACE
+fn resize(data: &[u8]) -> Vault {
+    let migrated = migrate_vault_data(data).unwrap();
+    let mut resized = Vec::new();
+    migrated.try_serialize(&mut resized).unwrap();
+    assert_eq!(resized.len(), VAULT_SPACE);
+    Vault::try_deserialize(&mut resized.as_slice()).unwrap()
+}
+
+#[test]
+fn legacy_vault_keeps_its_fields_and_defaults_the_new_ones() {
+    let mint = Pubkey::new_unique();
+    let pyth = Pubkey::new_unique();
+    let data = legacy_account(30, 5, 500, mint, pyth, 60, 1_000, 900, 77, 88);
+
+    let vault = resize(&data);
+
+    assert_eq!(vault.base_fee_bps, 30);
+    assert_eq!(vault.protocol_fee_bps, 5);
+    assert_eq!(vault.max_exit_fee_bps, 500);
+    assert_eq!(vault.token_mint, mint);
+    assert_eq!(vault.pyth_price_account, pyth);
+    assert_eq!(vault.max_age_price, 60);
+    assert_eq!(vault.initial_balance, 1_000);
+    assert_eq!(vault.current_balance, 900);
+    assert_eq!(vault.cumulative_yield_per_lp, 77);
+    assert_eq!(vault.oxe_cumulative_yield_per_staker, 88);
+
+    assert_eq!(vault.version, VAULT_VERSION);
+    assert_eq!(vault.impact_threshold_bps, DEFAULT_IMPACT_THRESHOLD_BPS);
+    assert!(!vault.protocol_fee_follows_curve);
+    assert_eq!(vault.max_protocol_fee_bps, 5);
+    assert_eq!(vault.feed_id, [0; 32]);
+    assert!(!vault.paused);
+    assert!(!vault.lockup_active);
+    assert_eq!(vault.max_total_deposits, 0);
+    assert_eq!(vault.protocol_yield, 0);
+    assert_eq!(vault.swap_count, 0);
+    assert_eq!(vault.fallback_pyth_price_account, Pubkey::default());
+    assert!(!vault.invert_price);
+    assert_eq!(vault.min_protocol_fee, 0);
+    assert!(!vault.stable_mode);
+}
+
+#[test]
+fn resized_vaults_still_swap() {
+    let sol = resize(&legacy_account(30, 5, 500, Pubkey::new_unique(), Pubkey::new_unique(), 60, 100_000_000_000, 100_000_000_000, 0, 0));
+    let mut usdc = resize(&legacy_account(30, 5, 500, Pubkey::new_unique(), Pubkey::new_unique(), 60, 18_000_000_000, 18_000_000_000, 0, 0));
+    let mut sol_in = sol.clone();
+    let sol_price = make_price_feed(18_000_000_000, -8);
+    let usdc_price = make_price_feed(100_000_000, -8);
+
+    // 1 SOL → USDC at $180
+    let result = preview_swap(1_000_000_000, sol_price, usdc_price, 9, 6, &sol, &usdc, NOW).unwrap();
+    assert_eq!(result.raw_amount_out, 180_000_000);
+    assert!(result.net_amount_out > 0 && result.net_amount_out < result.raw_amount_out);
+
+    settle_swap(&mut sol_in, &mut usdc, &result, result.protocol_fee_amount, 0, 18_000_000_000, 100_000_000, 0, NOW).unwrap();
+    assert_eq!(usdc.current_balance, 18_000_000_000 - result.net_amount_out);
+    assert_eq!(usdc.swap_count, 1);
+}
+
+#[test]
+fn current_or_unknown_layout_is_refused() {
+    let vault = resize(&legacy_account(30, 5, 500, Pubkey::new_unique(), Pubkey::new_unique(), 60, 1, 1, 0, 0));
+    let mut data = Vec::new();
+    vault.try_serialize(&mut data).unwrap();
+    assert!(matches!(migrate_vault_data(&data), Err(OxediumError::VaultUpToDate)));
+
+    data.push(0);
+    assert!(matches!(migrate_vault_data(&data), Err(OxediumError::InvalidVault)));
+
+    let mut legacy = legacy_account(30, 5, 500, Pubkey::new_unique(), Pubkey::new_unique(), 60, 1, 1, 0, 0);
+    legacy[0] ^= 0xff;
+    assert!(matches!(migrate_vault_data(&legacy), Err(OxediumError::InvalidVault)));
+}
@@ -0,0 +1,225 @@
+use oxedium_program::components::{compute_swap_math, preview_swap, SwapMathResult};
+use oxedium_program::states::Vault;
+use oxedium_program::utils::OxediumError;
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+mod common;
+
+fn make_price_feed(price: i64, conf: u64, exponent: i32) -> PriceFeedMessage {
+    PriceFeedMessage {
+        feed_id: [0u8; 32],
+        price,
+        conf,
+        exponent,
+        publish_time: 1_700_000_000,
+        prev_publish_time: 1_699_999_999,
+        ema_price: price,
+        ema_conf: conf,
+    }
+}
+
+fn make_vault(
+    base_fee_bps: u64,
+    protocol_fee_bps: u64,
+    initial_balance: u64,
+    current_balance: u64,
+) -> Vault {
+    Vault {
+        base_fee_bps,
+        protocol_fee_bps,
+        max_age_price: 60,
+        initial_balance,
+        current_balance,
+        ..common::vault()
+    }
+}
+
+// SOL constants (Pyth exponent = -8)
+const SOL_PRICE: i64 = 10_000_000_000; // $100.00
+const SOL_EXP: i32 = -8;
+const SOL_DECIMALS: u8 = 9;
+
+// USDC constants (Pyth exponent = -8)
+const USDC_PRICE: i64 = 100_000_000; // $1.00
+const USDC_EXP: i32 = -8;
+const USDC_DECIMALS: u8 = 6;
+
+// publish_time of make_price_feed
+const PUBLISH_TIME: i64 = 1_700_000_000;
+
+fn preview(now_ts: i64) -> anchor_lang::Result<SwapMathResult> {
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let vault_out = make_vault(30, 10, 1_000_000, 1_000_000);
+    preview_swap(
+        10_000,
+        make_price_feed(SOL_PRICE, 0, SOL_EXP),
+        make_price_feed(USDC_PRICE, 0, USDC_EXP),
+        SOL_DECIMALS,
+        USDC_DECIMALS,
+        &vault_in,
+        &vault_out,
+        now_ts,
+    )
+}
+
+#[test]
+fn fresh_oracles_match_compute_swap_math() {
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let vault_out = make_vault(30, 10, 1_000_000, 1_000_000);
+    let expected = compute_swap_math(
+        10_000,
+        make_price_feed(SOL_PRICE, 0, SOL_EXP),
+        make_price_feed(USDC_PRICE, 0, USDC_EXP),
+        SOL_DECIMALS,
+        USDC_DECIMALS,
+        &vault_in,
+        &vault_out,
+    )
+    .unwrap();
+
+    let result = preview(PUBLISH_TIME + 60).unwrap();
+    assert_eq!(result.net_amount_out, expected.net_amount_out);
+    assert_eq!(result.swap_fee_bps, expected.swap_fee_bps);
+}
+
+#[test]
+fn stale_oracle_is_rejected() {
+    // max_age_price = 60
+    assert_eq!(preview(PUBLISH_TIME + 61).err().unwrap(), OxediumError::OracleDataTooOld.into());
+}
+
+#[test]
+fn future_dated_oracle_is_rejected() {
+    assert_eq!(preview(PUBLISH_TIME - 1).err().unwrap(), OxediumError::OracleDataTooOld.into());
+}
+
+#[test]
+fn paused_vault_is_rejected() {
+    let vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let mut vault_out = make_vault(30, 10, 1_000_000, 1_000_000);
+    vault_out.paused = true;
+    let result = preview_swap(
+        10_000,
+        make_price_feed(SOL_PRICE, 0, SOL_EXP),
+        make_price_feed(USDC_PRICE, 0, USDC_EXP),
+        SOL_DECIMALS,
+        USDC_DECIMALS,
+        &vault_in,
+        &vault_out,
+        PUBLISH_TIME,
+    );
+    assert_eq!(result.err().unwrap(), OxediumError::VaultPaused.into());
+}
+
+fn preview_with_publish_times(
+    publish_in: i64,
+    publish_out: i64,
+    skew_in: i64,
+    skew_out: i64,
+) -> anchor_lang::Result<SwapMathResult> {
+    let mut vault_in = make_vault(30, 0, 1_000_000, 1_000_000);
+    let mut vault_out = make_vault(30, 10, 1_000_000, 1_000_000);
+    vault_in.max_oracle_skew = skew_in;
+    vault_out.max_oracle_skew = skew_out;
+    let mut oracle_in = make_price_feed(SOL_PRICE, 0, SOL_EXP);
+    let mut oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    oracle_in.publish_time = publish_in;
+    oracle_out.publish_time = publish_out;
+    preview_swap(
+        10_000,
+        oracle_in,
+        oracle_out,
+        SOL_DECIMALS,
+        USDC_DECIMALS,
+        &vault_in,
+        &vault_out,
+        PUBLISH_TIME + 60,
+    )
+}
+
+#[test]
+fn close_oracle_publish_times_pass() {
+    assert!(preview_with_publish_times(PUBLISH_TIME, PUBLISH_TIME + 5, 10, 10).is_ok());
+    assert!(preview_with_publish_times(PUBLISH_TIME + 10, PUBLISH_TIME, 10, 10).is_ok());
+}
+
+#[test]
+fn far_apart_but_fresh_oracles_are_rejected() {
+    // Both within max_age_price = 60 of now, but 50s apart
+    let result = preview_with_publish_times(PUBLISH_TIME + 55, PUBLISH_TIME + 5, 10, 10);
+    assert_eq!(result.err().unwrap(), OxediumError::OracleSkewTooLarge.into());
+}
+
+#[test]
+fn stricter_vault_skew_applies() {
+    // Output vault allows 60s, input vault only 10s
+    let result = preview_with_publish_times(PUBLISH_TIME, PUBLISH_TIME + 30, 10, 60);
+    assert_eq!(result.err().unwrap(), OxediumError::OracleSkewTooLarge.into());
+
+    // Only one vault sets a bound: it still applies
+    let result = preview_with_publish_times(PUBLISH_TIME, PUBLISH_TIME + 30, 0, 10);
+    assert_eq!(result.err().unwrap(), OxediumError::OracleSkewTooLarge.into());
+
+    // Neither sets one: any skew within staleness passes
+    assert!(preview_with_publish_times(PUBLISH_TIME, PUBLISH_TIME + 50, 0, 0).is_ok());
+}
+
+// --- protocol fee waiver ---
+
+fn preview_with_waiver(waiver_until: i64, now_ts: i64) -> SwapMathResult {
+    let vault_in = make_vault(30, 0, 100_000_000, 100_000_000);
+    let mut vault_out = make_vault(30, 10, 100_000_000, 100_000_000);
+    vault_out.protocol_fee_waiver_until = waiver_until;
+    let result = preview_swap(
+        10_000_000,
+        make_price_feed(SOL_PRICE, 0, SOL_EXP),
+        make_price_feed(USDC_PRICE, 0, USDC_EXP),
+        SOL_DECIMALS,
+        USDC_DECIMALS,
+        &vault_in,
+        &vault_out,
+        now_ts,
+    )
+    .unwrap();
+
+    // The waiver only affects pricing, never the stored fee
+    assert_eq!(vault_out.protocol_fee_bps, 10);
+    result
+}
+
+#[test]
+fn active_waiver_charges_no_protocol_fee() {
+    let waived = preview_with_waiver(PUBLISH_TIME + 1, PUBLISH_TIME);
+    let normal = preview_with_waiver(0, PUBLISH_TIME);
+
+    assert_eq!(waived.protocol_fee_amount, 0);
+    assert_eq!(normal.protocol_fee_amount, 1_000); // 10 bps of $1
+    assert_eq!(waived.lp_fee_amount, normal.lp_fee_amount);
+    assert_eq!(waived.net_amount_out, normal.net_amount_out + normal.protocol_fee_amount);
+}
+
+#[test]
+fn expired_waiver_charges_the_configured_protocol_fee() {
+    // The waiver ends at waiver_until itself
+    let expired = preview_with_waiver(PUBLISH_TIME, PUBLISH_TIME);
+    assert_eq!(expired.protocol_fee_amount, 1_000);
+}
+
+#[test]
+fn waiver_on_the_input_vault_does_not_apply() {
+    let mut vault_in = make_vault(30, 0, 100_000_000, 100_000_000);
+    vault_in.protocol_fee_waiver_until = i64::MAX;
+    let vault_out = make_vault(30, 10, 100_000_000, 100_000_000);
+    let result = preview_swap(
+        10_000_000,
+        make_price_feed(SOL_PRICE, 0, SOL_EXP),
+        make_price_feed(USDC_PRICE, 0, USDC_EXP),
+        SOL_DECIMALS,
+        USDC_DECIMALS,
+        &vault_in,
+        &vault_out,
+        PUBLISH_TIME,
+    )
+    .unwrap();
+    assert_eq!(result.protocol_fee_amount, 1_000);
+}
@@ -0,0 +1,122 @@
+use oxedium_program::components::{calculate_staker_yield, compute_swap_math, lp_yield_for_stake, preview_swap, with_hypothetical_stake};
+use oxedium_program::states::Vault;
+use oxedium_program::utils::SCALE;
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+mod common;
+
+// SOL: $180.00, USDC: $1.00 (Pyth exponent -8)
+const SOL_PRICE: i64 = 18_000_000_000;
+const USDC_PRICE: i64 = 100_000_000;
+const EXP: i32 = -8;
+const SOL_DEC: u8 = 9;
+const USDC_DEC: u8 = 6;
+
+fn make_price_feed(price: i64) -> PriceFeedMessage {
+    PriceFeedMessage {
+        feed_id: [0u8; 32],
+        price,
+        conf: 0,
+        exponent: EXP,
+        publish_time: 1_700_000_000,
+        prev_publish_time: 1_699_999_999,
+        ema_price: price,
+        ema_conf: 0,
+    }
+}
+
+fn make_vault(initial_balance: u64, current_balance: u64) -> Vault {
+    Vault {
+        base_fee_bps: 30,
+        protocol_fee_bps: 5,
+        initial_balance,
+        current_balance,
+        ..common::vault()
+    }
+}
+
+/// Stakes `stake` into `vault_out`, runs a real swap and returns the staker's yield.
+fn stake_then_swap(vault_in: &Vault, vault_out: &Vault, amount_in: u64, stake: u64) -> u64 {
+    let mut vault_out = vault_out.clone();
+    vault_out.initial_balance += stake;
+    vault_out.current_balance += stake;
+    let last_cumulative_yield = vault_out.cumulative_yield_per_lp;
+
+    let result = compute_swap_math(
+        amount_in,
+        make_price_feed(SOL_PRICE),
+        make_price_feed(USDC_PRICE),
+        SOL_DEC,
+        USDC_DEC,
+        vault_in,
+        &vault_out,
+    )
+    .unwrap();
+    vault_out.cumulative_yield_per_lp +=
+        (result.lp_fee_amount as u128 * SCALE) / vault_out.initial_balance as u128;
+
+    calculate_staker_yield(vault_out.cumulative_yield_per_lp, stake, last_cumulative_yield).unwrap()
+}
+
+/// The instruction's path: add the stake, price through `preview_swap`, spread the LP fee
+fn quote(vault_in: &Vault, vault_out: &Vault, amount_in: u64, stake: u64) -> u64 {
+    let staked_vault_out = with_hypothetical_stake(vault_out, stake).unwrap();
+    let result = preview_swap(
+        amount_in,
+        make_price_feed(SOL_PRICE),
+        make_price_feed(USDC_PRICE),
+        SOL_DEC,
+        USDC_DEC,
+        vault_in,
+        &staked_vault_out,
+        1_700_000_000,
+    )
+    .unwrap();
+
+    lp_yield_for_stake(result.lp_fee_amount, &staked_vault_out, stake).unwrap()
+}
+
+#[test]
+fn sole_lp_earns_entire_lp_fee() {
+    // Empty USDC vault: the hypothetical stake is the only LP.
+    // 1 SOL → 180 USDC raw, lp_fee = 180_000_000 × 30 / 10_000 = 540_000
+    let vault_in = make_vault(100_000_000_000, 100_000_000_000);
+    let vault_out = make_vault(0, 0);
+
+    assert_eq!(quote(&vault_in, &vault_out, 1_000_000_000, 18_000_000_000), 540_000);
+}
+
+#[test]
+fn quote_matches_real_stake_and_swap() {
+    // Existing LPs hold 18 000 USDC, the new LP considers adding 2 000 USDC (10% share)
+    let vault_in = make_vault(110_000_000_000, 110_000_000_000);
+    let vault_out = make_vault(18_000_000_000, 18_000_000_000);
+
+    let quoted = quote(&vault_in, &vault_out, 5_000_000_000, 2_000_000_000);
+    let real = stake_then_swap(&vault_in, &vault_out, 5_000_000_000, 2_000_000_000);
+
+    // 5 SOL → 900 USDC raw, lp_fee = 2_700_000; 10% share = 270_000
+    assert_eq!(quoted, 270_000);
+    assert_eq!(quoted, real);
+}
+
+#[test]
+fn quote_matches_real_swap_on_imbalanced_vault() {
+    // USDC vault already drained by prior swaps → imbalance and impact curves apply
+    let vault_in = make_vault(110_000_000_000, 126_000_000_000);
+    let vault_out = make_vault(18_000_000_000, 15_136_200_000);
+
+    let quoted = quote(&vault_in, &vault_out, 10_000_000_000, 3_000_000_000);
+    let real = stake_then_swap(&vault_in, &vault_out, 10_000_000_000, 3_000_000_000);
+
+    assert!(quoted > 0);
+    assert_eq!(quoted, real);
+}
+
+#[test]
+fn zero_stake_earns_nothing() {
+    let vault_in = make_vault(100_000_000_000, 100_000_000_000);
+    let vault_out = make_vault(18_000_000_000, 18_000_000_000);
+
+    assert_eq!(quote(&vault_in, &vault_out, 1_000_000_000, 0), 0);
+}
@@ -1,4 +1,5 @@
-use oxedium_program::components::raw_amount_out;
+use oxedium_program::components::{raw_amount_out, raw_amount_out_with_inversion, select_price};
+use oxedium_program::utils::OxediumError;
 use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
 
 fn make_price_feed(price: i64, conf: u64, exponent: i32) -> PriceFeedMessage {
@@ -80,7 +81,7 @@ fn one_usdc_gives_correct_sol_fraction() {
 fn same_price_and_decimals_returns_same_amount() {
     // Both tokens at $100 with 6 decimals → 1:1 swap
     let oracle = make_price_feed(10_000_000_000, 0, -8);
-    let out = raw_amount_out(500_000, 6, 6, oracle.clone(), oracle).unwrap();
+    let out = raw_amount_out(500_000, 6, 6, oracle, oracle).unwrap();
     assert_eq!(out, 500_000);
 }
 
@@ -104,6 +105,67 @@ fn positive_exponent_handled_correctly() {
     assert_eq!(out, 100_000_000);
 }
 
+// --- mixed-sign exponents ---
+
+#[test]
+fn negative_in_positive_out_exponent() {
+    // SOL at $100 (10_000_000_000e-8) into a token quoted at $2 000 (2e3)
+    // 1 SOL → 0.05 token (9 decimals)
+    let oracle_in = make_price_feed(SOL_PRICE, 0, SOL_EXP);
+    let oracle_out = make_price_feed(2, 0, 3);
+    let out = raw_amount_out(1_000_000_000, 9, 9, oracle_in, oracle_out).unwrap();
+    assert_eq!(out, 50_000_000);
+}
+
+#[test]
+fn positive_in_negative_out_exponent() {
+    // Token at $2 000 (2e3) into USDC at $1 (100_000_000e-8)
+    // 0.5 token (9 decimals) → 1 000 USDC (6 decimals)
+    let oracle_in = make_price_feed(2, 0, 3);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let out = raw_amount_out(500_000_000, 9, 6, oracle_in, oracle_out).unwrap();
+    assert_eq!(out, 1_000_000_000);
+}
+
+#[test]
+fn mixed_sign_round_trip_is_consistent() {
+    let token = make_price_feed(2, 0, 3);
+    let sol = make_price_feed(SOL_PRICE, 0, SOL_EXP);
+    let tokens = raw_amount_out(1_000_000_000, 9, 9, sol, token).unwrap();
+    let back = raw_amount_out(tokens, 9, 9, token, sol).unwrap();
+    assert_eq!(back, 1_000_000_000);
+}
+
+#[test]
+fn positive_exponent_in_with_large_amount_does_not_overflow() {
+    // exponent_in = +6 on a 9-decimal token, max amount. usd_fp reaches
+    // ~1.8e22 × 1e12 × 1e6 ≈ 1.8e40, past u128, but the result fits a u64.
+    let oracle_in = make_price_feed(1_000_000_000_000, 0, 6);
+    let oracle_out = make_price_feed(1_000_000_000_000, 0, 6);
+    let out = raw_amount_out(u64::MAX, 9, 9, oracle_in, oracle_out).unwrap();
+    assert_eq!(out, u64::MAX);
+}
+
+#[test]
+fn positive_in_negative_out_with_large_prices_does_not_overflow() {
+    // $1e9 per token either way: 1_000e+6 in, 1e17e-8 out. The output leg
+    // multiplies by 1e8 before dividing by price, past u128 for a max amount.
+    let oracle_in = make_price_feed(1_000, 0, 6);
+    let oracle_out = make_price_feed(100_000_000_000_000_000, 0, -8);
+    let out = raw_amount_out(u64::MAX, 9, 9, oracle_in, oracle_out).unwrap();
+    assert_eq!(out, u64::MAX);
+}
+
+#[test]
+fn result_above_u64_is_a_cast_error() {
+    let oracle_in = make_price_feed(1_000, 0, 6);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    assert!(matches!(
+        raw_amount_out(u64::MAX, 9, 6, oracle_in, oracle_out),
+        Err(OxediumError::OverflowInCast)
+    ));
+}
+
 // --- error cases ---
 
 #[test]
@@ -134,13 +196,33 @@ fn negative_price_out_returns_error() {
 }
 
 #[test]
-fn exponent_too_large_returns_overflow_error() {
-    // exponent > 38 triggers pow10 overflow guard
+fn exponent_too_large_returns_invalid_price() {
+    // exponent > 38 is rejected before any scaling
     let oracle_in = make_price_feed(100, 0, 39);
     let oracle_out = make_price_feed(100, 0, -8);
 
     let result = raw_amount_out(1_000_000, 6, 6, oracle_in, oracle_out);
-    assert!(result.is_err());
+    assert!(matches!(result, Err(OxediumError::InvalidPrice)));
+}
+
+#[test]
+fn out_of_range_exponents_return_invalid_price_on_either_feed() {
+    let usdc = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    for exponent in [-39, 39, i32::MIN, i32::MAX] {
+        let bad = make_price_feed(100, 0, exponent);
+        assert!(matches!(raw_amount_out(1_000_000, 6, 6, bad, usdc), Err(OxediumError::InvalidPrice)));
+        assert!(matches!(raw_amount_out(1_000_000, 6, 6, usdc, bad), Err(OxediumError::InvalidPrice)));
+    }
+}
+
+#[test]
+fn exponents_at_the_bounds_are_accepted() {
+    // 10^38 and 10^-38 are still in range; they only fail if the math overflows
+    let usdc = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let tiny = make_price_feed(1, 0, -38);
+    assert!(matches!(raw_amount_out(1_000_000, 6, 6, tiny, usdc), Err(OxediumError::OutputRoundsToZero)));
+    let huge = make_price_feed(1, 0, 38);
+    assert!(matches!(raw_amount_out(1_000_000, 6, 6, huge, usdc), Err(OxediumError::OverflowInCast)));
 }
 
 // --- zero amount ---
@@ -153,3 +235,150 @@ fn zero_amount_in_returns_zero() {
     let out = raw_amount_out(0, SOL_DECIMALS, USDC_DECIMALS, oracle_in, oracle_out).unwrap();
     assert_eq!(out, 0);
 }
+
+// --- EMA pricing mode ---
+
+fn make_price_feed_with_ema(price: i64, conf: u64, ema_price: i64, ema_conf: u64, exponent: i32) -> PriceFeedMessage {
+    PriceFeedMessage {
+        ema_price,
+        ema_conf,
+        ..make_price_feed(price, conf, exponent)
+    }
+}
+
+#[test]
+fn ema_mode_prices_off_ema_when_spot_spikes() {
+    // Spot SOL spiked to $120 for a slot; the EMA is still $100
+    let oracle_in = make_price_feed_with_ema(12_000_000_000, 0, SOL_PRICE, 0, SOL_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+
+    let spot = raw_amount_out(1_000_000_000, SOL_DECIMALS, USDC_DECIMALS, oracle_in, oracle_out).unwrap();
+    let ema = raw_amount_out(
+        1_000_000_000,
+        SOL_DECIMALS,
+        USDC_DECIMALS,
+        select_price(oracle_in, true),
+        select_price(oracle_out, true),
+    )
+    .unwrap();
+
+    assert_eq!(spot, 120_000_000);
+    assert_eq!(ema, 100_000_000);
+}
+
+#[test]
+fn ema_mode_uses_ema_conf_for_bounds() {
+    // Spot conf is 0, EMA conf is $1 → EMA output is bounded by (100 - 1) / 1
+    let oracle_in = make_price_feed_with_ema(SOL_PRICE, 0, SOL_PRICE, 100_000_000, SOL_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+
+    let out = raw_amount_out(1_000_000_000, SOL_DECIMALS, USDC_DECIMALS, select_price(oracle_in, true), oracle_out).unwrap();
+    assert_eq!(out, 99_000_000);
+}
+
+#[test]
+fn spot_mode_leaves_message_untouched() {
+    let oracle = make_price_feed_with_ema(12_000_000_000, 5, SOL_PRICE, 7, SOL_EXP);
+    let selected = select_price(oracle, false);
+    assert_eq!(selected.price, 12_000_000_000);
+    assert_eq!(selected.conf, 5);
+}
+
+#[test]
+fn negative_ema_price_is_rejected_in_ema_mode() {
+    let oracle_in = make_price_feed_with_ema(SOL_PRICE, 0, -1, 0, SOL_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+
+    assert!(raw_amount_out(1_000_000_000, SOL_DECIMALS, USDC_DECIMALS, oracle_in, oracle_out).is_ok());
+    assert!(raw_amount_out(1_000_000_000, SOL_DECIMALS, USDC_DECIMALS, select_price(oracle_in, true), oracle_out).is_err());
+}
+
+// --- output rounding to zero ---
+
+#[test]
+fn dust_into_low_decimal_token_rounds_to_zero_error() {
+    // 9-decimal token at $1 into a 2-decimal token at $1:
+    // anything under 0.01 (10_000_000 base units) floors to 0
+    let oracle_in = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let oracle_out = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+
+    assert!(matches!(
+        raw_amount_out(9_999_999, 9, 2, oracle_in, oracle_out),
+        Err(OxediumError::OutputRoundsToZero)
+    ));
+    assert_eq!(raw_amount_out(10_000_000, 9, 2, oracle_in, oracle_out).unwrap(), 1);
+}
+
+// --- inverted (token-per-USD) feeds ---
+
+// The SOL feed quoted the other way: 0.01 SOL per USD
+//   → stored price = 1_000_000, exponent = -8
+const SOL_PER_USD_PRICE: i64 = 1_000_000;
+
+#[test]
+fn inverted_input_feed_matches_the_reciprocal_usd_feed() {
+    let usdc = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let direct = raw_amount_out(1_000_000_000, SOL_DECIMALS, USDC_DECIMALS, make_price_feed(SOL_PRICE, 0, SOL_EXP), usdc).unwrap();
+    let inverted = raw_amount_out_with_inversion(
+        1_000_000_000, SOL_DECIMALS, USDC_DECIMALS,
+        make_price_feed(SOL_PER_USD_PRICE, 0, SOL_EXP), usdc,
+        true, false,
+    ).unwrap();
+
+    assert_eq!(direct, 100_000_000);
+    assert_eq!(inverted, direct);
+}
+
+#[test]
+fn inverted_output_feed_matches_the_reciprocal_usd_feed() {
+    let usdc = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let direct = raw_amount_out(100_000_000, USDC_DECIMALS, SOL_DECIMALS, usdc, make_price_feed(SOL_PRICE, 0, SOL_EXP)).unwrap();
+    let inverted = raw_amount_out_with_inversion(
+        100_000_000, USDC_DECIMALS, SOL_DECIMALS,
+        usdc, make_price_feed(SOL_PER_USD_PRICE, 0, SOL_EXP),
+        false, true,
+    ).unwrap();
+
+    assert_eq!(direct, 1_000_000_000);
+    assert_eq!(inverted, direct);
+}
+
+#[test]
+fn inverted_feed_with_positive_exponent() {
+    // A $0.0002 token: 2 * 10^-4 USD per token, or 5 * 10^3 tokens per USD.
+    // 5_000 tokens (6 decimals) → $1 → 1 USDC
+    let usdc = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let direct = raw_amount_out(5_000_000_000, 6, USDC_DECIMALS, make_price_feed(2, 0, -4), usdc).unwrap();
+    let inverted = raw_amount_out_with_inversion(5_000_000_000, 6, USDC_DECIMALS, make_price_feed(5, 0, 3), usdc, true, false).unwrap();
+
+    assert_eq!(direct, 1_000_000);
+    assert_eq!(inverted, direct);
+}
+
+#[test]
+fn inverted_feed_confidence_still_works_against_the_trader() {
+    let usdc = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    let in_no_conf = make_price_feed(SOL_PER_USD_PRICE, 0, SOL_EXP);
+    let in_conf = make_price_feed(SOL_PER_USD_PRICE, 10_000, SOL_EXP);
+    let sell = |feed| raw_amount_out_with_inversion(1_000_000_000, SOL_DECIMALS, USDC_DECIMALS, feed, usdc, true, false).unwrap();
+    let buy = |feed| raw_amount_out_with_inversion(100_000_000, USDC_DECIMALS, SOL_DECIMALS, usdc, feed, false, true).unwrap();
+
+    assert!(sell(in_conf) < sell(in_no_conf));
+    assert!(buy(in_conf) < buy(in_no_conf));
+}
+
+#[test]
+fn inverted_feed_rejects_non_positive_price() {
+    let usdc = make_price_feed(USDC_PRICE, 0, USDC_EXP);
+    for price in [0, -1] {
+        let feed = make_price_feed(price, 0, SOL_EXP);
+        assert!(matches!(
+            raw_amount_out_with_inversion(1_000_000_000, SOL_DECIMALS, USDC_DECIMALS, feed, usdc, true, false),
+            Err(OxediumError::InvalidPrice)
+        ));
+        assert!(matches!(
+            raw_amount_out_with_inversion(100_000_000, USDC_DECIMALS, SOL_DECIMALS, usdc, feed, false, true),
+            Err(OxediumError::InvalidPrice)
+        ));
+    }
+}
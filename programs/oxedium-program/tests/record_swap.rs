@@ -0,0 +1,98 @@
+use oxedium_program::components::{compute_swap_math, record_swap};
+use oxedium_program::states::Vault;
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+mod common;
+
+const NOW: i64 = 1_700_000_000;
+
+fn make_price_feed(price: i64) -> PriceFeedMessage {
+    PriceFeedMessage {
+        feed_id: [0u8; 32],
+        price,
+        conf: 0,
+        exponent: -8,
+        publish_time: NOW,
+        prev_publish_time: NOW - 1,
+        ema_price: price,
+        ema_conf: 0,
+    }
+}
+
+fn make_vault(initial_balance: u64, current_balance: u64) -> Vault {
+    Vault {
+        base_fee_bps: 30,
+        protocol_fee_bps: 5,
+        initial_balance,
+        current_balance,
+        ..common::vault()
+    }
+}
+
+#[test]
+fn first_swap_seeds_tracking_fields() {
+    let mut vault = make_vault(1_000_000, 1_000_000);
+
+    record_swap(&mut vault, NOW).unwrap();
+
+    assert_eq!(vault.swap_count, 1);
+    assert_eq!(vault.last_swap_ts, NOW);
+}
+
+#[test]
+fn subsequent_swaps_advance_tracking_fields() {
+    let mut vault = make_vault(1_000_000, 1_000_000);
+
+    record_swap(&mut vault, NOW).unwrap();
+    record_swap(&mut vault, NOW + 30).unwrap();
+    record_swap(&mut vault, NOW + 45).unwrap();
+
+    assert_eq!(vault.swap_count, 3);
+    assert_eq!(vault.last_swap_ts, NOW + 45);
+}
+
+#[test]
+fn swap_count_overflow_returns_error() {
+    let mut vault = make_vault(1_000_000, 1_000_000);
+    vault.swap_count = u64::MAX;
+
+    assert!(record_swap(&mut vault, NOW).is_err());
+}
+
+#[test]
+fn first_swap_fee_matches_subsequent_swap_with_same_balances() {
+    // A fresh vault (current == initial, no tracking history) and a vault that has
+    // already seen swaps but sits at the same balances must price identically.
+    let vault_in = make_vault(1_000_000_000, 1_000_000_000);
+    let fresh_out = make_vault(1_000_000_000, 1_000_000_000);
+
+    let mut seasoned_out = make_vault(1_000_000_000, 1_000_000_000);
+    seasoned_out.swap_count = 42;
+    seasoned_out.last_swap_ts = NOW - 60;
+
+    let first = compute_swap_math(
+        200_000_000,
+        make_price_feed(100_000_000),
+        make_price_feed(100_000_000),
+        6,
+        6,
+        &vault_in,
+        &fresh_out,
+    )
+    .unwrap();
+    let later = compute_swap_math(
+        200_000_000,
+        make_price_feed(100_000_000),
+        make_price_feed(100_000_000),
+        6,
+        6,
+        &vault_in,
+        &seasoned_out,
+    )
+    .unwrap();
+
+    assert_eq!(first.swap_fee_bps, later.swap_fee_bps);
+    assert_eq!(first.net_amount_out, later.net_amount_out);
+    assert_eq!(first.lp_fee_amount, later.lp_fee_amount);
+    assert_eq!(first.protocol_fee_amount, later.protocol_fee_amount);
+}
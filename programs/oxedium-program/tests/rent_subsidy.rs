@@ -0,0 +1,110 @@
+use oxedium_program::components::{apply_rent_subsidy, refund_rent_subsidy, rent_subsidy_refund};
+use oxedium_program::states::RentReserve;
+use oxedium_program::utils::OxediumError;
+
+// Rent-exempt minimum of a Staker PDA (8 + 32 + 32 + 8 + 16 + 8 + 1 = 105 bytes)
+const STAKER_RENT: u64 = 1_621_680;
+
+fn make_reserve(rent_reserve: u64, min_subsidized_deposit: u64, max_subsidized_stakers: u64) -> RentReserve {
+    RentReserve {
+        rent_reserve,
+        min_subsidized_deposit,
+        max_subsidized_stakers,
+        subsidized_stakers: 0,
+    }
+}
+
+// --- subsidized init ---
+
+#[test]
+fn qualifying_first_deposit_is_subsidized() {
+    let mut reserve = make_reserve(10 * STAKER_RENT, 1_000_000, 5);
+
+    let subsidy = apply_rent_subsidy(&mut reserve, 1_000_000, STAKER_RENT).unwrap();
+
+    assert_eq!(subsidy, STAKER_RENT);
+    assert_eq!(reserve.rent_reserve, 9 * STAKER_RENT);
+    assert_eq!(reserve.subsidized_stakers, 1);
+}
+
+#[test]
+fn deposit_below_minimum_is_not_subsidized() {
+    let mut reserve = make_reserve(10 * STAKER_RENT, 1_000_000, 5);
+
+    let subsidy = apply_rent_subsidy(&mut reserve, 999_999, STAKER_RENT).unwrap();
+
+    assert_eq!(subsidy, 0);
+    assert_eq!(reserve.rent_reserve, 10 * STAKER_RENT);
+    assert_eq!(reserve.subsidized_stakers, 0);
+}
+
+#[test]
+fn max_subsidized_stakers_caps_exposure() {
+    let mut reserve = make_reserve(10 * STAKER_RENT, 1, 2);
+
+    assert_eq!(apply_rent_subsidy(&mut reserve, 1, STAKER_RENT).unwrap(), STAKER_RENT);
+    assert_eq!(apply_rent_subsidy(&mut reserve, 1, STAKER_RENT).unwrap(), STAKER_RENT);
+    // third staker exceeds the cap even though lamports remain
+    assert_eq!(apply_rent_subsidy(&mut reserve, 1, STAKER_RENT).unwrap(), 0);
+
+    assert_eq!(reserve.subsidized_stakers, 2);
+    assert_eq!(reserve.rent_reserve, 8 * STAKER_RENT);
+}
+
+#[test]
+fn reserve_is_never_drained_below_zero() {
+    // Only enough for one full subsidy plus dust — the second staker pays their own rent
+    let mut reserve = make_reserve(STAKER_RENT + STAKER_RENT / 2, 1, 10);
+
+    assert_eq!(apply_rent_subsidy(&mut reserve, 1, STAKER_RENT).unwrap(), STAKER_RENT);
+    assert_eq!(apply_rent_subsidy(&mut reserve, 1, STAKER_RENT).unwrap(), 0);
+
+    assert_eq!(reserve.rent_reserve, STAKER_RENT / 2);
+    assert_eq!(reserve.subsidized_stakers, 1);
+}
+
+#[test]
+fn zero_max_disables_subsidies() {
+    let mut reserve = make_reserve(10 * STAKER_RENT, 0, 0);
+    assert_eq!(apply_rent_subsidy(&mut reserve, u64::MAX, STAKER_RENT).unwrap(), 0);
+}
+
+// --- refund on close ---
+
+#[test]
+fn refund_on_close_restores_reserve() {
+    let mut reserve = make_reserve(10 * STAKER_RENT, 1_000_000, 5);
+
+    let subsidy = apply_rent_subsidy(&mut reserve, 1_000_000, STAKER_RENT).unwrap();
+    refund_rent_subsidy(&mut reserve, subsidy).unwrap();
+
+    assert_eq!(reserve.rent_reserve, 10 * STAKER_RENT);
+    assert_eq!(reserve.subsidized_stakers, 0);
+}
+
+#[test]
+fn refund_frees_a_slot_for_the_next_staker() {
+    let mut reserve = make_reserve(10 * STAKER_RENT, 1, 1);
+
+    let subsidy = apply_rent_subsidy(&mut reserve, 1, STAKER_RENT).unwrap();
+    assert_eq!(apply_rent_subsidy(&mut reserve, 1, STAKER_RENT).unwrap(), 0);
+
+    refund_rent_subsidy(&mut reserve, subsidy).unwrap();
+    assert_eq!(apply_rent_subsidy(&mut reserve, 1, STAKER_RENT).unwrap(), STAKER_RENT);
+}
+
+#[test]
+fn refund_without_subsidy_returns_error() {
+    let mut reserve = make_reserve(0, 1, 1);
+    let result = refund_rent_subsidy(&mut reserve, STAKER_RENT);
+    assert!(matches!(result, Err(OxediumError::OverflowInSub)));
+}
+
+#[test]
+fn close_returns_only_the_subsidy_to_the_reserve() {
+    // migrate_staker top-up on top of the subsidized rent stays with the owner
+    let lamports = STAKER_RENT + 55_680;
+
+    assert_eq!(rent_subsidy_refund(lamports, STAKER_RENT), STAKER_RENT);
+    assert_eq!(rent_subsidy_refund(STAKER_RENT, STAKER_RENT), STAKER_RENT);
+}
@@ -0,0 +1,28 @@
+use oxedium_program::components::slippage_min_out;
+
+#[test]
+fn one_percent_keeps_ninety_nine_percent_of_raw_out() {
+    assert_eq!(slippage_min_out(1_000_000, 100), 990_000);
+}
+
+#[test]
+fn zero_slippage_requires_full_raw_out() {
+    assert_eq!(slippage_min_out(1_000_000, 0), 1_000_000);
+}
+
+#[test]
+fn bound_rounds_up_in_the_traders_favour() {
+    // 999 × 9_950 / 10_000 = 994.005
+    assert_eq!(slippage_min_out(999, 50), 995);
+}
+
+#[test]
+fn full_or_larger_slippage_disables_the_bound() {
+    assert_eq!(slippage_min_out(1_000_000, 10_000), 0);
+    assert_eq!(slippage_min_out(1_000_000, u64::MAX), 0);
+}
+
+#[test]
+fn large_raw_out_does_not_overflow() {
+    assert_eq!(slippage_min_out(u64::MAX, 0), u64::MAX);
+}
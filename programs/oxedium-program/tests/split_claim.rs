@@ -0,0 +1,22 @@
+use oxedium_program::components::split_claim;
+use oxedium_program::utils::OxediumError;
+
+#[test]
+fn zero_request_claims_everything() {
+    assert_eq!(split_claim(1_000, 0).unwrap(), (1_000, 0));
+}
+
+#[test]
+fn exact_request_claims_everything() {
+    assert_eq!(split_claim(1_000, 1_000).unwrap(), (1_000, 0));
+}
+
+#[test]
+fn half_claim_leaves_remainder_pending() {
+    assert_eq!(split_claim(1_000, 500).unwrap(), (500, 500));
+}
+
+#[test]
+fn over_claim_is_rejected() {
+    assert!(matches!(split_claim(1_000, 1_001), Err(OxediumError::ClaimExceedsYield)));
+}
@@ -0,0 +1,40 @@
+use oxedium_program::components::split_referral_fee;
+
+#[test]
+fn no_referrer_leaves_protocol_fee_unchanged() {
+    // swap() passes 0 bps when no referrer_ata is supplied
+    assert_eq!(split_referral_fee(1_000, 0, 5_000).unwrap(), (1_000, 0));
+}
+
+#[test]
+fn referrer_gets_requested_share_of_protocol_fee() {
+    // 20% of the protocol fee
+    let (kept, referral) = split_referral_fee(1_000, 2_000, 5_000).unwrap();
+    assert_eq!(referral, 200);
+    assert_eq!(kept, 800);
+    assert_eq!(kept + referral, 1_000);
+}
+
+#[test]
+fn referral_bps_above_cap_is_clamped() {
+    // Asks for 80%, vault allows 30%
+    let (kept, referral) = split_referral_fee(1_000, 8_000, 3_000).unwrap();
+    assert_eq!(referral, 300);
+    assert_eq!(kept, 700);
+}
+
+#[test]
+fn referral_never_exceeds_protocol_fee() {
+    assert_eq!(split_referral_fee(1_000, u64::MAX, u64::MAX).unwrap(), (0, 1_000));
+}
+
+#[test]
+fn zero_cap_disables_referrals() {
+    assert_eq!(split_referral_fee(1_000, 2_000, 0).unwrap(), (1_000, 0));
+}
+
+#[test]
+fn referral_rounds_down_in_favour_of_protocol() {
+    // 3 * 5_000 / 10_000 = 1.5 → 1
+    assert_eq!(split_referral_fee(3, 5_000, 10_000).unwrap(), (2, 1));
+}
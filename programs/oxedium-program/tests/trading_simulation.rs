@@ -11,14 +11,19 @@
 //!   Phase 5  – Swap:      Charlie 3 600 USDC → SOL (rebalancing,   base fee)
 //!   Phase 6  – Claim:     Carol collects all accrued USDC yield
 //!   Phase 7  – Yield:     Alice's and Bob's SOL yield is verified
-//!   Phase 8  – Unstake:   normal exit (vault healthy, no exit fee)
+//!   Phase 8  – Unstake:   normal exit (vault healthy, tiny exit fee)
 //!   Phase 9  – Unstake:   exit fee triggered (vault health < 50 %)
 
-use anchor_lang::prelude::Pubkey;
-use oxedium_program::components::{calculate_fee_amount, calculate_staker_yield, compute_swap_math};
+use anchor_lang::prelude::{AnchorDeserialize, Pubkey};
+use anchor_lang::{Discriminator, Event};
+use oxedium_program::components::{absorb_surplus, apply_vault_config, blend_entry_basis, calculate_fee_amount, calculate_staker_yield, check_action_cooldown, check_batch_amounts, check_deposit_cap, check_dust_sweepable, check_instant_unstake, check_max_impact, check_min_stake_age, check_min_out, check_min_stake, check_min_swap_output, check_solvency, check_staker_closable, check_vault_active, check_vault_closable, claimable_yield, compound_yield, compute_swap_math, credit_lp_or_protocol_yield, credit_stake, credit_lp_yield, execute_two_hop, exit_fee_bps, extend_unlock_ts, fees_setting, forfeit_dust, lockup_penalty_bps, merge_vault_config, preview_swap, queue_withdrawal, release_lp_yield, slippage_min_out, staking_event, unstake_amount_for_bps, seed_vault_liquidity, select_price, settle_swap, snapshot_yield, take_claimable_yield, take_protocol_yield, take_queued_withdrawal, update_staker_count, vault_config, vault_state_event, VaultConfigUpdate};
+use oxedium_program::events::{CollectEvent, SwapEvent, YieldCheckpointEvent};
 use oxedium_program::states::{Staker, Vault};
+use oxedium_program::utils::{OxediumError, DUST_SWEEP_GRACE_PERIOD, MAX_DUST_THRESHOLD};
 use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
 
+mod common;
+
 // Precision scale from utils.rs
 const SCALE: u128 = 1_000_000_000_000;
 
@@ -55,14 +60,7 @@ fn make_vault(base_fee_bps: u64, protocol_fee_bps: u64) -> Vault {
     Vault {
         base_fee_bps,
         protocol_fee_bps,
-        max_exit_fee_bps: 10_000,
-        token_mint: Pubkey::default(),
-        pyth_price_account: Pubkey::default(),
-        max_age_price: 0,
-        initial_balance: 0,
-        current_balance: 0,
-        cumulative_yield_per_lp: 0,
-        oxe_cumulative_yield_per_staker: 0,
+        ..common::vault()
     }
 }
 
@@ -72,7 +70,15 @@ fn make_staker() -> Staker {
         vault: Pubkey::default(),
         staked_amount: 0,
         last_cumulative_yield: 0,
+        avg_entry_cumulative_yield: 0,
         pending_claim: 0,
+        stake_unlock_ts: 0,
+        last_action_ts: 0,
+        rent_subsidized: false,
+        version: 1,
+        pending_unstake_amount: 0,
+        unstake_available_ts: 0,
+        subsidized_rent: 0,
     }
 }
 
@@ -80,14 +86,23 @@ fn make_staker() -> Staker {
 
 /// Mirrors staking.rs: snapshot yield, update staked amount and both vault balances.
 fn do_stake(staker: &mut Staker, vault: &mut Vault, amount: u64) {
-    let earned = calculate_staker_yield(
-        vault.cumulative_yield_per_lp,
+    do_stake_at(staker, vault, amount, 0);
+}
+
+/// `do_stake` at clock time `now`, for lock-up tests.
+fn do_stake_at(staker: &mut Staker, vault: &mut Vault, amount: u64, now: i64) {
+    check_action_cooldown(staker.last_action_ts, vault.action_cooldown, now).unwrap();
+    snapshot_yield(staker, vault.cumulative_yield_per_lp).expect("yield calc overflow");
+    staker.avg_entry_cumulative_yield = blend_entry_basis(
+        staker.avg_entry_cumulative_yield,
         staker.staked_amount,
-        staker.last_cumulative_yield,
-    ).expect("yield calc overflow");
-    staker.pending_claim += earned;
-    staker.last_cumulative_yield = vault.cumulative_yield_per_lp;
+        vault.cumulative_yield_per_lp,
+        amount,
+    ).unwrap();
+    update_staker_count(vault, staker.staked_amount, staker.staked_amount + amount).unwrap();
     staker.staked_amount += amount;
+    staker.stake_unlock_ts = extend_unlock_ts(vault, staker.stake_unlock_ts, now).unwrap();
+    staker.last_action_ts = now;
     vault.initial_balance += amount;
     vault.current_balance += amount;
 }
@@ -96,6 +111,7 @@ fn do_stake(staker: &mut Staker, vault: &mut Vault, amount: u64) {
 ///   vault_in.current  += amount_in
 ///   vault_out.current -= net_out
 ///   vault_out.cumulative_yield_per_lp += lp_fee * SCALE / initial_balance
+///   (or vault_out.protocol_yield += lp_fee with no LPs)
 fn do_swap(
     vault_in: &mut Vault,
     vault_out: &mut Vault,
@@ -118,10 +134,7 @@ fn do_swap(
 
     vault_in.current_balance += amount_in;
     vault_out.current_balance -= result.net_amount_out;
-    if vault_out.initial_balance > 0 {
-        vault_out.cumulative_yield_per_lp +=
-            (result.lp_fee_amount as u128 * SCALE) / vault_out.initial_balance as u128;
-    }
+    credit_lp_or_protocol_yield(vault_out, result.lp_fee_amount).unwrap();
 
     (
         result.swap_fee_bps,
@@ -134,17 +147,12 @@ fn do_swap(
 
 /// Mirrors claim.rs: snapshot yield into pending_claim, pay it out, reduce vault balance.
 fn do_claim(staker: &mut Staker, vault: &mut Vault) -> u64 {
-    let earned = calculate_staker_yield(
-        vault.cumulative_yield_per_lp,
-        staker.staked_amount,
-        staker.last_cumulative_yield,
-    ).expect("yield calc overflow");
-    staker.pending_claim += earned;
-    staker.last_cumulative_yield = vault.cumulative_yield_per_lp;
+    snapshot_yield(staker, vault.cumulative_yield_per_lp).expect("yield calc overflow");
 
     let payout = staker.pending_claim;
     staker.pending_claim = 0;
     vault.current_balance -= payout;
+    release_lp_yield(vault, payout);
     payout
 }
 
@@ -152,45 +160,45 @@ fn do_claim(staker: &mut Staker, vault: &mut Vault) -> u64 {
 /// reduce vault balances, redistribute exit fee into cumulative_yield_per_lp.
 /// Returns the amount the user actually receives (after exit fee).
 fn do_unstake(staker: &mut Staker, vault: &mut Vault, amount: u64) -> u64 {
+    do_unstake_at(staker, vault, amount, 0)
+}
+
+/// `do_unstake` at clock time `now`, for lock-up tests.
+fn do_unstake_at(staker: &mut Staker, vault: &mut Vault, amount: u64, now: i64) -> u64 {
+    check_instant_unstake(vault.withdraw_delay).unwrap();
+    settle_unstake_at(staker, vault, amount, now)
+}
+
+/// Settlement shared by `unstaking` and `request_unstake`: fees and balances,
+/// without the payout.
+fn settle_unstake_at(staker: &mut Staker, vault: &mut Vault, amount: u64, now: i64) -> u64 {
     assert!(staker.staked_amount >= amount, "insufficient stake");
+    check_action_cooldown(staker.last_action_ts, vault.action_cooldown, now).unwrap();
 
     // snapshot yield before balance changes
-    let earned = calculate_staker_yield(
-        vault.cumulative_yield_per_lp,
-        staker.staked_amount,
-        staker.last_cumulative_yield,
-    ).expect("yield calc overflow");
-    staker.pending_claim += earned;
-    staker.last_cumulative_yield = vault.cumulative_yield_per_lp;
+    snapshot_yield(staker, vault.cumulative_yield_per_lp).expect("yield calc overflow");
+    staker.last_action_ts = now;
 
     // quadratic exit fee curve on health deficit (mirrors unstaking.rs)
-    let health = if vault.initial_balance == 0 {
-        100u128
-    } else {
-        (vault.current_balance as u128 * 100) / vault.initial_balance as u128
-    };
-    let deficit = 100u128.saturating_sub(health);
-    let curved = deficit * deficit / 100;
-    let exit_fee_bps = (vault.max_exit_fee_bps as u128 * curved / 100) as u64;
+    let exit_fee_bps = exit_fee_bps(vault.health_bps(), vault.max_exit_fee_bps);
+    let total_fee_bps = exit_fee_bps + lockup_penalty_bps(vault, staker.stake_unlock_ts, now);
 
-    let unstake_amount = if exit_fee_bps > 0 {
-        calculate_fee_amount(amount, exit_fee_bps, 0)
+    let unstake_amount = if total_fee_bps > 0 {
+        calculate_fee_amount(amount, total_fee_bps, 0)
             .expect("exit fee calc failed")
             .0
     } else {
         amount
     };
 
+    update_staker_count(vault, staker.staked_amount, staker.staked_amount - amount).unwrap();
     staker.staked_amount -= amount;
     vault.initial_balance -= amount;
     vault.current_balance -= unstake_amount;
 
     // exit fee stays in vault, redistributed to remaining LP stakers
-    let exit_fee = amount - unstake_amount;
-    if exit_fee > 0 && vault.initial_balance > 0 {
-        vault.cumulative_yield_per_lp +=
-            (exit_fee as u128 * SCALE) / vault.initial_balance as u128;
-    }
+    // (or to protocol_yield when this was the last LP)
+    credit_lp_or_protocol_yield(vault, amount - unstake_amount).unwrap();
 
     unstake_amount
 }
@@ -411,18 +419,19 @@ fn simulate_sol_usdc_trading() {
     // Alice + Bob ≈ total lp_fee from the rebalancing swap (≤1 lamport rounding dust)
     assert!(lp4 - alice_yield - bob_yield <= 1);
 
-    // ── Phase 8: Alice unstakes 5 SOL (vault healthy, no exit fee) ──────────
+    // ── Phase 8: Alice unstakes 5 SOL (vault healthy, tiny exit fee) ─────────
 
     let usdc_before_unstake = usdc_vault.current_balance; // guard no USDC side-effects
 
     let alice_receives = do_unstake(&mut alice, &mut sol_vault, 5_000_000_000);
 
-    // Vault health before unstake: 106_152_000_000 / 110_000_000_000 ≈ 96 % — well above 50 %
-    // → no exit fee, Alice receives her full 5 SOL
-    assert_eq!(alice_receives, 5_000_000_000);
+    // Vault health before unstake: 106_152_000_000 / 110_000_000_000 = 9_650 bps
+    // deficit = 350, curved = 350² / 10_000 = 12 → exit_fee_bps = 10_000 × 12 / 10_000 = 12
+    // fee = 5_000_000_000 × 12 / 10_000 = 6_000_000, which stays in the vault
+    assert_eq!(alice_receives, 4_994_000_000);
     assert_eq!(alice.staked_amount, 95_000_000_000);
     assert_eq!(sol_vault.initial_balance, 105_000_000_000);
-    assert_eq!(sol_vault.current_balance, 101_152_000_000);
+    assert_eq!(sol_vault.current_balance, 101_158_000_000);
     assert_eq!(usdc_vault.current_balance, usdc_before_unstake); // USDC vault untouched
 
     // Pending yield was snapshotted before exit
@@ -440,14 +449,1812 @@ fn simulate_sol_usdc_trading() {
 
     let dave_receives = do_unstake(&mut dave, &mut distressed_vault, 1_000_000_000);
 
-    // health = 8_000_000_000 * 100 / 18_000_000_000 = 44
-    // deficit = 56, curved = 56² / 100 = 31
-    // exit_fee_bps = 10_000 * 31 / 100 = 3_100 bps
-    // fee = 1_000_000_000 * 3_100 / 10_000 = 310_000_000
-    // Dave receives: 1_000_000_000 − 310_000_000 = 690_000_000
-    assert_eq!(dave_receives, 690_000_000);
+    // health = 8_000_000_000 * 10_000 / 18_000_000_000 = 4_444 bps
+    // deficit = 5_556, curved = 5_556² / 10_000 = 3_086
+    // exit_fee_bps = 10_000 * 3_086 / 10_000 = 3_086 bps
+    // fee = 1_000_000_000 * 3_086 / 10_000 = 308_600_000
+    // Dave receives: 1_000_000_000 − 308_600_000 = 691_400_000
+    assert_eq!(dave_receives, 691_400_000);
 
     // initial_balance decreases by full amount; current_balance by net amount only
     assert_eq!(distressed_vault.initial_balance, 17_000_000_000);
-    assert_eq!(distressed_vault.current_balance, 7_310_000_000);
+    assert_eq!(distressed_vault.current_balance, 7_308_600_000);
+}
+
+#[test]
+fn updated_exit_fee_ceiling_applies_to_next_unstake() {
+    // init_vault with a 5% exit ceiling, then update_vault lowers it to 2%
+    let mut vault = make_vault(30, 5);
+    vault.max_protocol_fee_bps = 500;
+    vault.max_age_price = 60;
+    vault.max_exit_fee_bps = 500;
+    vault.initial_balance = 10_000_000_000;
+    vault.current_balance = 4_000_000_000; // 40 % health
+
+    // health = 4_000, deficit = 6_000, curved = 6_000² / 10_000 = 3_600
+    // exit_fee_bps = 500 × 3_600 / 10_000 = 180 bps
+    let fee_before = exit_fee_bps(vault.health_bps(), vault.max_exit_fee_bps);
+    assert_eq!(fee_before, 180);
+    let mut before = make_staker();
+    before.staked_amount = 1_000_000_000;
+    let mut vault_before = vault.clone();
+    assert_eq!(do_unstake(&mut before, &mut vault_before, 1_000_000_000), 982_000_000);
+
+    // A ceiling above 10% is rejected by update_vault and changes nothing
+    assert_eq!(do_update_max_exit_fee(&mut vault, 1_001).unwrap_err(), OxediumError::FeeExceeds.into());
+    assert_eq!(exit_fee_bps(vault.health_bps(), vault.max_exit_fee_bps), fee_before);
+
+    // update_vault(max_exit_fee_bps = 200)
+    do_update_max_exit_fee(&mut vault, 200).unwrap();
+
+    // exit_fee_bps = 200 × 3_600 / 10_000 = 72 bps → fee = 7_200_000
+    let fee_after = exit_fee_bps(vault.health_bps(), vault.max_exit_fee_bps);
+    assert_eq!(fee_after, 72);
+    assert!(fee_after < fee_before);
+    let mut after = make_staker();
+    after.staked_amount = 1_000_000_000;
+    assert_eq!(do_unstake(&mut after, &mut vault, 1_000_000_000), 992_800_000);
+}
+
+/// update_vault with only `max_exit_fee_bps` passed (mirrors update_vault.rs)
+fn do_update_max_exit_fee(vault: &mut Vault, max_exit_fee_bps: u64) -> anchor_lang::Result<()> {
+    let update = VaultConfigUpdate { max_exit_fee_bps: Some(max_exit_fee_bps), ..Default::default() };
+    let config = merge_vault_config(vault_config(vault), update);
+    apply_vault_config(vault, &config)
+}
+
+#[test]
+fn quote_matches_executed_swap() {
+    // quote_swap runs compute_swap_math on untouched vault state; the swap that
+    // follows must deliver exactly the quoted amounts.
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 110_000_000_000;
+    sol_vault.current_balance = 116_000_000_000;
+    usdc_vault.initial_balance = 18_000_000_000;
+    usdc_vault.current_balance = 16_923_780_000;
+
+    let quote = compute_swap_math(
+        10_000_000_000,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        SOL_DEC,
+        USDC_DEC,
+        &sol_vault,
+        &usdc_vault,
+    )
+    .unwrap();
+    let usdc_before = usdc_vault.current_balance;
+
+    let (fee_bps, raw, net, lp, proto) = do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        10_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+
+    assert_eq!(quote.swap_fee_bps, fee_bps);
+    assert_eq!(quote.raw_amount_out, raw);
+    assert_eq!(quote.net_amount_out, net);
+    assert_eq!(quote.lp_fee_amount, lp);
+    assert_eq!(quote.protocol_fee_amount, proto);
+    assert_eq!(usdc_before - usdc_vault.current_balance, quote.net_amount_out);
+}
+
+#[test]
+fn staker_closable_only_after_full_unstake_and_claim() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    let mut lp = make_staker();
+    let mut other_lp = make_staker();
+    do_stake(&mut lp, &mut usdc_vault, 18_000_000_000);
+    do_stake(&mut other_lp, &mut usdc_vault, 18_000_000_000);
+    sol_vault.initial_balance = 110_000_000_000;
+    sol_vault.current_balance = 110_000_000_000;
+
+    do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        10_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+
+    // Still staked, with yield accrued since the last snapshot
+    assert!(check_staker_closable(&lp, usdc_vault.cumulative_yield_per_lp).is_err());
+
+    let stake = lp.staked_amount;
+    do_unstake(&mut lp, &mut usdc_vault, stake);
+    // Fully unstaked, but the swap yield now sits in pending_claim
+    assert!(lp.pending_claim > 0);
+    assert!(check_staker_closable(&lp, usdc_vault.cumulative_yield_per_lp).is_err());
+
+    do_claim(&mut lp, &mut usdc_vault);
+    check_staker_closable(&lp, usdc_vault.cumulative_yield_per_lp).unwrap();
+}
+
+#[test]
+fn compound_matches_claim_then_restake() {
+    let setup = || {
+        let mut sol_vault = make_vault(30, 5);
+        let mut usdc_vault = make_vault(30, 5);
+        let mut lp = make_staker();
+        do_stake(&mut lp, &mut usdc_vault, 18_000_000_000);
+        sol_vault.initial_balance = 110_000_000_000;
+        sol_vault.current_balance = 110_000_000_000;
+        do_swap(
+            &mut sol_vault,
+            &mut usdc_vault,
+            10_000_000_000,
+            SOL_DEC,
+            USDC_DEC,
+            oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+            oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        );
+        (lp, usdc_vault)
+    };
+
+    let (mut manual_lp, mut manual_vault) = setup();
+    let claimed = do_claim(&mut manual_lp, &mut manual_vault);
+    do_stake(&mut manual_lp, &mut manual_vault, claimed);
+
+    let (mut lp, mut vault) = setup();
+    let compounded = compound_yield(&mut lp, &mut vault).unwrap();
+
+    assert!(compounded > 0);
+    assert_eq!(compounded, claimed);
+    assert_eq!(lp.staked_amount, manual_lp.staked_amount);
+    assert_eq!(lp.pending_claim, 0);
+    assert_eq!(lp.last_cumulative_yield, manual_lp.last_cumulative_yield);
+    assert_eq!(lp.avg_entry_cumulative_yield, manual_lp.avg_entry_cumulative_yield);
+    assert_eq!(vault.initial_balance, manual_vault.initial_balance);
+    assert_eq!(vault.current_balance, manual_vault.current_balance);
+}
+
+#[test]
+fn healthy_vault_unstake_clears_min_out() {
+    let mut vault = make_vault(30, 5);
+    vault.max_exit_fee_bps = 500;
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut vault, 10_000_000_000);
+
+    // 100 % health → no exit fee
+    let received = do_unstake(&mut lp, &mut vault, 1_000_000_000);
+    assert_eq!(received, 1_000_000_000);
+    assert!(check_min_out(received, 995_000_000).is_ok());
+}
+
+#[test]
+fn distressed_vault_unstake_below_min_out_reverts() {
+    // Simulated at full health, executed after the vault fell to 40 % health
+    let mut vault = make_vault(30, 5);
+    vault.max_exit_fee_bps = 500;
+    vault.initial_balance = 10_000_000_000;
+    vault.current_balance = 4_000_000_000;
+    let mut lp = make_staker();
+    lp.staked_amount = 1_000_000_000;
+
+    // 180 bps exit fee → 982_000_000
+    let received = do_unstake(&mut lp, &mut vault, 1_000_000_000);
+    assert_eq!(received, 982_000_000);
+    assert_eq!(
+        check_min_out(received, 995_000_000).unwrap_err(),
+        OxediumError::HighSlippage.into()
+    );
+}
+
+#[test]
+fn batch_claim_matches_sequential_claims() {
+    // Three vaults with different accrued yield; the third has none
+    let build = || {
+        let mut vaults = [make_vault(30, 5), make_vault(30, 5), make_vault(30, 5)];
+        let mut stakers = [make_staker(), make_staker(), make_staker()];
+        for (vault, staker) in vaults.iter_mut().zip(stakers.iter_mut()) {
+            do_stake(staker, vault, 10_000_000_000);
+        }
+        vaults[0].cumulative_yield_per_lp += 3 * SCALE / 1_000;
+        vaults[0].current_balance += 30_000_000;
+        vaults[1].cumulative_yield_per_lp += 7 * SCALE / 1_000;
+        vaults[1].current_balance += 70_000_000;
+        stakers[1].pending_claim = 5_000;
+        vaults[1].current_balance += 5_000;
+        (vaults, stakers)
+    };
+
+    let (mut seq_vaults, mut seq_stakers) = build();
+    let sequential: Vec<u64> = seq_vaults
+        .iter_mut()
+        .zip(seq_stakers.iter_mut())
+        .map(|(vault, staker)| do_claim(staker, vault))
+        .collect();
+
+    let (mut vaults, mut stakers) = build();
+    let batched: Vec<u64> = vaults
+        .iter_mut()
+        .zip(stakers.iter_mut())
+        .map(|(vault, staker)| take_claimable_yield(staker, vault).unwrap())
+        .collect();
+
+    assert_eq!(batched, sequential);
+    assert_eq!(batched, vec![30_000_000, 70_005_000, 0]);
+    for i in 0..3 {
+        assert_eq!(vaults[i].current_balance, seq_vaults[i].current_balance);
+        assert_eq!(stakers[i].pending_claim, 0);
+        assert_eq!(stakers[i].last_cumulative_yield, seq_stakers[i].last_cumulative_yield);
+    }
+}
+
+#[test]
+fn paused_output_vault_blocks_swaps_into_it() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    let mut usdt_vault = make_vault(30, 5);
+    for vault in [&mut sol_vault, &mut usdc_vault, &mut usdt_vault] {
+        vault.initial_balance = 100_000_000_000;
+        vault.current_balance = 100_000_000_000;
+    }
+
+    // set_vault_pause(usdc, true)
+    usdc_vault.paused = true;
+
+    // SOL → USDC: swap checks both sides and rejects the paused output
+    assert!(check_vault_active(&sol_vault).is_ok());
+    assert_eq!(
+        check_vault_active(&usdc_vault).unwrap_err(),
+        OxediumError::VaultPaused.into()
+    );
+
+    // SOL → USDT: the SOL input side is unaffected by the USDC pause
+    assert!(check_vault_active(&sol_vault).is_ok());
+    assert!(check_vault_active(&usdt_vault).is_ok());
+    let (_, _, net, _, _) = do_swap(
+        &mut sol_vault,
+        &mut usdt_vault,
+        1_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+    assert!(net > 0);
+
+    // Stakers in the paused vault can still exit
+    let mut lp = make_staker();
+    lp.staked_amount = 1_000_000_000;
+    assert_eq!(do_unstake(&mut lp, &mut usdc_vault, 1_000_000_000), 1_000_000_000);
+}
+
+#[test]
+fn get_claimable_matches_subsequent_claim() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 110_000_000_000;
+    sol_vault.current_balance = 110_000_000_000;
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut usdc_vault, 18_000_000_000);
+
+    let swap = |sol_vault: &mut Vault, usdc_vault: &mut Vault| {
+        do_swap(
+            sol_vault,
+            usdc_vault,
+            5_000_000_000,
+            SOL_DEC,
+            USDC_DEC,
+            oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+            oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        );
+    };
+
+    // Yield from the first swap is parked in pending_claim by the top-up,
+    // the second swap accrues on top of the new snapshot
+    swap(&mut sol_vault, &mut usdc_vault);
+    do_stake(&mut lp, &mut usdc_vault, 1_000_000_000);
+    assert!(lp.pending_claim > 0);
+    swap(&mut sol_vault, &mut usdc_vault);
+
+    let pending_before = lp.pending_claim;
+    let balance_before = usdc_vault.current_balance;
+    let claimable = claimable_yield(&lp, &usdc_vault).unwrap();
+
+    // get_claimable is read-only
+    assert_eq!(lp.pending_claim, pending_before);
+    assert_eq!(usdc_vault.current_balance, balance_before);
+
+    let claimed = do_claim(&mut lp, &mut usdc_vault);
+    assert!(claimable > pending_before);
+    assert_eq!(claimable, claimed);
+    assert_eq!(claimable_yield(&lp, &usdc_vault).unwrap(), 0);
+}
+
+#[test]
+fn normal_swap_clears_min_swap_output() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    usdc_vault.initial_balance = 18_000_000_000;
+    usdc_vault.current_balance = 18_000_000_000;
+    usdc_vault.min_swap_output = 100_000; // 0.10 USDC
+
+    // 1 SOL → ~$180 at base fee
+    let (_, _, net, _, _) = do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        1_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+    assert!(check_min_swap_output(net, usdc_vault.min_swap_output).is_ok());
+}
+
+#[test]
+fn fee_dominated_tiny_swap_is_below_min_swap_output() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 0);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    // USDC vault drained to 1 USDC of 18 000
+    usdc_vault.initial_balance = 18_000_000_000;
+    usdc_vault.current_balance = 1_000_000;
+    usdc_vault.min_swap_output = 100_000; // 0.10 USDC
+
+    // 0.0055 SOL → $0.99 raw, 99% utilization of what's left: the curve eats nearly all of it
+    let (fee_bps, raw, net, _, _) = do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        5_500_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+    assert_eq!(raw, 990_000);
+    assert!(fee_bps > 9_000);
+    assert!(net > 0);
+
+    assert_eq!(
+        check_min_swap_output(net, usdc_vault.min_swap_output).unwrap_err(),
+        OxediumError::OutputTooSmall.into()
+    );
+    // Disabled by default
+    assert!(check_min_swap_output(net, 0).is_ok());
+}
+
+#[test]
+fn staker_count_and_lifetime_yield_track_entries_and_exits() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    usdc_vault.max_exit_fee_bps = 1_000;
+    sol_vault.initial_balance = 110_000_000_000;
+    sol_vault.current_balance = 110_000_000_000;
+
+    let mut alice = make_staker();
+    let mut bob = make_staker();
+    let mut carol = make_staker();
+
+    do_stake(&mut alice, &mut usdc_vault, 9_000_000_000);
+    do_stake(&mut bob, &mut usdc_vault, 6_000_000_000);
+    assert_eq!(usdc_vault.total_stakers, 2);
+
+    // Top-ups don't count as new stakers
+    do_stake(&mut alice, &mut usdc_vault, 1_000_000_000);
+    assert_eq!(usdc_vault.total_stakers, 2);
+
+    do_stake(&mut carol, &mut usdc_vault, 2_000_000_000);
+    assert_eq!(usdc_vault.total_stakers, 3);
+
+    let (_, _, _, lp_fee_1, _) = do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        20_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+    assert_eq!(usdc_vault.lifetime_yield_distributed, lp_fee_1);
+
+    // Partial exit keeps Bob counted
+    do_unstake(&mut bob, &mut usdc_vault, 1_000_000_000);
+    assert_eq!(usdc_vault.total_stakers, 3);
+
+    // Full exit under stress: Bob leaves the count and his exit fee is distributed
+    let before = usdc_vault.lifetime_yield_distributed;
+    let received = do_unstake(&mut bob, &mut usdc_vault, 5_000_000_000);
+    let exit_fee = 5_000_000_000 - received;
+    assert!(exit_fee > 0);
+    assert_eq!(usdc_vault.total_stakers, 2);
+    assert_eq!(usdc_vault.lifetime_yield_distributed, before + exit_fee);
+
+    // Claiming and closing an already-empty position doesn't touch the count
+    do_claim(&mut bob, &mut usdc_vault);
+    assert!(check_staker_closable(&bob, usdc_vault.cumulative_yield_per_lp).is_ok());
+    assert_eq!(usdc_vault.total_stakers, 2);
+
+    // Re-entry after a full exit counts again
+    do_stake(&mut bob, &mut usdc_vault, 500_000_000);
+    assert_eq!(usdc_vault.total_stakers, 3);
+
+    // Compounding into a non-empty stake doesn't change the count
+    compound_yield(&mut carol, &mut usdc_vault).unwrap();
+    assert_eq!(usdc_vault.total_stakers, 3);
+
+    // Traders bring the USDC back so everyone can leave
+    do_swap(
+        &mut usdc_vault,
+        &mut sol_vault,
+        4_000_000_000,
+        USDC_DEC,
+        SOL_DEC,
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+    );
+
+    do_unstake(&mut alice, &mut usdc_vault, 10_000_000_000);
+    do_unstake(&mut bob, &mut usdc_vault, 500_000_000);
+    let carol_stake = carol.staked_amount;
+    do_unstake(&mut carol, &mut usdc_vault, carol_stake);
+    assert_eq!(usdc_vault.total_stakers, 0);
+
+    // A pre-counter position exiting can't underflow the count
+    update_staker_count(&mut usdc_vault, 1_000, 0).unwrap();
+    assert_eq!(usdc_vault.total_stakers, 0);
+
+    // With no LP principal left, fees aren't credited or counted
+    let before = usdc_vault.lifetime_yield_distributed;
+    credit_lp_yield(&mut usdc_vault, 1_000).unwrap();
+    assert_eq!(usdc_vault.lifetime_yield_distributed, before);
+}
+
+#[test]
+fn entry_basis_is_deposit_weighted() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 0);
+    sol_vault.initial_balance = 110_000_000_000;
+    sol_vault.current_balance = 110_000_000_000;
+    let mut lp = make_staker();
+
+    do_stake(&mut lp, &mut usdc_vault, 9_000_000_000);
+    let first_entry = usdc_vault.cumulative_yield_per_lp;
+    assert_eq!(lp.avg_entry_cumulative_yield, first_entry);
+
+    do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        10_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+    let second_entry = usdc_vault.cumulative_yield_per_lp;
+    assert!(second_entry > first_entry);
+
+    // Top up with a third of the original stake
+    do_stake(&mut lp, &mut usdc_vault, 3_000_000_000);
+    let expected = (first_entry * 9_000_000_000 + second_entry * 3_000_000_000) / 12_000_000_000;
+    assert_eq!(lp.avg_entry_cumulative_yield, expected);
+    assert!(lp.avg_entry_cumulative_yield > first_entry);
+    assert!(lp.avg_entry_cumulative_yield < second_entry);
+
+    // Payout snapshot still tracks the latest accumulator, not the basis
+    assert_eq!(lp.last_cumulative_yield, second_entry);
+}
+
+fn make_locked_vault() -> Vault {
+    let mut vault = make_vault(30, 0);
+    vault.lockup_active = true;
+    vault.lockup_duration = 1_000;
+    vault.max_lockup_penalty_bps = 500; // 5% with the full lock remaining
+    vault
+}
+
+#[test]
+fn unstake_after_unlock_pays_no_lockup_penalty() {
+    let mut vault = make_locked_vault();
+    let mut lp = make_staker();
+    let mut other_lp = make_staker();
+    do_stake_at(&mut other_lp, &mut vault, 10_000_000_000, 0);
+    do_stake_at(&mut lp, &mut vault, 1_000_000_000, 0);
+    assert_eq!(lp.stake_unlock_ts, 1_000);
+
+    let received = do_unstake_at(&mut lp, &mut vault, 1_000_000_000, 1_000);
+    assert_eq!(received, 1_000_000_000);
+    assert_eq!(vault.cumulative_yield_per_lp, 0);
+}
+
+#[test]
+fn mid_lock_unstake_pays_partial_penalty_to_lps() {
+    let mut vault = make_locked_vault();
+    let mut lp = make_staker();
+    let mut other_lp = make_staker();
+    do_stake_at(&mut other_lp, &mut vault, 10_000_000_000, 0);
+    do_stake_at(&mut lp, &mut vault, 1_000_000_000, 0);
+
+    // 40% of the lock left → 2% penalty
+    let received = do_unstake_at(&mut lp, &mut vault, 1_000_000_000, 600);
+    assert_eq!(received, 980_000_000);
+
+    // The penalty is credited to the remaining LP like the exit fee
+    assert!(vault.cumulative_yield_per_lp > 0);
+    assert_eq!(do_claim(&mut other_lp, &mut vault), 20_000_000);
+}
+
+#[test]
+fn lockup_penalty_stacks_on_health_exit_fee() {
+    let mut vault = make_locked_vault();
+    vault.max_exit_fee_bps = 1_000;
+    let mut lp = make_staker();
+    do_stake_at(&mut lp, &mut vault, 10_000_000_000, 0);
+
+    // Health 50% → 25% deficit curve → 250 bps; half the lock left → 250 bps
+    vault.current_balance = 5_000_000_000;
+    let received = do_unstake_at(&mut lp, &mut vault, 1_000_000_000, 500);
+    assert_eq!(received, 950_000_000);
+}
+
+#[test]
+fn top_up_extends_the_lock() {
+    let mut vault = make_locked_vault();
+    let mut lp = make_staker();
+    do_stake_at(&mut lp, &mut vault, 1_000_000_000, 0);
+    assert_eq!(lp.stake_unlock_ts, 1_000);
+
+    // Top-up at t=800 re-locks the whole position until 1 800
+    do_stake_at(&mut lp, &mut vault, 1_000_000_000, 800);
+    assert_eq!(lp.stake_unlock_ts, 1_800);
+
+    // At t=1 000 the original lock would have expired; now 80% of it remains
+    let received = do_unstake_at(&mut lp, &mut vault, 1_000_000_000, 1_000);
+    assert_eq!(received, 960_000_000);
+
+    // A shortened duration never pulls an existing lock earlier
+    vault.lockup_duration = 100;
+    do_stake_at(&mut lp, &mut vault, 1_000_000_000, 1_000);
+    assert_eq!(lp.stake_unlock_ts, 1_800);
+}
+
+#[test]
+fn inactive_lockup_neither_locks_nor_penalizes() {
+    let mut vault = make_locked_vault();
+    vault.lockup_active = false;
+    let mut lp = make_staker();
+    do_stake_at(&mut lp, &mut vault, 1_000_000_000, 0);
+    assert_eq!(lp.stake_unlock_ts, 0);
+
+    // Even a position locked earlier exits penalty-free once the flag is off
+    lp.stake_unlock_ts = 1_000;
+    assert_eq!(do_unstake_at(&mut lp, &mut vault, 1_000_000_000, 0), 1_000_000_000);
+}
+
+#[test]
+fn swap_event_carries_decimals_and_post_swap_balances() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut usdc_vault, 18_000_000_000);
+    sol_vault.initial_balance = 110_000_000_000;
+    sol_vault.current_balance = 110_000_000_000;
+
+    let (fee_bps, _, net_out, lp_fee, protocol_fee) = do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        1_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+
+    // Built the way swap() emits it, after both balance updates
+    let event = SwapEvent {
+        user: Pubkey::new_unique(),
+        fee_bps,
+        token_in: sol_vault.token_mint,
+        token_out: usdc_vault.token_mint,
+        requested_amount_in: 1_000_000_000,
+        amount_in: 1_000_000_000,
+        amount_out: net_out,
+        price_in: SOL_PRICE as u64,
+        price_out: USDC_PRICE as u64,
+        lp_fee,
+        protocol_fee,
+        referral_fee: 0,
+        price_impact_bps: 0,
+        decimals_in: SOL_DEC,
+        decimals_out: USDC_DEC,
+        vault_in_balance_after: sol_vault.current_balance,
+        vault_out_balance_after: usdc_vault.current_balance,
+        base_fee_bps: fee_bps,
+        imbalance_fee_bps: 0,
+        liquidity_impact_bps: 0,
+        oracle_conf_bps: 0,
+        timestamp: 0,
+        slot: 0,
+    };
+
+    let data = event.data();
+    assert_eq!(&data[..8], SwapEvent::DISCRIMINATOR);
+    let decoded = SwapEvent::try_from_slice(&data[8..]).unwrap();
+
+    assert_eq!(decoded.decimals_in, SOL_DEC);
+    assert_eq!(decoded.decimals_out, USDC_DEC);
+    assert_eq!(decoded.vault_in_balance_after, 111_000_000_000);
+    assert_eq!(decoded.vault_in_balance_after, sol_vault.current_balance);
+    assert_eq!(decoded.vault_out_balance_after, 18_000_000_000 - net_out);
+    assert_eq!(decoded.vault_out_balance_after, usdc_vault.current_balance);
+}
+
+#[test]
+fn fee_accounting_stays_within_current_balance() {
+    // Swap fees and exit fees are both left in the vault ATA and credited through
+    // cumulative_yield_per_lp, so current_balance must keep matching the tokens
+    // physically held and must always cover every LP's claimable yield.
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 110_000_000_000;
+    sol_vault.current_balance = 110_000_000_000;
+    let mut alice = make_staker();
+    let mut bob = make_staker();
+
+    // Tokens physically in the USDC vault ATA
+    let mut ata: u64 = 0;
+    let check = |vault: &Vault, ata: u64, stakers: [&Staker; 2]| {
+        assert_eq!(vault.current_balance, ata);
+        let claimable: u64 = stakers.iter().map(|s| claimable_yield(s, vault).unwrap()).sum();
+        assert!(claimable <= vault.current_balance);
+    };
+
+    do_stake(&mut alice, &mut usdc_vault, 12_000_000_000);
+    do_stake(&mut bob, &mut usdc_vault, 6_000_000_000);
+    ata += 18_000_000_000;
+    check(&usdc_vault, ata, [&alice, &bob]);
+
+    for amount_in in [10_000_000_000, 20_000_000_000, 25_000_000_000] {
+        let (_, _, net_out, _, _) = do_swap(
+            &mut sol_vault,
+            &mut usdc_vault,
+            amount_in,
+            SOL_DEC,
+            USDC_DEC,
+            oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+            oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        );
+        ata -= net_out;
+        check(&usdc_vault, ata, [&alice, &bob]);
+    }
+
+    // Below 50% health: this unstake pays an exit fee that stays in the ATA
+    assert!(usdc_vault.current_balance * 2 < usdc_vault.initial_balance);
+    let received = do_unstake(&mut bob, &mut usdc_vault, 3_000_000_000);
+    assert!(received < 3_000_000_000);
+    ata -= received;
+    check(&usdc_vault, ata, [&alice, &bob]);
+
+    let (_, _, net_out, _, _) = do_swap(
+        &mut usdc_vault,
+        &mut sol_vault,
+        1_000_000_000,
+        USDC_DEC,
+        SOL_DEC,
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+    );
+    assert!(net_out > 0);
+    ata += 1_000_000_000;
+    check(&usdc_vault, ata, [&alice, &bob]);
+
+    let received = do_unstake(&mut alice, &mut usdc_vault, 4_000_000_000);
+    ata -= received;
+    check(&usdc_vault, ata, [&alice, &bob]);
+
+    // Everything owed can be paid out of what the vault holds
+    ata -= do_claim(&mut alice, &mut usdc_vault);
+    ata -= do_claim(&mut bob, &mut usdc_vault);
+    check(&usdc_vault, ata, [&alice, &bob]);
+}
+
+#[test]
+fn exit_fee_is_claimable_by_remaining_lps() {
+    let mut vault = make_vault(30, 5);
+    let mut leaver = make_staker();
+    let mut stayer = make_staker();
+    do_stake(&mut leaver, &mut vault, 2_000_000_000);
+    do_stake(&mut stayer, &mut vault, 10_000_000_000);
+
+    // 40% health: deficit 6 000 → curved 3 600 → 3 600 bps of the 10 000 max
+    vault.current_balance = 4_800_000_000;
+    let claimable_before = claimable_yield(&stayer, &vault).unwrap();
+
+    let received = do_unstake(&mut leaver, &mut vault, 2_000_000_000);
+    let exit_fee = 2_000_000_000 - received;
+    assert_eq!(exit_fee, 720_000_000);
+
+    // The whole fee went to the only remaining LP, none of it to the protocol
+    assert_eq!(claimable_yield(&stayer, &vault).unwrap(), claimable_before + exit_fee);
+    assert_eq!(vault.lifetime_yield_distributed, exit_fee);
+}
+
+#[test]
+fn last_lp_exit_fee_goes_to_protocol_yield_and_is_collectable() {
+    // A last LP can't leave a vault below 100% health in full: the payout
+    // (1 − fee) always exceeds what the vault holds. The lock-up penalty is
+    // health-independent, so it's how a last LP's exit fee arises in practice.
+    let mut vault = make_locked_vault();
+    let mut lp = make_staker();
+    do_stake_at(&mut lp, &mut vault, 10_000_000_000, 0);
+
+    // Half the lock left → 2.5% penalty
+    let received = do_unstake_at(&mut lp, &mut vault, 10_000_000_000, 500);
+    assert_eq!(received, 9_750_000_000);
+    assert_eq!(vault.initial_balance, 0);
+
+    // No LP left to credit: the fee is booked for the admin, not LP yield
+    assert_eq!(vault.protocol_yield, 250_000_000);
+    assert_eq!(vault.cumulative_yield_per_lp, 0);
+    assert_eq!(vault.current_balance, 250_000_000);
+
+    // collect drains exactly protocol_yield
+    assert_eq!(take_protocol_yield(&mut vault).unwrap(), 250_000_000);
+    assert_eq!(vault.protocol_yield, 0);
+    assert_eq!(vault.current_balance, 0);
+    assert_eq!(take_protocol_yield(&mut vault).unwrap(), 0);
+}
+
+#[test]
+fn exit_fee_goes_to_lps_while_any_remain() {
+    let mut vault = make_vault(30, 5);
+    let mut leaver = make_staker();
+    let mut stayer = make_staker();
+    do_stake(&mut leaver, &mut vault, 2_000_000_000);
+    do_stake(&mut stayer, &mut vault, 10_000_000_000);
+    vault.current_balance = 4_800_000_000;
+
+    do_unstake(&mut leaver, &mut vault, 2_000_000_000);
+    assert_eq!(vault.protocol_yield, 0);
+    assert!(vault.cumulative_yield_per_lp > 0);
+}
+
+#[test]
+fn collect_rejects_protocol_yield_above_current_balance() {
+    let mut vault = make_vault(30, 5);
+    vault.protocol_yield = 1_000;
+    vault.current_balance = 999;
+    assert!(matches!(
+        take_protocol_yield(&mut vault),
+        Err(OxediumError::ProtocolYieldExceedsBalance)
+    ));
+    assert_eq!(vault.protocol_yield, 1_000);
+    assert_eq!(vault.current_balance, 999);
+}
+
+// ─── LP yield invariants ─────────────────────────────────────────────────────
+
+/// Stakes, swaps one way until the output vault is heavily drawn down, stakes
+/// again at the new balance, swaps some more, then has everyone claim. Returns
+/// (sum of claims, sum of LP fees credited).
+fn run_one_way_flow(first_stakes: &[u64], late_stakes: &[u64], swaps: &[u64]) -> (u64, u64) {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 1_000_000_000_000;
+    sol_vault.current_balance = 1_000_000_000_000;
+
+    let mut stakers: Vec<Staker> = Vec::new();
+    for &amount in first_stakes {
+        let mut staker = make_staker();
+        do_stake(&mut staker, &mut usdc_vault, amount);
+        stakers.push(staker);
+    }
+
+    let mut lp_fees: u64 = 0;
+    let (early, late) = swaps.split_at(swaps.len() / 2);
+    let mut swap_sol_in = |usdc_vault: &mut Vault, amount_in: u64| {
+        let (_, _, _, lp_fee, _) = do_swap(
+            &mut sol_vault,
+            usdc_vault,
+            amount_in,
+            SOL_DEC,
+            USDC_DEC,
+            oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+            oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        );
+        lp_fees += lp_fee;
+    };
+
+    for &amount_in in early {
+        swap_sol_in(&mut usdc_vault, amount_in);
+    }
+    for &amount in late_stakes {
+        let mut staker = make_staker();
+        do_stake(&mut staker, &mut usdc_vault, amount);
+        stakers.push(staker);
+    }
+    for &amount_in in late {
+        swap_sol_in(&mut usdc_vault, amount_in);
+    }
+
+    let claimed: u64 = stakers.iter_mut().map(|s| do_claim(s, &mut usdc_vault)).sum();
+    (claimed, lp_fees)
+}
+
+#[test]
+fn claims_never_exceed_lp_fees_credited() {
+    let cases: [(&[u64], &[u64], &[u64]); 4] = [
+        // Balanced entry, one late staker after a heavy drawdown
+        (&[9_000_000_000, 9_000_000_000], &[18_000_000_000], &[20_000_000_000, 30_000_000_000, 10_000_000_000, 10_000_000_000]),
+        // Late staker far larger than the early pool
+        (&[1_000_000_000], &[50_000_000_000], &[3_000_000_000, 2_000_000_000]),
+        // Odd amounts so every division rounds
+        (&[1_234_567_891, 7_777_777_777, 3], &[999_999_999, 1], &[1_111_111_111, 3_333_333_333, 2_222_222_222, 777_777_777]),
+        // Many small swaps
+        (&[10_000_000_000], &[5_000_000_000, 5_000_000_000], &[100_000_000; 40]),
+    ];
+
+    for (first, late, swaps) in cases {
+        let (claimed, lp_fees) = run_one_way_flow(first, late, swaps);
+        assert!(lp_fees > 0);
+        assert!(claimed <= lp_fees, "claimed {claimed} > credited {lp_fees}");
+        // Only flooring dust is lost, at most one unit per staker per credit
+        let stakers = (first.len() + late.len()) as u64;
+        assert!(lp_fees - claimed <= stakers * swaps.len() as u64);
+    }
+}
+
+#[test]
+fn late_staker_earns_nothing_from_fees_before_entry() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 1_000_000_000_000;
+    sol_vault.current_balance = 1_000_000_000_000;
+    let mut early = make_staker();
+    do_stake(&mut early, &mut usdc_vault, 18_000_000_000);
+
+    let (_, _, _, lp_fee, _) = do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        40_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+
+    // Entering while current_balance < initial_balance doesn't dilute the early LP
+    let mut late = make_staker();
+    do_stake(&mut late, &mut usdc_vault, 18_000_000_000);
+    assert_eq!(claimable_yield(&late, &usdc_vault).unwrap(), 0);
+    assert!(lp_fee - claimable_yield(&early, &usdc_vault).unwrap() <= 1);
+}
+
+#[test]
+fn lp_fee_without_lps_is_booked_to_protocol_yield() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 110_000_000_000;
+    sol_vault.current_balance = 110_000_000_000;
+    // Liquidity seeded without any LP principal
+    usdc_vault.current_balance = 18_000_000_000;
+
+    let (_, _, net_out, lp_fee, _) = do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        1_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+    assert!(lp_fee > 0);
+    assert_eq!(usdc_vault.protocol_yield, lp_fee);
+    assert_eq!(usdc_vault.cumulative_yield_per_lp, 0);
+    assert_eq!(usdc_vault.lifetime_yield_distributed, 0);
+
+    // LPs joining later don't inherit it; the admin collects it
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut usdc_vault, 1_000_000_000);
+    assert_eq!(claimable_yield(&lp, &usdc_vault).unwrap(), 0);
+    assert_eq!(take_protocol_yield(&mut usdc_vault).unwrap(), lp_fee);
+    assert_eq!(usdc_vault.current_balance, 18_000_000_000 - net_out - lp_fee + 1_000_000_000);
+}
+
+#[test]
+fn small_fees_on_high_decimal_vault_are_not_lost() {
+    // 18-decimal tokens at $1 each: 10 tokens of LP principal is 1e19 base
+    // units, so a 30 bps fee on a 1e9-unit swap is 3e6 and
+    // 3e6 × SCALE / 1e19 = 0.3 would floor to nothing on its own
+    const DEC: u8 = 18;
+    let mut vault_in = make_vault(30, 0);
+    let mut vault_out = make_vault(30, 0);
+    vault_in.initial_balance = 10_000_000_000_000_000_000;
+    vault_in.current_balance = 10_000_000_000_000_000_000;
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut vault_out, 10_000_000_000_000_000_000);
+
+    let mut lp_fees = 0;
+    for _ in 0..1_000 {
+        let (_, _, _, lp_fee, _) = do_swap(
+            &mut vault_in,
+            &mut vault_out,
+            1_000_000_000,
+            DEC,
+            DEC,
+            oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+            oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        );
+        assert!((lp_fee as u128) * SCALE < vault_out.initial_balance as u128);
+        lp_fees += lp_fee;
+    }
+
+    assert_eq!(lp_fees, 3_000_000_000);
+    let claimable = claimable_yield(&lp, &vault_out).unwrap();
+    assert!(lp_fees - claimable <= 1, "lost {} of {lp_fees}", lp_fees - claimable);
+}
+
+#[test]
+fn small_exit_fees_on_large_vault_are_not_lost() {
+    // The stayer's 1e19 principal makes each 2.5e6 exit fee worth 0.25 of an
+    // accumulator step, which would floor to nothing without the carry
+    let mut vault = make_locked_vault();
+    vault.current_balance = 0;
+    let mut stayer = make_staker();
+    do_stake_at(&mut stayer, &mut vault, 10_000_000_000_000_000_000, 0);
+
+    let mut exit_fees = 0;
+    for _ in 0..1_000 {
+        let mut leaver = make_staker();
+        do_stake_at(&mut leaver, &mut vault, 100_000_000, 0);
+        // Half the lock left → 2.5% penalty
+        let received = do_unstake_at(&mut leaver, &mut vault, 100_000_000, 500);
+        exit_fees += 100_000_000 - received;
+    }
+
+    assert_eq!(exit_fees, 2_500_000_000);
+    assert_eq!(vault.protocol_yield, 0);
+    let claimable = claimable_yield(&stayer, &vault).unwrap();
+    assert!(exit_fees - claimable <= 1, "lost {} of {exit_fees}", exit_fees - claimable);
+}
+
+#[test]
+fn low_utilization_swap_passes_any_impact_bound() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    usdc_vault.initial_balance = 18_000_000_000;
+    usdc_vault.current_balance = 18_000_000_000;
+
+    // 1 SOL out of an 18 000 USDC vault: 1% utilization, under the threshold
+    let result = compute_swap_math(
+        1_000_000_000,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        SOL_DEC,
+        USDC_DEC,
+        &sol_vault,
+        &usdc_vault,
+    )
+    .unwrap();
+    assert_eq!(result.swap_fee_bps, result.base_fee_bps);
+    assert!(check_max_impact(result.swap_fee_bps, result.base_fee_bps, 0).is_ok());
+}
+
+#[test]
+fn high_utilization_swap_exceeds_tight_impact_bound() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    usdc_vault.initial_balance = 18_000_000_000;
+    usdc_vault.current_balance = 18_000_000_000;
+
+    // 50 SOL → $9 000, half the USDC vault: the curve adds ~20% on top of base
+    let result = compute_swap_math(
+        50_000_000_000,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        SOL_DEC,
+        USDC_DEC,
+        &sol_vault,
+        &usdc_vault,
+    )
+    .unwrap();
+    let impact_bps = result.swap_fee_bps - result.base_fee_bps;
+    assert!(impact_bps > 1_900, "impact {impact_bps}");
+
+    // A min_out tolerating 25% off the oracle price would still accept it
+    assert!(check_min_out(result.net_amount_out, result.raw_amount_out * 75 / 100).is_ok());
+    // but a 2% impact bound rejects it
+    assert_eq!(
+        check_max_impact(result.swap_fee_bps, result.base_fee_bps, 200).unwrap_err(),
+        OxediumError::ImpactTooHigh.into()
+    );
+    assert!(check_max_impact(result.swap_fee_bps, result.base_fee_bps, 10_000).is_ok());
+}
+
+#[test]
+fn collect_all_drains_each_vault_and_logs_nonzero_sweeps() {
+    // Mirrors collect_all: one settlement per vault group, zero-yield vaults skipped
+    let mut vaults = [make_vault(30, 5), make_vault(30, 5), make_vault(30, 5), make_vault(30, 5)];
+    let yields = [250_000_000, 0, 1_000, 42_000_000];
+    for (vault, protocol_yield) in vaults.iter_mut().zip(yields) {
+        vault.current_balance = 5_000_000_000 + protocol_yield;
+        vault.protocol_yield = protocol_yield;
+    }
+    let mints: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let vault_keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let collector = Pubkey::new_unique();
+    let recipient = Pubkey::new_unique();
+
+    let mut events = Vec::new();
+    for ((vault, mint), vault_key) in vaults.iter_mut().zip(&mints).zip(&vault_keys) {
+        let amount = take_protocol_yield(vault).unwrap();
+        if amount == 0 {
+            continue;
+        }
+        let event = CollectEvent {
+            admin: collector,
+            mint: *mint,
+            vault: *vault_key,
+            recipient,
+            amount,
+            vault_balance_after: vault.current_balance,
+            timestamp: 0,
+            slot: 0,
+        };
+        events.push(CollectEvent::try_from_slice(&event.data()[8..]).unwrap());
+    }
+
+    for vault in &vaults {
+        assert_eq!(vault.protocol_yield, 0);
+        assert_eq!(vault.current_balance, 5_000_000_000);
+    }
+    let logged: Vec<(Pubkey, u64)> = events.iter().map(|e| (e.mint, e.amount)).collect();
+    assert_eq!(logged, vec![(mints[0], 250_000_000), (mints[2], 1_000), (mints[3], 42_000_000)]);
+    // Each sweep is attributed to its vault and to the signing collector
+    let attributed: Vec<Pubkey> = events.iter().map(|e| e.vault).collect();
+    assert_eq!(attributed, vec![vault_keys[0], vault_keys[2], vault_keys[3]]);
+    assert!(events.iter().all(|e| e.admin == collector));
+}
+
+// ETH: $3 000.00, Pyth exponent -8
+const ETH_PRICE: i64 = 300_000_000_000;
+const ETH_EXP: i32 = -8;
+const ETH_DEC: u8 = 8;
+
+/// publish_time of `oracle`; with `max_age_price` 0 the swap must run at it
+const NOW: i64 = 1_700_000_000;
+
+/// Mirrors swap.rs end to end (no referrer, no transfer fees)
+#[allow(clippy::too_many_arguments)]
+fn do_settled_swap(
+    vault_in: &mut Vault,
+    vault_out: &mut Vault,
+    amount_in: u64,
+    decimals_in: u8,
+    decimals_out: u8,
+    oracle_in: PriceFeedMessage,
+    oracle_out: PriceFeedMessage,
+    total_oxe_staked: u64,
+) -> anchor_lang::Result<u64> {
+    let result = preview_swap(amount_in, oracle_in, oracle_out, decimals_in, decimals_out, vault_in, vault_out, NOW)?;
+    check_min_swap_output(result.net_amount_out, vault_out.min_swap_output)?;
+    let price_in = select_price(oracle_in, vault_in.use_ema_price).price as u64;
+    let price_out = select_price(oracle_out, vault_out.use_ema_price).price as u64;
+    settle_swap(vault_in, vault_out, &result, result.protocol_fee_amount, 0, price_in, price_out, total_oxe_staked, NOW)?;
+    check_solvency(vault_out)?;
+    vault_in.current_balance += amount_in;
+    Ok(result.net_amount_out)
+}
+
+#[test]
+fn two_hop_matches_two_sequential_swaps() {
+    let build = || {
+        let mut sol_vault = make_vault(30, 5);
+        let mut usdc_vault = make_vault(30, 5);
+        let mut eth_vault = make_vault(30, 5);
+        sol_vault.initial_balance = 100_000_000_000;
+        sol_vault.current_balance = 100_000_000_000;
+        usdc_vault.initial_balance = 18_000_000_000;
+        usdc_vault.current_balance = 18_000_000_000;
+        eth_vault.initial_balance = 300_000_000;
+        eth_vault.current_balance = 300_000_000;
+        [sol_vault, usdc_vault, eth_vault]
+    };
+    let sol = oracle(SOL_PRICE, SOL_CONF, SOL_EXP);
+    let usdc = oracle(USDC_PRICE, USDC_CONF, USDC_EXP);
+    let eth = oracle(ETH_PRICE, 0, ETH_EXP);
+    // 20 SOL → $3 600, a fifth of the USDC vault: leg 1 runs up the impact
+    // curve and leaves the mid vault unhealthy for leg 2's pricing
+    let amount_in = 20_000_000_000;
+
+    let [mut sol_a, mut usdc_a, mut eth_a] = build();
+    let mid = do_settled_swap(&mut sol_a, &mut usdc_a, amount_in, SOL_DEC, USDC_DEC, sol, usdc, 1_000).unwrap();
+    let sequential_out = do_settled_swap(&mut usdc_a, &mut eth_a, mid, USDC_DEC, ETH_DEC, usdc, eth, 1_000).unwrap();
+
+    let [mut sol_b, mut usdc_b, mut eth_b] = build();
+    let (leg_in, leg_out) = execute_two_hop(
+        amount_in, sol, usdc, eth, SOL_DEC, USDC_DEC, ETH_DEC, &mut sol_b, &mut usdc_b, &mut eth_b, 1_000, NOW,
+    )
+    .unwrap();
+    sol_b.current_balance += amount_in;
+
+    assert_eq!(leg_in.net_amount_out, mid);
+    assert_eq!(leg_out.net_amount_out, sequential_out);
+    assert!(leg_in.swap_fee_bps > 30, "leg 1 should be past the impact threshold");
+    for (a, b) in [(&sol_a, &sol_b), (&usdc_a, &usdc_b), (&eth_a, &eth_b)] {
+        assert_eq!(a.current_balance, b.current_balance);
+        assert_eq!(a.cumulative_yield_per_lp, b.cumulative_yield_per_lp);
+        assert_eq!(a.yield_remainder, b.yield_remainder);
+        assert_eq!(a.oxe_cumulative_yield_per_staker, b.oxe_cumulative_yield_per_staker);
+        assert_eq!(a.swap_count, b.swap_count);
+        assert_eq!(a.price_cumulative, b.price_cumulative);
+    }
+    // Both legs recorded on the mid vault
+    assert_eq!(usdc_b.swap_count, 2);
+}
+
+#[test]
+fn second_staking_event_reports_the_combined_position() {
+    let mut vault = make_vault(30, 5);
+    let mut other = make_staker();
+    let mut lp = make_staker();
+    do_stake(&mut other, &mut vault, 6_000_000_000);
+
+    do_stake(&mut lp, &mut vault, 2_000_000_000);
+    let first = staking_event(&lp, &vault, 2_000_000_000, NOW, 1);
+    assert_eq!((first.user, first.mint, first.amount), (lp.owner, vault.token_mint, 2_000_000_000));
+    assert_eq!((first.staked_amount, first.share_bps), (2_000_000_000, 2_500)); // 2 of 8
+    assert_eq!(first.entry_cumulative_yield, 0);
+
+    credit_lp_yield(&mut vault, 80_000_000).unwrap();
+    do_stake(&mut lp, &mut vault, 2_000_000_000);
+    let second = staking_event(&lp, &vault, 2_000_000_000, NOW, 2);
+    assert_eq!((second.timestamp, second.slot), (NOW, 2));
+    assert_eq!((second.staked_amount, second.share_bps), (4_000_000_000, 4_000)); // 4 of 10
+    assert_eq!(second.entry_cumulative_yield, vault.cumulative_yield_per_lp);
+}
+
+#[test]
+fn reconcile_books_out_of_band_tokens_as_collectable_protocol_yield() {
+    let mut vault = make_vault(30, 5);
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut vault, 10_000_000_000);
+    credit_lp_yield(&mut vault, 50_000_000).unwrap();
+    vault.current_balance += 50_000_000;
+    let claimable_before = claimable_yield(&lp, &vault).unwrap();
+
+    // Someone transfers 7 tokens straight to the vault ATA
+    let vault_ata_amount = vault.current_balance + 7_000_000;
+    assert_eq!(absorb_surplus(&mut vault, vault_ata_amount).unwrap(), 7_000_000);
+    assert_eq!(vault.current_balance, vault_ata_amount);
+    assert_eq!(vault.protocol_yield, 7_000_000);
+
+    // LP principal and yield are untouched, and collect drains exactly the surplus
+    assert_eq!(vault.initial_balance, 10_000_000_000);
+    assert_eq!(claimable_yield(&lp, &vault).unwrap(), claimable_before);
+    assert_eq!(take_protocol_yield(&mut vault).unwrap(), 7_000_000);
+    assert_eq!(vault.current_balance, 10_050_000_000);
+
+    // Already in sync: nothing to book
+    assert_eq!(absorb_surplus(&mut vault, 10_050_000_000).unwrap(), 0);
+}
+
+#[test]
+fn reconcile_refuses_a_vault_ata_below_current_balance() {
+    let mut vault = make_vault(30, 5);
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut vault, 10_000_000_000);
+
+    assert!(matches!(absorb_surplus(&mut vault, 9_999_999_999), Err(OxediumError::VaultBalanceDeficit)));
+    assert_eq!(vault.current_balance, 10_000_000_000);
+    assert_eq!(vault.protocol_yield, 0);
+}
+
+#[test]
+fn swap_that_would_pay_out_unclaimed_yield_reverts() {
+    let mut sol_vault = make_vault(30, 0);
+    let mut usdc_vault = make_vault(30, 0);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut usdc_vault, 18_000_000_000);
+    let sol = oracle(SOL_PRICE, SOL_CONF, SOL_EXP);
+    let usdc = oracle(USDC_PRICE, USDC_CONF, USDC_EXP);
+
+    // 50 SOL → $9 000: the LP fee stays in the vault, owed to the LP
+    do_settled_swap(&mut sol_vault, &mut usdc_vault, 50_000_000_000, SOL_DEC, USDC_DEC, sol, usdc, 0).unwrap();
+    let owed = usdc_vault.unclaimed_lp_yield;
+    assert!(owed > 0);
+    assert!(claimable_yield(&lp, &usdc_vault).unwrap() <= owed);
+    let free = usdc_vault.current_balance - owed;
+
+    // Paying out more than the unreserved balance would dip into the LP's yield
+    let mut breach_in = sol_vault.clone();
+    let mut breach_out = usdc_vault.clone();
+    let err = do_settled_swap(&mut breach_in, &mut breach_out, 55_000_000_000, SOL_DEC, USDC_DEC, sol, usdc, 0).unwrap_err();
+    assert_eq!(err, OxediumError::SolvencyViolation.into());
+
+    // A smaller swap keeps the reserve intact
+    let out = do_settled_swap(&mut sol_vault, &mut usdc_vault, 5_000_000_000, SOL_DEC, USDC_DEC, sol, usdc, 0).unwrap();
+    assert!(out < free);
+    assert!(check_solvency(&usdc_vault).is_ok());
+
+    // Claiming releases the reserve along with the tokens
+    let paid = do_claim(&mut lp, &mut usdc_vault);
+    assert!(paid > owed);
+    assert!(usdc_vault.unclaimed_lp_yield <= 1);
+    assert!(check_solvency(&usdc_vault).is_ok());
+}
+
+#[test]
+fn slippage_bps_bound_matches_equivalent_minimum_out() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    usdc_vault.initial_balance = 18_000_000_000;
+    usdc_vault.current_balance = 18_000_000_000;
+    let sol = oracle(SOL_PRICE, SOL_CONF, SOL_EXP);
+    let usdc = oracle(USDC_PRICE, USDC_CONF, USDC_EXP);
+
+    // 1 SOL → $180 on a deep vault: base fee plus protocol fee only
+    let result = compute_swap_math(1_000_000_000, sol, usdc, SOL_DEC, USDC_DEC, &sol_vault, &usdc_vault).unwrap();
+    assert_eq!(result.raw_amount_out, 180_000_000);
+
+    // swap_with_slippage_bps(50) applies the same bound as swap(minimum_out = raw × 99.5%)
+    let minimum_out = 179_100_000;
+    assert_eq!(slippage_min_out(result.raw_amount_out, 50), minimum_out);
+    assert!(check_min_out(result.net_amount_out, minimum_out).is_ok());
+    assert!(check_min_out(result.net_amount_out, slippage_min_out(result.raw_amount_out, 50)).is_ok());
+
+    // Just under the fee, both reject
+    let fee_bps = (result.raw_amount_out - result.net_amount_out) * 10_000 / result.raw_amount_out;
+    let tight_bps = fee_bps - 1;
+    let tight_minimum_out = slippage_min_out(result.raw_amount_out, tight_bps);
+    assert!(result.net_amount_out < tight_minimum_out);
+    assert_eq!(check_min_out(result.net_amount_out, tight_minimum_out).unwrap_err(), OxediumError::HighSlippage.into());
+}
+
+#[test]
+fn curve_heavy_swap_reverts_under_tight_slippage_bps() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    usdc_vault.initial_balance = 18_000_000_000;
+    usdc_vault.current_balance = 18_000_000_000;
+    let sol = oracle(SOL_PRICE, SOL_CONF, SOL_EXP);
+    let usdc = oracle(USDC_PRICE, USDC_CONF, USDC_EXP);
+
+    // 50 SOL → $9 000, half the USDC vault: deep into the impact curve
+    let result = compute_swap_math(50_000_000_000, sol, usdc, SOL_DEC, USDC_DEC, &sol_vault, &usdc_vault).unwrap();
+    assert!(result.swap_fee_bps > 100);
+
+    let bound = slippage_min_out(result.raw_amount_out, 100);
+    assert_eq!(check_min_out(result.net_amount_out, bound).unwrap_err(), OxediumError::HighSlippage.into());
+
+    // A bound wide enough for the curve lets it through
+    assert!(check_min_out(result.net_amount_out, slippage_min_out(result.raw_amount_out, 5_000)).is_ok());
+}
+
+#[test]
+fn swap_event_breaks_a_curve_heavy_fee_down_by_source() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    // USDC vault already 20 % below principal: the imbalance curve applies
+    usdc_vault.initial_balance = 18_000_000_000;
+    usdc_vault.current_balance = 14_400_000_000;
+    let sol = oracle(SOL_PRICE, SOL_CONF, SOL_EXP);
+    let usdc = oracle(USDC_PRICE, USDC_CONF, USDC_EXP);
+
+    // 40 SOL → $7 200, half the USDC vault: deep into the impact curve
+    let result = compute_swap_math(40_000_000_000, sol, usdc, SOL_DEC, USDC_DEC, &sol_vault, &usdc_vault).unwrap();
+
+    // Built the way swap() emits it
+    let event = SwapEvent {
+        user: Pubkey::new_unique(),
+        fee_bps: result.swap_fee_bps,
+        token_in: sol_vault.token_mint,
+        token_out: usdc_vault.token_mint,
+        requested_amount_in: 40_000_000_000,
+        amount_in: 40_000_000_000,
+        amount_out: result.net_amount_out,
+        price_in: SOL_PRICE as u64,
+        price_out: USDC_PRICE as u64,
+        lp_fee: result.lp_fee_amount,
+        protocol_fee: result.protocol_fee_amount,
+        referral_fee: 0,
+        price_impact_bps: result.price_impact_bps,
+        decimals_in: SOL_DEC,
+        decimals_out: USDC_DEC,
+        vault_in_balance_after: sol_vault.current_balance,
+        vault_out_balance_after: usdc_vault.current_balance,
+        base_fee_bps: result.vault_base_fee_bps,
+        imbalance_fee_bps: result.imbalance_fee_bps,
+        liquidity_impact_bps: result.liquidity_impact_bps,
+        oracle_conf_bps: result.oracle_conf_bps,
+        timestamp: 0,
+        slot: 0,
+    };
+    let decoded = SwapEvent::try_from_slice(&event.data()[8..]).unwrap();
+
+    assert_eq!(decoded.base_fee_bps, 30);
+    assert!(decoded.imbalance_fee_bps > 0);
+    assert!(decoded.liquidity_impact_bps > decoded.imbalance_fee_bps);
+    assert_eq!(decoded.oracle_conf_bps, 0);
+    assert_eq!(
+        decoded.base_fee_bps + decoded.imbalance_fee_bps + decoded.liquidity_impact_bps + decoded.oracle_conf_bps,
+        decoded.fee_bps
+    );
+    assert_eq!(decoded.fee_bps, result.swap_fee_bps);
+}
+
+#[test]
+fn unstake_bps_withdraws_a_share_of_the_current_position() {
+    let mut vault = make_vault(30, 5);
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut vault, 10_000_000_000);
+
+    // unstake_bps(5_000): half of whatever is staked at execution
+    let half = unstake_amount_for_bps(lp.staked_amount, 5_000).unwrap();
+    assert_eq!(half, 5_000_000_000);
+    let received = do_unstake(&mut lp, &mut vault, half);
+    assert_eq!(received, half);
+    assert_eq!(lp.staked_amount, 5_000_000_000);
+
+    // unstake_bps(10_000): the rest, leaving the position empty
+    let rest = unstake_amount_for_bps(lp.staked_amount, 10_000).unwrap();
+    assert_eq!(rest, 5_000_000_000);
+    do_unstake(&mut lp, &mut vault, rest);
+    assert_eq!(lp.staked_amount, 0);
+    assert_eq!(vault.initial_balance, 0);
+    assert_eq!(vault.total_stakers, 0);
+}
+
+#[test]
+fn action_cooldown_blocks_back_to_back_stake_and_unstake() {
+    let mut vault = make_vault(30, 5);
+    vault.action_cooldown = 60;
+    let mut lp = make_staker();
+
+    do_stake_at(&mut lp, &mut vault, 10_000_000_000, 1_000);
+    assert_eq!(lp.last_action_ts, 1_000);
+
+    // Same block, and one second short of the cooldown: unstaking reverts
+    for now in [1_000, 1_059] {
+        assert_eq!(
+            check_action_cooldown(lp.last_action_ts, vault.action_cooldown, now).unwrap_err(),
+            OxediumError::CooldownActive.into()
+        );
+    }
+
+    // Once it elapses the position can leave, and the exit restarts the cooldown
+    let received = do_unstake_at(&mut lp, &mut vault, 4_000_000_000, 1_060);
+    assert_eq!(received, 4_000_000_000);
+    assert_eq!(lp.last_action_ts, 1_060);
+    assert!(check_action_cooldown(lp.last_action_ts, vault.action_cooldown, 1_100).is_err());
+}
+
+#[test]
+fn min_stake_age_blocks_claiming_fees_from_a_swap_right_after_staking() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    usdc_vault.min_stake_age = 300;
+    let mut lp = make_staker();
+
+    // Stake just ahead of a large swap that credits LP fees in the same slot
+    do_stake_at(&mut lp, &mut usdc_vault, 18_000_000_000, NOW);
+    do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        10_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+    assert!(claimable_yield(&lp, &usdc_vault).unwrap() > 0);
+
+    // claim / compound at the same timestamp revert
+    assert_eq!(
+        check_min_stake_age(lp.last_action_ts, usdc_vault.min_stake_age, NOW).unwrap_err(),
+        OxediumError::StakeTooYoung.into()
+    );
+
+    // Once the position has aged, the yield can be claimed
+    assert!(check_min_stake_age(lp.last_action_ts, usdc_vault.min_stake_age, NOW + 300).is_ok());
+    assert!(do_claim(&mut lp, &mut usdc_vault) > 0);
+}
+
+#[test]
+fn vault_state_event_reports_metrics_after_a_swap() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    let mut sol_lp = make_staker();
+    let mut usdc_lp = make_staker();
+    do_stake(&mut sol_lp, &mut sol_vault, 100_000_000_000);
+    do_stake(&mut usdc_lp, &mut usdc_vault, 18_000_000_000);
+
+    let (_, _, _, lp_fee, _) = do_swap(
+        &mut sol_vault,
+        &mut usdc_vault,
+        10_000_000_000,
+        SOL_DEC,
+        USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+
+    let state = vault_state_event(&usdc_vault);
+    assert_eq!(state.initial_balance, 18_000_000_000);
+    assert_eq!(state.current_balance, usdc_vault.current_balance);
+    assert!(state.current_balance < state.initial_balance);
+    assert_eq!(
+        state.health_bps,
+        (state.current_balance as u128 * 10_000 / state.initial_balance as u128) as u64
+    );
+    assert_eq!(state.protocol_yield, usdc_vault.protocol_yield);
+    assert_eq!(state.cumulative_yield_per_lp, lp_fee as u128 * SCALE / 18_000_000_000);
+    assert_eq!((state.base_fee_bps, state.max_exit_fee_bps), (30, 10_000));
+
+    // The input vault gained tokens: health above 10_000, no fees booked
+    let state = vault_state_event(&sol_vault);
+    assert!(state.health_bps > 10_000);
+    assert_eq!((state.protocol_yield, state.cumulative_yield_per_lp), (0, 0));
+}
+
+/// A position that fully unstaked with 5 units of yield left in `pending_claim`
+fn drained_with_dust(vault: &mut Vault, stayer: &mut Staker) -> Staker {
+    let mut leaver = make_staker();
+    do_stake(stayer, vault, 9_000_000_000);
+    do_stake(&mut leaver, vault, 1_000_000_000);
+    credit_lp_yield(vault, 50).unwrap();
+    do_unstake_at(&mut leaver, vault, 1_000_000_000, NOW);
+    assert_eq!((leaver.staked_amount, leaver.pending_claim), (0, 5));
+    leaver
+}
+
+#[test]
+fn sweep_dust_forfeits_a_drained_position_to_active_lps() {
+    let mut vault = make_vault(30, 5);
+    let mut stayer = make_staker();
+    let mut leaver = drained_with_dust(&mut vault, &mut stayer);
+    let before = claimable_yield(&stayer, &vault).unwrap();
+
+    check_dust_sweepable(&leaver, 10, NOW + DUST_SWEEP_GRACE_PERIOD).unwrap();
+    assert_eq!(forfeit_dust(&mut leaver, &mut vault).unwrap(), 5);
+
+    // The dust moves from the leaver's claim to the remaining LP; the vault
+    // still reserves the same total
+    assert_eq!(leaver.pending_claim, 0);
+    assert!(claimable_yield(&stayer, &vault).unwrap() > before);
+    assert_eq!(vault.unclaimed_lp_yield, 50);
+    assert_eq!(vault.protocol_yield, 0);
+}
+
+#[test]
+fn sweep_dust_leaves_positions_above_threshold_or_recently_active_alone() {
+    let mut vault = make_vault(30, 5);
+    let mut stayer = make_staker();
+    let leaver = drained_with_dust(&mut vault, &mut stayer);
+    let later = NOW + DUST_SWEEP_GRACE_PERIOD;
+
+    // pending_claim must be strictly below the threshold
+    assert_eq!(check_dust_sweepable(&leaver, 5, later).unwrap_err(), OxediumError::StakerNotDust.into());
+    // Active stakes are never dust
+    assert_eq!(check_dust_sweepable(&stayer, MAX_DUST_THRESHOLD, later).unwrap_err(), OxediumError::StakerNotDust.into());
+    // The owner has the whole grace period to claim or close it themselves
+    assert_eq!(
+        check_dust_sweepable(&leaver, 10, later - 1).unwrap_err(),
+        OxediumError::SweepGracePeriodActive.into()
+    );
+    // The threshold itself is capped, so a real claim can never be swept
+    assert_eq!(
+        check_dust_sweepable(&leaver, MAX_DUST_THRESHOLD + 1, later).unwrap_err(),
+        OxediumError::InvalidDustThreshold.into()
+    );
+}
+
+/// Snapshots `staker` the way every staker instruction now does first and
+/// returns the `YieldCheckpointEvent` it emits
+fn checkpoint(staker: &mut Staker, vault: &Vault) -> YieldCheckpointEvent {
+    let earned = snapshot_yield(staker, vault.cumulative_yield_per_lp).unwrap();
+    let event = YieldCheckpointEvent {
+        user: staker.owner,
+        vault: Pubkey::default(),
+        earned_since_last: earned,
+        new_pending_claim: staker.pending_claim,
+        cumulative_yield_per_lp: vault.cumulative_yield_per_lp,
+    };
+    YieldCheckpointEvent::try_from_slice(&event.data()[8..]).unwrap()
+}
+
+#[test]
+fn yield_checkpoints_sum_to_the_claimed_amount() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    let mut other = make_staker();
+    let mut lp = make_staker();
+    do_stake(&mut other, &mut usdc_vault, 9_000_000_000);
+    let swap = |sol_vault: &mut Vault, usdc_vault: &mut Vault| {
+        do_swap(
+            sol_vault,
+            usdc_vault,
+            1_000_000_000,
+            SOL_DEC,
+            USDC_DEC,
+            oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+            oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        );
+    };
+
+    let mut checkpoints = Vec::new();
+    checkpoints.push(checkpoint(&mut lp, &usdc_vault));
+    do_stake(&mut lp, &mut usdc_vault, 9_000_000_000);
+    swap(&mut sol_vault, &mut usdc_vault);
+    checkpoints.push(checkpoint(&mut lp, &usdc_vault));
+    do_stake(&mut lp, &mut usdc_vault, 9_000_000_000);
+    swap(&mut sol_vault, &mut usdc_vault);
+    checkpoints.push(checkpoint(&mut lp, &usdc_vault));
+    do_unstake(&mut lp, &mut usdc_vault, 18_000_000_000);
+    checkpoints.push(checkpoint(&mut lp, &usdc_vault));
+    let claimed = do_claim(&mut lp, &mut usdc_vault);
+
+    let earned: Vec<u64> = checkpoints.iter().map(|c| c.earned_since_last).collect();
+    assert_eq!(earned[0], 0);
+    assert!(earned[1] > 0 && earned[2] > 0);
+    // Nothing staked after the unstake, so the claim snapshot adds nothing
+    assert_eq!(earned[3], 0);
+    assert!(claimed > 0);
+    assert_eq!(earned.iter().sum::<u64>(), claimed);
+    assert_eq!(checkpoints[3].new_pending_claim, claimed);
+    assert_eq!(checkpoints[3].cumulative_yield_per_lp, usdc_vault.cumulative_yield_per_lp);
+}
+
+/// Mirrors request_unstake.rs: settle like `do_unstake_at`, but move the
+/// payout into `reserved_for_withdrawals` instead of paying it.
+fn do_request_unstake_at(staker: &mut Staker, vault: &mut Vault, amount: u64, now: i64) -> u64 {
+    let payout = settle_unstake_at(staker, vault, amount, now);
+    queue_withdrawal(staker, vault, payout, now).unwrap();
+    payout
+}
+
+#[test]
+fn queued_unstake_reserves_its_payout_until_the_delay_passes() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    sol_vault.initial_balance = 100_000_000_000;
+    sol_vault.current_balance = 100_000_000_000;
+    usdc_vault.withdraw_delay = 3_600;
+    let mut whale = make_staker();
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut usdc_vault, 2_000_000_000);
+    do_stake(&mut whale, &mut usdc_vault, 16_000_000_000);
+
+    // The queue can't be skipped: unstaking reverts before touching the position
+    assert_eq!(check_instant_unstake(usdc_vault.withdraw_delay).unwrap_err(), OxediumError::WithdrawQueueRequired.into());
+
+    let payout = do_request_unstake_at(&mut whale, &mut usdc_vault, 16_000_000_000, NOW);
+    assert_eq!(payout, 16_000_000_000); // healthy vault, no exit fee
+    assert_eq!((whale.staked_amount, whale.pending_unstake_amount), (0, payout));
+    assert_eq!(whale.unstake_available_ts, NOW + 3_600);
+    // Still in the ATA, but no longer liquidity swaps can take
+    assert_eq!(usdc_vault.reserved_for_withdrawals, payout);
+    assert_eq!((usdc_vault.initial_balance, usdc_vault.current_balance), (2_000_000_000, 2_000_000_000));
+
+    // A swap in the meantime only sees the unreserved balance
+    let result = preview_swap(
+        20_000_000_000,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP),
+        oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+        SOL_DEC,
+        USDC_DEC,
+        &sol_vault,
+        &usdc_vault,
+        NOW,
+    );
+    assert!(result.is_err());
+
+    // Only one request at a time
+    let mut topped_up = whale.clone();
+    topped_up.staked_amount = 1;
+    assert!(matches!(
+        queue_withdrawal(&mut topped_up, &mut usdc_vault.clone(), 1, NOW),
+        Err(OxediumError::WithdrawAlreadyQueued)
+    ));
+
+    // Early finalize reverts and changes nothing
+    assert!(matches!(
+        take_queued_withdrawal(&mut whale, &mut usdc_vault, NOW + 3_599),
+        Err(OxediumError::WithdrawNotReady)
+    ));
+    assert_eq!(whale.pending_unstake_amount, payout);
+
+    assert_eq!(take_queued_withdrawal(&mut whale, &mut usdc_vault, NOW + 3_600).unwrap(), payout);
+    assert_eq!((whale.pending_unstake_amount, whale.unstake_available_ts), (0, 0));
+    assert_eq!(usdc_vault.reserved_for_withdrawals, 0);
+    assert!(check_staker_closable(&whale, usdc_vault.cumulative_yield_per_lp).is_ok());
+
+    // Nothing left to finalize
+    assert!(matches!(
+        take_queued_withdrawal(&mut whale, &mut usdc_vault, NOW + 3_600),
+        Err(OxediumError::NoWithdrawQueued)
+    ));
+}
+
+#[test]
+fn reconcile_does_not_book_queued_payouts_as_surplus() {
+    let mut vault = make_vault(30, 5);
+    let mut lp = make_staker();
+    let mut leaver = make_staker();
+    do_stake(&mut lp, &mut vault, 6_000_000_000);
+    do_stake(&mut leaver, &mut vault, 4_000_000_000);
+    let payout = do_request_unstake_at(&mut leaver, &mut vault, 4_000_000_000, NOW);
+
+    // The ATA still holds the queued payout
+    let ata = vault.current_balance + payout;
+    assert_eq!(absorb_surplus(&mut vault, ata).unwrap(), 0);
+    assert!(check_staker_closable(&leaver, vault.cumulative_yield_per_lp).is_err());
+    assert_eq!(absorb_surplus(&mut vault, ata + 5).unwrap(), 5);
+    assert_eq!(vault.protocol_yield, 5);
+    assert!(matches!(absorb_surplus(&mut vault, ata), Err(OxediumError::VaultBalanceDeficit)));
+}
+
+#[test]
+fn seeding_a_distressed_vault_lowers_exit_and_swap_fees() {
+    let mut sol_vault = make_vault(30, 5);
+    let mut usdc_vault = make_vault(30, 5);
+    let mut sol_lp = make_staker();
+    let mut usdc_lp = make_staker();
+    do_stake(&mut sol_lp, &mut sol_vault, 1_000_000_000_000);
+    do_stake(&mut usdc_lp, &mut usdc_vault, 36_000_000_000);
+
+    // 100 SOL → USDC drains about 40 % of the USDC vault
+    do_swap(
+        &mut sol_vault, &mut usdc_vault, 100_000_000_000, SOL_DEC, USDC_DEC,
+        oracle(SOL_PRICE, SOL_CONF, SOL_EXP), oracle(USDC_PRICE, USDC_CONF, USDC_EXP),
+    );
+    let health_before = usdc_vault.health_bps();
+    let exit_fee_before = exit_fee_bps(health_before, usdc_vault.max_exit_fee_bps);
+    let swap_fee_before = fees_setting(&sol_vault, &usdc_vault);
+    assert!(health_before < 7_000);
+    assert!(swap_fee_before > usdc_vault.base_fee_bps);
+
+    let initial_before = usdc_vault.initial_balance;
+    let shortfall = usdc_vault.initial_balance - usdc_vault.current_balance;
+    assert_eq!(seed_vault_liquidity(&mut usdc_vault, shortfall).unwrap(), initial_before);
+
+    // No LP claim minted: principal and the existing position are unchanged
+    assert_eq!(usdc_vault.initial_balance, initial_before);
+    assert_eq!(usdc_lp.staked_amount, 36_000_000_000);
+    assert_eq!(usdc_vault.health_bps(), 10_000);
+    assert!(exit_fee_bps(usdc_vault.health_bps(), usdc_vault.max_exit_fee_bps) < exit_fee_before);
+    assert!(fees_setting(&sol_vault, &usdc_vault) < swap_fee_before);
+
+    // The LP now exits the whole position with no exit fee
+    let payout = do_unstake(&mut usdc_lp, &mut usdc_vault, 36_000_000_000);
+    assert_eq!(payout, 36_000_000_000);
+}
+
+// ─── Closing a vault ─────────────────────────────────────────────────────────
+
+#[test]
+fn fully_exited_vault_is_closable() {
+    let mut usdc_vault = make_vault(30, 5);
+    check_vault_closable(&usdc_vault).unwrap();
+
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut usdc_vault, 36_000_000_000);
+    assert_eq!(do_unstake(&mut lp, &mut usdc_vault, 36_000_000_000), 36_000_000_000);
+    assert_eq!((usdc_vault.initial_balance, usdc_vault.current_balance, usdc_vault.total_stakers), (0, 0, 0));
+    check_vault_closable(&usdc_vault).unwrap();
+
+    // Dust up to dust_floor is swept on close, anything above is not dust
+    usdc_vault.dust_floor = 1_000;
+    usdc_vault.current_balance = 1_000;
+    check_vault_closable(&usdc_vault).unwrap();
+    usdc_vault.current_balance = 1_001;
+    assert_eq!(check_vault_closable(&usdc_vault).unwrap_err(), OxediumError::VaultNotEmpty.into());
+}
+
+#[test]
+fn vault_with_live_lps_or_unpaid_yield_is_not_closable() {
+    let mut usdc_vault = make_vault(30, 5);
+    let mut lp = make_staker();
+    do_stake(&mut lp, &mut usdc_vault, 36_000_000_000);
+
+    // Residual principal alone blocks closing, whatever the balance says
+    let mut residual = usdc_vault.clone();
+    residual.current_balance = 0;
+    residual.total_stakers = 0;
+    assert_eq!(check_vault_closable(&residual).unwrap_err(), OxediumError::VaultNotEmpty.into());
+
+    // Each remaining obligation blocks it on its own
+    let mut drained = make_vault(30, 5);
+    drained.total_stakers = 1;
+    assert!(check_vault_closable(&drained).is_err());
+    let mut drained = make_vault(30, 5);
+    drained.reserved_for_withdrawals = 1;
+    assert!(check_vault_closable(&drained).is_err());
+    let mut drained = make_vault(30, 5);
+    drained.unclaimed_lp_yield = 1;
+    assert!(check_vault_closable(&drained).is_err());
+
+    // The last LP's lock-up penalty is owed to the fee collector until collected
+    let mut vault = make_locked_vault();
+    let mut leaver = make_staker();
+    do_stake_at(&mut leaver, &mut vault, 10_000_000_000, 0);
+    do_unstake_at(&mut leaver, &mut vault, 10_000_000_000, 500);
+    assert_eq!(vault.initial_balance, 0);
+    assert_eq!(vault.protocol_yield, 250_000_000);
+    assert_eq!(check_vault_closable(&vault).unwrap_err(), OxediumError::VaultNotEmpty.into());
+
+    take_protocol_yield(&mut vault).unwrap();
+    check_vault_closable(&vault).unwrap();
+}
+
+// ─── Batched staking ─────────────────────────────────────────────────────────
+
+/// Mirrors stake_many.rs: one `[token_mint, vault_pda, signer_ata, staker_pda,
+/// vault_ata]` group per vault, each settled like `staking`
+fn do_stake_many(groups: &mut [(&mut Vault, &mut Staker)], amounts: &[u64], now: i64) -> anchor_lang::Result<()> {
+    check_batch_amounts(groups.len() * 5, 5, amounts.len())?;
+    for ((vault, staker), &amount) in groups.iter_mut().zip(amounts) {
+        check_action_cooldown(staker.last_action_ts, vault.action_cooldown, now)?;
+        check_min_stake(staker.staked_amount, amount, vault.min_stake_amount)?;
+        credit_stake(staker, vault, amount, now)?;
+        check_deposit_cap(vault.initial_balance, vault.max_total_deposits)?;
+    }
+    Ok(())
+}
+
+#[test]
+fn stake_many_seeds_three_vaults_like_separate_stakes() {
+    let (mut sol_vault, mut usdc_vault, mut eth_vault) = (make_vault(30, 5), make_vault(30, 5), make_vault(30, 5));
+    let (mut sol_pol, mut usdc_pol, mut eth_pol) = (make_staker(), make_staker(), make_staker());
+    let amounts = [1_000_000_000_000, 36_000_000_000, 50_000_000_000];
+
+    do_stake_many(
+        &mut [(&mut sol_vault, &mut sol_pol), (&mut usdc_vault, &mut usdc_pol), (&mut eth_vault, &mut eth_pol)],
+        &amounts,
+        NOW,
+    ).unwrap();
+
+    for ((vault, staker), amount) in [(&sol_vault, &sol_pol), (&usdc_vault, &usdc_pol), (&eth_vault, &eth_pol)].into_iter().zip(amounts) {
+        assert_eq!((vault.initial_balance, vault.current_balance, vault.total_stakers), (amount, amount, 1));
+        assert_eq!((staker.staked_amount, staker.pending_claim, staker.last_action_ts), (amount, 0, NOW));
+    }
+
+    // Same records as three separate `staking` calls
+    let mut single_vault = make_vault(30, 5);
+    let mut single = make_staker();
+    do_stake_at(&mut single, &mut single_vault, 36_000_000_000, NOW);
+    assert_eq!(single_vault.initial_balance, usdc_vault.initial_balance);
+    assert_eq!(single.staked_amount, usdc_pol.staked_amount);
+    assert_eq!(single.avg_entry_cumulative_yield, usdc_pol.avg_entry_cumulative_yield);
+}
+
+#[test]
+fn stake_many_needs_one_amount_per_vault() {
+    let (mut sol_vault, mut usdc_vault) = (make_vault(30, 5), make_vault(30, 5));
+    let (mut sol_pol, mut usdc_pol) = (make_staker(), make_staker());
+
+    let result = do_stake_many(&mut [(&mut sol_vault, &mut sol_pol), (&mut usdc_vault, &mut usdc_pol)], &[1_000_000_000], NOW);
+    assert_eq!(result.unwrap_err(), OxediumError::BatchLengthMismatch.into());
+    assert_eq!(check_batch_amounts(9, 5, 2).unwrap_err(), OxediumError::InvalidVault.into());
+    assert_eq!(check_batch_amounts(0, 5, 0).unwrap_err(), OxediumError::InvalidVault.into());
+    assert_eq!((sol_vault.initial_balance, usdc_vault.initial_balance), (0, 0));
 }
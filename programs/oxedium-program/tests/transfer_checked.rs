@@ -0,0 +1,194 @@
+//! Decimal validation on token transfers.
+//!
+//! Every vault transfer goes through `transfer_checked` with the mint's
+//! `decimals`. There is no program-test harness here, so these tests run the
+//! token program processors in-process against the same instruction our CPIs
+//! build, and check that a wrong `decimals` never moves funds.
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+
+const DECIMALS: u8 = 6;
+
+struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+impl TestAccount {
+    fn info(&mut self, is_signer: bool) -> AccountInfo<'_> {
+        AccountInfo::new(&self.key, is_signer, true, &mut self.lamports, &mut self.data, &self.owner, false, 0)
+    }
+}
+
+/// Mint, source, destination and authority for a `program`-owned transfer
+fn setup(program: Pubkey, mint_data: Vec<u8>, source_data: Vec<u8>, dest_data: Vec<u8>) -> [TestAccount; 4] {
+    [
+        TestAccount { key: Pubkey::new_unique(), owner: program, lamports: 1, data: mint_data },
+        TestAccount { key: Pubkey::new_unique(), owner: program, lamports: 1, data: source_data },
+        TestAccount { key: Pubkey::new_unique(), owner: program, lamports: 1, data: dest_data },
+        TestAccount { key: Pubkey::new_unique(), owner: Pubkey::default(), lamports: 1, data: vec![] },
+    ]
+}
+
+fn spl_token_account(authority: &Pubkey, mint: &Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0u8; spl_token::state::Account::LEN];
+    let account = spl_token::state::Account {
+        mint: *mint,
+        owner: *authority,
+        amount,
+        state: spl_token::state::AccountState::Initialized,
+        ..Default::default()
+    };
+    spl_token::state::Account::pack(account, &mut data).unwrap();
+    data
+}
+
+fn spl_token_mint() -> Vec<u8> {
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    let mint = spl_token::state::Mint { decimals: DECIMALS, is_initialized: true, supply: 1_000_000, ..Default::default() };
+    spl_token::state::Mint::pack(mint, &mut data).unwrap();
+    data
+}
+
+fn token_2022_account(authority: &Pubkey, mint: &Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0u8; spl_token_2022::state::Account::LEN];
+    let account = spl_token_2022::state::Account {
+        mint: *mint,
+        owner: *authority,
+        amount,
+        state: spl_token_2022::state::AccountState::Initialized,
+        ..Default::default()
+    };
+    spl_token_2022::state::Account::pack(account, &mut data).unwrap();
+    data
+}
+
+fn token_2022_mint() -> Vec<u8> {
+    let mut data = vec![0u8; spl_token_2022::state::Mint::LEN];
+    let mint = spl_token_2022::state::Mint { decimals: DECIMALS, is_initialized: true, supply: 1_000_000, ..Default::default() };
+    spl_token_2022::state::Mint::pack(mint, &mut data).unwrap();
+    data
+}
+
+/// Runs `transfer_checked(amount, decimals)` from source to destination and
+/// returns the processor result with both balances afterwards
+fn run_spl_token(decimals: u8) -> (Result<(), anchor_lang::prelude::ProgramError>, u64, u64) {
+    let authority = Pubkey::new_unique();
+    let mint_key = Pubkey::new_unique();
+    let mut accounts = setup(
+        spl_token::ID,
+        spl_token_mint(),
+        spl_token_account(&authority, &mint_key, 1_000_000),
+        spl_token_account(&Pubkey::new_unique(), &mint_key, 0),
+    );
+    accounts[0].key = mint_key;
+    accounts[3].key = authority;
+
+    let ix = spl_token::instruction::transfer_checked(
+        &spl_token::ID, &accounts[1].key, &mint_key, &accounts[2].key, &authority, &[], 500_000, decimals,
+    )
+    .unwrap();
+
+    let [mint, source, dest, auth] = &mut accounts;
+    let infos = [source.info(false), mint.info(false), dest.info(false), auth.info(true)];
+    let result = spl_token::processor::Processor::process(&spl_token::ID, &infos, &ix.data);
+    drop(infos);
+
+    let source_amount = spl_token::state::Account::unpack(&source.data).unwrap().amount;
+    let dest_amount = spl_token::state::Account::unpack(&dest.data).unwrap().amount;
+    (result, source_amount, dest_amount)
+}
+
+fn run_token_2022(decimals: u8) -> (Result<(), anchor_lang::prelude::ProgramError>, u64, u64) {
+    let authority = Pubkey::new_unique();
+    let mint_key = Pubkey::new_unique();
+    let mut accounts = setup(
+        spl_token_2022::ID,
+        token_2022_mint(),
+        token_2022_account(&authority, &mint_key, 1_000_000),
+        token_2022_account(&Pubkey::new_unique(), &mint_key, 0),
+    );
+    accounts[0].key = mint_key;
+    accounts[3].key = authority;
+
+    let ix = spl_token_2022::instruction::transfer_checked(
+        &spl_token_2022::ID, &accounts[1].key, &mint_key, &accounts[2].key, &authority, &[], 500_000, decimals,
+    )
+    .unwrap();
+
+    let [mint, source, dest, auth] = &mut accounts;
+    let infos = [source.info(false), mint.info(false), dest.info(false), auth.info(true)];
+    let result = spl_token_2022::processor::Processor::process(&spl_token_2022::ID, &infos, &ix.data);
+    drop(infos);
+
+    let source_amount = spl_token_2022::state::Account::unpack(&source.data).unwrap().amount;
+    let dest_amount = spl_token_2022::state::Account::unpack(&dest.data).unwrap().amount;
+    (result, source_amount, dest_amount)
+}
+
+#[test]
+fn spl_token_transfer_with_mint_decimals_succeeds() {
+    let (result, source, dest) = run_spl_token(DECIMALS);
+    assert!(result.is_ok());
+    assert_eq!((source, dest), (500_000, 500_000));
+}
+
+#[test]
+fn spl_token_transfer_with_wrong_decimals_fails_without_moving_funds() {
+    let (result, source, dest) = run_spl_token(DECIMALS + 3);
+    assert_eq!(result.unwrap_err(), spl_token::error::TokenError::MintDecimalsMismatch.into());
+    assert_eq!((source, dest), (1_000_000, 0));
+}
+
+#[test]
+fn token_2022_transfer_with_mint_decimals_succeeds() {
+    let (result, source, dest) = run_token_2022(DECIMALS);
+    assert!(result.is_ok());
+    assert_eq!((source, dest), (500_000, 500_000));
+}
+
+#[test]
+fn token_2022_transfer_with_wrong_decimals_fails_without_moving_funds() {
+    let (result, source, dest) = run_token_2022(DECIMALS - 1);
+    assert_eq!(result.unwrap_err(), spl_token_2022::error::TokenError::MintDecimalsMismatch.into());
+    assert_eq!((source, dest), (1_000_000, 0));
+}
+
+#[test]
+fn collect_pays_the_recipient_not_the_collector() {
+    // collect's CPI: vault_ata (vault PDA authority) → recipient_ata, owned by a
+    // cold wallet. The collector only signs the instruction; its own token
+    // account isn't part of the transfer and keeps its balance.
+    let vault_pda = Pubkey::new_unique();
+    let cold_wallet = Pubkey::new_unique();
+    let collector = Pubkey::new_unique();
+    let mint_key = Pubkey::new_unique();
+    let mut accounts = setup(
+        spl_token::ID,
+        spl_token_mint(),
+        spl_token_account(&vault_pda, &mint_key, 1_000_000),
+        spl_token_account(&cold_wallet, &mint_key, 0),
+    );
+    accounts[0].key = mint_key;
+    accounts[3].key = vault_pda;
+    let collector_ata = spl_token_account(&collector, &mint_key, 42);
+
+    let ix = spl_token::instruction::transfer_checked(
+        &spl_token::ID, &accounts[1].key, &mint_key, &accounts[2].key, &vault_pda, &[], 500_000, DECIMALS,
+    )
+    .unwrap();
+
+    let [mint, vault_ata, recipient_ata, auth] = &mut accounts;
+    let infos = [vault_ata.info(false), mint.info(false), recipient_ata.info(false), auth.info(true)];
+    spl_token::processor::Processor::process(&spl_token::ID, &infos, &ix.data).unwrap();
+    drop(infos);
+
+    let recipient = spl_token::state::Account::unpack(&recipient_ata.data).unwrap();
+    assert_eq!((recipient.owner, recipient.amount), (cold_wallet, 500_000));
+    assert_eq!(spl_token::state::Account::unpack(&collector_ata).unwrap().amount, 42);
+}
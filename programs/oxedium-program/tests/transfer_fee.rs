@@ -0,0 +1,234 @@
+//! Token-2022 transfer-fee handling.
+//!
+//! There is no program-test harness in this crate, so the staking and swap
+//! legs are mirrored here the same way `trading_simulation.rs` mirrors the
+//! instructions: price the transfer, move tokens minus the mint's fee, then
+//! reconcile and credit the vault with what actually arrived.
+
+use anchor_lang::prelude::{AccountInfo, Pubkey};
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token::{self, spl_token};
+use anchor_spl::token_2022::{
+    self,
+    spl_token_2022::{
+        extension::{
+            transfer_fee::{TransferFee, TransferFeeConfig},
+            BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut,
+        },
+        state::Mint,
+    },
+};
+use oxedium_program::components::{
+    compute_swap_math, derive_swap_accounts, derive_swap_accounts_with_programs, gross_for_transfer,
+    reconcile_received, transfer_fee,
+};
+use oxedium_program::states::Vault;
+use oxedium_program::utils::OxediumError;
+use pyth_solana_receiver_sdk::price_update::PriceFeedMessage;
+
+mod common;
+
+const EPOCH: u64 = 500;
+
+/// Token-2022 mint data with a `TransferFeeConfig` charging `fee_bps`, capped at `max_fee`
+fn fee_mint_data(fee_bps: u16, max_fee: u64) -> Vec<u8> {
+    let space = ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::TransferFeeConfig]).unwrap();
+    let mut data = vec![0u8; space];
+    let mut state = StateWithExtensionsMut::<Mint>::unpack_uninitialized(&mut data).unwrap();
+
+    let fee = TransferFee {
+        epoch: 0.into(),
+        maximum_fee: max_fee.into(),
+        transfer_fee_basis_points: fee_bps.into(),
+    };
+    let config = state.init_extension::<TransferFeeConfig>(true).unwrap();
+    config.older_transfer_fee = fee;
+    config.newer_transfer_fee = fee;
+
+    state.base = Mint { decimals: 6, is_initialized: true, ..Default::default() };
+    state.pack_base();
+    state.init_account_type().unwrap();
+    data
+}
+
+fn classic_mint_data() -> Vec<u8> {
+    let mut data = vec![0u8; spl_token::state::Mint::LEN];
+    let mint = spl_token::state::Mint { decimals: 6, is_initialized: true, ..Default::default() };
+    spl_token::state::Mint::pack(mint, &mut data).unwrap();
+    data
+}
+
+fn mint_info<'a>(key: &'a Pubkey, owner: &'a Pubkey, lamports: &'a mut u64, data: &'a mut [u8]) -> AccountInfo<'a> {
+    AccountInfo::new(key, false, false, lamports, data, owner, false, 0)
+}
+
+fn make_vault(current_balance: u64) -> Vault {
+    Vault {
+        base_fee_bps: 30,
+        max_exit_fee_bps: 0,
+        initial_balance: current_balance,
+        current_balance,
+        ..common::vault()
+    }
+}
+
+fn one_dollar() -> PriceFeedMessage {
+    PriceFeedMessage {
+        feed_id: [0u8; 32],
+        price: 100_000_000,
+        conf: 0,
+        exponent: -8,
+        publish_time: 1_700_000_000,
+        prev_publish_time: 1_699_999_999,
+        ema_price: 100_000_000,
+        ema_conf: 0,
+    }
+}
+
+// --- transfer_fee / gross_for_transfer ---
+
+#[test]
+fn classic_mint_charges_no_transfer_fee() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = classic_mint_data();
+    let info = mint_info(&key, &token::ID, &mut lamports, &mut data);
+
+    assert_eq!(transfer_fee(&info, 1_000_000, EPOCH).unwrap(), 0);
+    assert_eq!(gross_for_transfer(&info, 1_000_000, EPOCH).unwrap(), 1_000_000);
+}
+
+#[test]
+fn token_2022_mint_without_fee_extension_charges_nothing() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = classic_mint_data();
+    let info = mint_info(&key, &token_2022::ID, &mut lamports, &mut data);
+
+    assert_eq!(transfer_fee(&info, 1_000_000, EPOCH).unwrap(), 0);
+}
+
+#[test]
+fn token_2022_fee_mint_withholds_its_fee() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = fee_mint_data(100, u64::MAX); // 1%
+    let info = mint_info(&key, &token_2022::ID, &mut lamports, &mut data);
+
+    assert_eq!(transfer_fee(&info, 1_000_000, EPOCH).unwrap(), 10_000);
+
+    // Sending the gross amount lands at least the requested net
+    let gross = gross_for_transfer(&info, 990_000, EPOCH).unwrap();
+    let net = gross - transfer_fee(&info, gross, EPOCH).unwrap();
+    assert!(net >= 990_000);
+    assert_eq!(gross, 1_000_000);
+}
+
+#[test]
+fn transfer_fee_respects_maximum() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = fee_mint_data(100, 5_000);
+    let info = mint_info(&key, &token_2022::ID, &mut lamports, &mut data);
+
+    assert_eq!(transfer_fee(&info, 1_000_000_000, EPOCH).unwrap(), 5_000);
+}
+
+// --- reconcile_received ---
+
+#[test]
+fn reconcile_returns_amount_that_arrived() {
+    assert_eq!(reconcile_received(1_000, 1_990, 990).unwrap(), 990);
+    // A rounding surplus is credited, not rejected
+    assert_eq!(reconcile_received(1_000, 1_991, 990).unwrap(), 991);
+}
+
+#[test]
+fn reconcile_rejects_short_transfer() {
+    assert_eq!(
+        reconcile_received(1_000, 1_989, 990).unwrap_err(),
+        OxediumError::TransferAmountMismatch.into()
+    );
+    assert_eq!(
+        reconcile_received(1_000, 999, 0).unwrap_err(),
+        OxediumError::TransferAmountMismatch.into()
+    );
+}
+
+// --- instruction mirrors ---
+
+#[test]
+fn staking_fee_mint_credits_net_received() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = fee_mint_data(50, u64::MAX); // 0.5%
+    let info = mint_info(&key, &token_2022::ID, &mut lamports, &mut data);
+
+    let mut vault = make_vault(0);
+    let mut vault_ata_balance: u64 = 0;
+    let mut staked_amount: u64 = 0;
+    let amount = 2_000_000;
+
+    // staking(): price the transfer, move tokens, reconcile
+    let expected = amount - transfer_fee(&info, amount, EPOCH).unwrap();
+    let before = vault_ata_balance;
+    vault_ata_balance += amount - transfer_fee(&info, amount, EPOCH).unwrap();
+    let received = reconcile_received(before, vault_ata_balance, expected).unwrap();
+
+    staked_amount += received;
+    vault.initial_balance += received;
+    vault.current_balance += received;
+
+    // The position, the vault and StakingEvent.amount all carry the net amount
+    assert_eq!(received, 1_990_000);
+    assert_eq!(staked_amount, received);
+    assert_eq!(vault.current_balance, vault_ata_balance);
+    assert_eq!(vault.initial_balance, vault_ata_balance);
+}
+
+#[test]
+fn swap_from_fee_mint_prices_net_input() {
+    let key = Pubkey::new_unique();
+    let mut lamports = 0;
+    let mut data = fee_mint_data(100, u64::MAX); // 1%
+    let info = mint_info(&key, &token_2022::ID, &mut lamports, &mut data);
+
+    let mut vault_in = make_vault(1_000_000_000);
+    let mut vault_ata_in: u64 = 1_000_000_000;
+    let vault_out = make_vault(1_000_000_000);
+    let amount_in = 1_000_000;
+
+    // swap(): the math runs on what the vault will receive, not on amount_in
+    let net_in = amount_in - transfer_fee(&info, amount_in, EPOCH).unwrap();
+    let result = compute_swap_math(net_in, one_dollar(), one_dollar(), 6, 6, &vault_in, &vault_out).unwrap();
+    assert_eq!(result.raw_amount_out, 990_000);
+
+    let before = vault_ata_in;
+    vault_ata_in += amount_in - transfer_fee(&info, amount_in, EPOCH).unwrap();
+    vault_in.current_balance += reconcile_received(before, vault_ata_in, net_in).unwrap();
+
+    assert_eq!(vault_in.current_balance, vault_ata_in);
+}
+
+#[test]
+fn token_2022_atas_use_their_program_in_derivation() {
+    let signer = Pubkey::new_unique();
+    let mint_in = Pubkey::new_unique();
+    let mint_out = Pubkey::new_unique();
+
+    let classic = derive_swap_accounts(&signer, &mint_in, &mint_out);
+    let mixed = derive_swap_accounts_with_programs(&signer, &mint_in, &mint_out, &token::ID, &token_2022::ID);
+
+    assert_eq!(classic.vault_ata_in, mixed.vault_ata_in);
+    assert_eq!(classic.signer_ata_in, mixed.signer_ata_in);
+    assert_ne!(classic.signer_ata_out, mixed.signer_ata_out);
+    assert_eq!(
+        mixed.signer_ata_out,
+        get_associated_token_address_with_program_id(&signer, &mint_out, &token_2022::ID)
+    );
+    assert_eq!(
+        mixed.vault_ata_out,
+        get_associated_token_address_with_program_id(&mixed.vault_pda_out, &mint_out, &token_2022::ID)
+    );
+}
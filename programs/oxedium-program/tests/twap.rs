@@ -0,0 +1,145 @@
+use oxedium_program::components::{check_price_jump, check_twap_deviation, price_cumulative_at, record_price, twap_since};
+use oxedium_program::states::Vault;
+use oxedium_program::utils::{OxediumError, PRICE_JUMP_WINDOW_SECS, TWAP_WINDOW_SECS};
+
+mod common;
+
+const T0: i64 = 1_700_000_000;
+
+fn make_vault(max_twap_deviation_bps: u64) -> Vault {
+    Vault {
+        base_fee_bps: 30,
+        protocol_fee_bps: 5,
+        max_exit_fee_bps: 0,
+        max_twap_deviation_bps,
+        ..common::vault()
+    }
+}
+
+#[test]
+fn accumulator_weights_prices_by_time_held() {
+    let mut vault = make_vault(0);
+    record_price(&mut vault, 100, T0, TWAP_WINDOW_SECS).unwrap();
+    record_price(&mut vault, 200, T0 + 30, TWAP_WINDOW_SECS).unwrap();
+
+    // 100 held for 30s, then 200 held for 10s
+    assert_eq!(price_cumulative_at(&vault, T0 + 40).unwrap(), 100 * 30 + 200 * 10);
+    assert_eq!(twap_since(&vault, 0, T0, T0 + 40).unwrap(), 125);
+}
+
+#[test]
+fn empty_window_returns_last_price() {
+    let mut vault = make_vault(0);
+    record_price(&mut vault, 100, T0, TWAP_WINDOW_SECS).unwrap();
+    assert_eq!(twap_since(&vault, vault.price_cumulative, T0, T0).unwrap(), 100);
+}
+
+#[test]
+fn anchor_rolls_forward_after_window() {
+    let mut vault = make_vault(0);
+    record_price(&mut vault, 100, T0, TWAP_WINDOW_SECS).unwrap();
+    record_price(&mut vault, 100, T0 + 10, TWAP_WINDOW_SECS).unwrap();
+    assert_eq!(vault.twap_anchor_ts, T0);
+
+    record_price(&mut vault, 100, T0 + TWAP_WINDOW_SECS, TWAP_WINDOW_SECS).unwrap();
+    assert_eq!(vault.twap_anchor_ts, T0 + TWAP_WINDOW_SECS);
+    assert_eq!(vault.twap_anchor_cumulative, vault.price_cumulative);
+}
+
+#[test]
+fn spike_swap_reverts_under_tight_deviation_bound() {
+    // 2 % bound; steady $100 swaps every 20s, then a 10 % oracle spike
+    let mut vault = make_vault(200);
+    for i in 0..5 {
+        let now = T0 + i * 20;
+        check_twap_deviation(&vault, 10_000_000_000, now).unwrap();
+        record_price(&mut vault, 10_000_000_000, now, TWAP_WINDOW_SECS).unwrap();
+    }
+
+    let result = check_twap_deviation(&vault, 11_000_000_000, T0 + 100);
+    assert!(matches!(result, Err(OxediumError::TwapDeviation)));
+
+    // A move inside the bound still goes through
+    assert!(check_twap_deviation(&vault, 10_150_000_000, T0 + 100).is_ok());
+}
+
+#[test]
+fn lasting_move_unfreezes_once_the_twap_goes_stale() {
+    // 2 % bound; the oracle moves 10 % for good and every swap reverts
+    let mut vault = make_vault(200);
+    record_price(&mut vault, 10_000_000_000, T0, TWAP_WINDOW_SECS).unwrap();
+    for t in [1, 60, TWAP_WINDOW_SECS - 1] {
+        assert!(matches!(
+            check_twap_deviation(&vault, 11_000_000_000, T0 + t),
+            Err(OxediumError::TwapDeviation)
+        ));
+    }
+
+    // A window after the last accepted swap the TWAP no longer gates
+    let reopen = T0 + TWAP_WINDOW_SECS;
+    check_twap_deviation(&vault, 11_000_000_000, reopen).unwrap();
+    record_price(&mut vault, 11_000_000_000, reopen, TWAP_WINDOW_SECS).unwrap();
+    assert_eq!(vault.twap_anchor_ts, reopen);
+
+    // The re-anchored TWAP tracks the new price and guards it again
+    check_twap_deviation(&vault, 11_000_000_000, reopen + 20).unwrap();
+    record_price(&mut vault, 11_000_000_000, reopen + 20, TWAP_WINDOW_SECS).unwrap();
+    assert!(matches!(
+        check_twap_deviation(&vault, 10_000_000_000, reopen + 40),
+        Err(OxediumError::TwapDeviation)
+    ));
+}
+
+#[test]
+fn deviation_check_disabled_by_zero_bound() {
+    let mut vault = make_vault(0);
+    record_price(&mut vault, 100, T0, TWAP_WINDOW_SECS).unwrap();
+    assert!(check_twap_deviation(&vault, 1_000, T0 + 1).is_ok());
+}
+
+#[test]
+fn first_swap_has_no_twap_to_check_against() {
+    let vault = make_vault(200);
+    assert!(check_twap_deviation(&vault, 10_000_000_000, T0).is_ok());
+}
+
+#[test]
+fn instant_price_jump_trips_the_circuit_breaker() {
+    // 5 % bound; a swap at $100, then a 20 % move 2 seconds later
+    let mut vault = make_vault(0);
+    vault.max_price_jump_bps = 500;
+    record_price(&mut vault, 10_000_000_000, T0, TWAP_WINDOW_SECS).unwrap();
+
+    let result = check_price_jump(&vault, 12_000_000_000, T0 + 2);
+    assert!(matches!(result, Err(OxediumError::PriceJumpTooLarge)));
+    let result = check_price_jump(&vault, 8_000_000_000, T0 + 2);
+    assert!(matches!(result, Err(OxediumError::PriceJumpTooLarge)));
+
+    // A move inside the bound still goes through
+    assert!(check_price_jump(&vault, 10_500_000_000, T0 + 2).is_ok());
+}
+
+#[test]
+fn slow_drift_passes_the_circuit_breaker() {
+    // 1 % per swap, one swap per window: 30 % over the run, never blocked
+    let mut vault = make_vault(0);
+    vault.max_price_jump_bps = 500;
+    let mut price = 10_000_000_000u64;
+    for i in 0..30 {
+        let now = T0 + i * PRICE_JUMP_WINDOW_SECS;
+        check_price_jump(&vault, price, now).unwrap();
+        record_price(&mut vault, price, now, TWAP_WINDOW_SECS).unwrap();
+        price += price / 100;
+    }
+
+    // Once the window has passed, even a large move is not a jump
+    let result = check_price_jump(&vault, price * 2, vault.last_price_ts + PRICE_JUMP_WINDOW_SECS);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn first_swap_has_no_price_to_jump_from() {
+    let mut vault = make_vault(0);
+    vault.max_price_jump_bps = 500;
+    assert!(check_price_jump(&vault, 10_000_000_000, T0).is_ok());
+}
@@ -0,0 +1,24 @@
+use oxedium_program::components::unstake_amount_for_bps;
+use oxedium_program::utils::OxediumError;
+
+#[test]
+fn half_share_is_half_the_position() {
+    assert_eq!(unstake_amount_for_bps(1_000_000, 5_000).unwrap(), 500_000);
+}
+
+#[test]
+fn full_share_is_the_whole_position() {
+    assert_eq!(unstake_amount_for_bps(999_999_999, 10_000).unwrap(), 999_999_999);
+    assert_eq!(unstake_amount_for_bps(u64::MAX, 10_000).unwrap(), u64::MAX);
+}
+
+#[test]
+fn share_rounds_down() {
+    // 333 × 3_333 / 10_000 = 110.99
+    assert_eq!(unstake_amount_for_bps(333, 3_333).unwrap(), 110);
+}
+
+#[test]
+fn share_above_full_position_is_rejected() {
+    assert!(matches!(unstake_amount_for_bps(1_000, 10_001), Err(OxediumError::InvalidUnstakeBps)));
+}
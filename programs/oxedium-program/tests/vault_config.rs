@@ -0,0 +1,120 @@
+use anchor_lang::prelude::{AnchorDeserialize, Pubkey};
+use anchor_lang::{Discriminator, Event};
+use oxedium_program::components::{apply_vault_config, merge_vault_config, vault_config, vault_init_event, vault_update_event, VaultConfig, VaultConfigUpdate};
+use oxedium_program::events::{VaultInitEvent, VaultUpdateEvent};
+use oxedium_program::states::Vault;
+use oxedium_program::utils::OxediumError;
+
+mod common;
+
+fn config() -> VaultConfig {
+    VaultConfig {
+        base_fee_bps: 30,
+        protocol_fee_bps: 5,
+        protocol_fee_follows_curve: true,
+        max_protocol_fee_bps: 50,
+        max_exit_fee_bps: 500,
+        impact_threshold_bps: 2_000,
+        max_age_price: 60,
+        use_ema_price: true,
+        max_twap_deviation_bps: 300,
+        require_nonzero_conf: true,
+        max_conf_bps: 100,
+        dust_floor: 10,
+        max_total_deposits: 1_000_000,
+        min_stake_amount: 1_000,
+        min_swap_output: 5,
+        max_referral_fee_bps: 2_000,
+        lockup_active: true,
+        lockup_duration: 86_400,
+        max_lockup_penalty_bps: 300,
+        max_oracle_skew: 30,
+        fallback_pyth_price_account: Pubkey::new_from_array([7; 32]),
+    }
+}
+
+fn configured_vault() -> Vault {
+    let mut vault = common::vault();
+    apply_vault_config(&mut vault, &config()).unwrap();
+    vault
+}
+
+/// update_vault's write: merge the passed settings over the stored ones, then apply
+fn update(vault: &mut Vault, update: VaultConfigUpdate) -> anchor_lang::Result<()> {
+    let config = merge_vault_config(vault_config(vault), update);
+    apply_vault_config(vault, &config)
+}
+
+#[test]
+fn applied_config_reads_back_unchanged() {
+    let vault = configured_vault();
+
+    assert_eq!(vault_config(&vault), config());
+    assert_eq!((vault.base_fee_bps, vault.max_exit_fee_bps, vault.max_age_price), (30, 500, 60));
+}
+
+#[test]
+fn update_changes_only_the_passed_settings() {
+    let mut vault = configured_vault();
+    let before = vault_config(&vault);
+
+    update(&mut vault, VaultConfigUpdate { base_fee_bps: Some(45), lockup_active: Some(false), ..Default::default() }).unwrap();
+
+    let after = vault_config(&vault);
+    assert_eq!((after.base_fee_bps, after.lockup_active), (45, false));
+    assert_eq!(after, VaultConfig { base_fee_bps: 45, lockup_active: false, ..before });
+}
+
+#[test]
+fn empty_update_leaves_the_config_alone() {
+    let before = config();
+
+    assert_eq!(merge_vault_config(before, VaultConfigUpdate::default()), before);
+}
+
+#[test]
+fn update_is_checked_against_the_merged_config() {
+    let mut vault = configured_vault();
+
+    // Below the stored protocol fee of 5, so the merged config is invalid
+    let result = update(&mut vault, VaultConfigUpdate { max_protocol_fee_bps: Some(4), ..Default::default() });
+    assert_eq!(result.unwrap_err(), OxediumError::FeeExceeds.into());
+
+    let result = update(&mut vault, VaultConfigUpdate { lockup_duration: Some(-1), ..Default::default() });
+    assert_eq!(result.unwrap_err(), OxediumError::InvalidLockup.into());
+
+    // A rejected update writes nothing
+    assert_eq!(vault_config(&vault), config());
+}
+
+// --- events ---
+
+#[test]
+fn init_event_carries_the_whole_config() {
+    let mut vault = configured_vault();
+    vault.token_mint = Pubkey::new_unique();
+    vault.pyth_price_account = Pubkey::new_unique();
+    vault.feed_id = [3; 32];
+
+    let data = vault_init_event(&vault, 1_700_000_000, 42).data();
+    assert_eq!(&data[..8], VaultInitEvent::DISCRIMINATOR);
+    let event = VaultInitEvent::try_from_slice(&data[8..]).unwrap();
+
+    assert_eq!((event.mint, event.pyth_price_account, event.feed_id), (vault.token_mint, vault.pyth_price_account, [3; 32]));
+    assert_eq!(event.config, config());
+    assert_eq!((event.timestamp, event.slot), (1_700_000_000, 42));
+}
+
+#[test]
+fn update_event_reports_changed_and_unchanged_settings() {
+    let mut vault = configured_vault();
+    update(&mut vault, VaultConfigUpdate { max_exit_fee_bps: Some(200), min_swap_output: Some(0), ..Default::default() }).unwrap();
+
+    let data = vault_update_event(&vault, 1_700_000_100, 43).data();
+    assert_eq!(&data[..8], VaultUpdateEvent::DISCRIMINATOR);
+    assert_ne!(VaultUpdateEvent::DISCRIMINATOR, VaultInitEvent::DISCRIMINATOR);
+    let event = VaultUpdateEvent::try_from_slice(&data[8..]).unwrap();
+
+    assert_eq!(event.config, VaultConfig { max_exit_fee_bps: 200, min_swap_output: 0, ..config() });
+    assert_eq!((event.timestamp, event.slot), (1_700_000_100, 43));
+}
@@ -0,0 +1,75 @@
+use oxedium_program::states::Vault;
+
+mod common;
+
+fn make_vault(initial_balance: u64, current_balance: u64) -> Vault {
+    Vault {
+        base_fee_bps: 30,
+        protocol_fee_bps: 10,
+        initial_balance,
+        current_balance,
+        ..common::vault()
+    }
+}
+
+// --- health_bps ---
+
+#[test]
+fn vault_without_lp_principal_is_fully_healthy() {
+    assert_eq!(make_vault(0, 0).health_bps(), 10_000);
+    assert_eq!(make_vault(0, 5_000).health_bps(), 10_000);
+}
+
+#[test]
+fn balanced_vault_is_at_full_health() {
+    assert_eq!(make_vault(18_000_000_000, 18_000_000_000).health_bps(), 10_000);
+}
+
+#[test]
+fn distressed_vault_health_matches_exit_fee_scenario() {
+    // 8 000 of 18 000 USDC left, as in the simulation's Phase 9
+    let vault = make_vault(18_000_000_000, 8_000_000_000);
+    assert_eq!(vault.health_bps(), 4_444);
+    assert_eq!(vault.health_bps() / 100, 44);
+}
+
+#[test]
+fn inflows_push_health_above_full() {
+    assert_eq!(make_vault(10_000, 15_000).health_bps(), 15_000);
+    assert_eq!(make_vault(1, u64::MAX).health_bps(), u64::MAX);
+}
+
+// --- utilization_bps ---
+
+#[test]
+fn empty_vault_is_fully_utilized() {
+    assert_eq!(make_vault(10_000, 0).utilization_bps(0), 10_000);
+    assert_eq!(make_vault(10_000, 0).utilization_bps(1), 10_000);
+}
+
+#[test]
+fn utilization_is_share_of_current_balance() {
+    let vault = make_vault(10_000_000, 8_000_000);
+    assert_eq!(vault.utilization_bps(0), 0);
+    assert_eq!(vault.utilization_bps(800_000), 1_000);
+    assert_eq!(vault.utilization_bps(8_000_000), 10_000);
+}
+
+#[test]
+fn utilization_is_capped_at_full() {
+    assert_eq!(make_vault(10_000, 1_000).utilization_bps(u64::MAX), 10_000);
+}
+
+// --- share_bps ---
+
+#[test]
+fn share_is_position_over_lp_principal() {
+    let vault = make_vault(10_000_000, 4_000_000);
+    assert_eq!(vault.share_bps(2_500_000), 2_500);
+    assert_eq!(vault.share_bps(10_000_000), 10_000);
+}
+
+#[test]
+fn vault_without_lp_principal_gives_no_share() {
+    assert_eq!(make_vault(0, 5_000).share_bps(1_000), 0);
+}